@@ -1,25 +1,98 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, ListingMsg, ListingQuery, MigrateMsg, QueryMsg};
+use crate::msg::{
+    AttestationMsg, AttestationResponse, ChainMsg, DiffResponse, EventSchema, EventsSchemaResponse,
+    ExecuteMsg, FieldChange, InstantiateMsg, ListingMsg, ListingQuery, MigrateMsg, PendingMsg,
+    QueryMsg, StorageStatsResponse, SudoMsg, VerifierMsg,
+};
 use crate::state::Listing;
 use crate::state::{
-    Config, Field,
+    add_entries, listings, Attestation, ChainInfo, Config, Deposit, EscrowedFee, ExtensionLimits,
+    Field,
     Field::{Chain, Exp, Logo},
-    Metadata, CONFIG, DENOM_MAP, SYMBOL_MAP,
+    ListingStatus, Metadata, PendingAction, PendingChange, ATTESTATIONS, CHAINS, CONFIG, DEPOSITS,
+    ENTRY_COUNTS, ESCROW_TOTALS, FEE_ESCROW, HISTORY, LEGACY_SYMBOL_MAP, LISTINGS_COUNT,
+    PENDING_ACTIONS, PENDING_ID, VERIFIERS,
 };
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order,
-    Response, StdError, StdResult, Uint128,
+    to_json_binary, Addr, BankMsg, Binary, Coin, Coins, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Timestamp, Uint128,
 };
 use cw2::{get_contract_version, set_contract_version};
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, PrefixBound};
 
 // version info for migration info
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// How long a pending action can be confirmed for when no period is configured
+const DEFAULT_CONFIRM_PERIOD: u64 = 86_400;
+
 // Pagination for queries
 const MAX_PAGE_LIMIT: u32 = 250;
 
+// The actions emitted by this contract version and the attribute keys that accompany them
+const EVENTS: &[(&str, &[&str])] = &[
+    ("assetlist_instantiate", &[]),
+    (
+        "assetlist_add_listings",
+        &["status", "deposit", "refund", "forwarded"],
+    ),
+    (
+        "assetlist_update_listings",
+        &["status", "refund", "forwarded"],
+    ),
+    ("assetlist_approve_listings", &[]),
+    ("assetlist_reject_listings", &["reason"]),
+    (
+        "assetlist_remove_listings",
+        &["refund_denom", "refund_recipient", "refund_amount"],
+    ),
+    (
+        "assetlist_slash_listings",
+        &["reason", "slashed", "forwarded"],
+    ),
+    (
+        "assetlist_refund_deposits",
+        &["refund_denom", "refund_recipient", "refund_amount"],
+    ),
+    (
+        "assetlist_renew_listings",
+        &["expires", "refund", "forwarded"],
+    ),
+    (
+        "assetlist_prune_expired",
+        &[
+            "pruned",
+            "refund_denom",
+            "refund_recipient",
+            "refund_amount",
+        ],
+    ),
+    ("assetlist_set_verified", &["verified"]),
+    ("assetlist_set_chains", &[]),
+    ("assetlist_remove_chains", &[]),
+    ("assetlist_set_verifiers", &[]),
+    ("assetlist_remove_verifiers", &[]),
+    ("assetlist_attest", &["denom", "verifier", "kind"]),
+    (
+        "assetlist_revoke_attestation",
+        &["denom", "verifier", "kind"],
+    ),
+    ("assetlist_update_config", &[]),
+    ("assetlist_clear_fee_collector", &[]),
+    ("assetlist_propose", &["pending_id", "expires"]),
+    ("assetlist_confirm", &["pending_id"]),
+    ("assetlist_cancel", &["pending_id"]),
+    ("assetlist_prune_history", &["denom", "pruned"]),
+    ("assetlist_withdraw_fees", &["to", "amount"]),
+    (
+        "assetlist_governance_remove_listings",
+        &["refund_denom", "refund_recipient", "refund_amount"],
+    ),
+];
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -36,6 +109,10 @@ pub fn instantiate(
     }
     admins.push(info.sender.clone());
 
+    if let Some(fee_collector) = &msg.0.fee_collector {
+        deps.api.addr_validate(fee_collector.as_str())?;
+    }
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -43,8 +120,17 @@ pub fn instantiate(
             remove_permissioned: msg.0.remove_permissioned,
             required_fields: msg.0.required_fields,
             fee: msg.0.fee,
+            deposit: msg.0.deposit,
+            update_fee: msg.0.update_fee,
+            renewal_fee: msg.0.renewal_fee,
+            listing_ttl: msg.0.listing_ttl,
+            fee_collector: msg.0.fee_collector,
             admins: Some(admins),
             owner: Some(msg.0.owner.unwrap_or(info.sender)),
+            confirm_threshold: msg.0.confirm_threshold,
+            confirm_period: msg.0.confirm_period,
+            review: msg.0.review,
+            extension_limits: msg.0.extension_limits,
         },
     )?;
 
@@ -54,7 +140,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -69,74 +155,289 @@ pub fn execute(
         ExecuteMsg::Listing(msg) => match msg {
             ListingMsg::Add(listings) => execute_add_listings(
                 deps,
+                &env,
                 &info.sender,
                 &info.funds,
                 config.fee,
+                config.deposit,
+                config.fee_collector,
+                config.listing_ttl,
                 admin,
                 config.add_permissioned.unwrap_or_default(),
+                config.review.unwrap_or_default(),
                 &config.required_fields.unwrap_or_default(),
+                &config.extension_limits.unwrap_or_default(),
                 listings,
             ),
             ListingMsg::Update(updates) => execute_update_listings(
                 deps,
+                env.block.height,
                 &info.sender,
+                &info.funds,
+                config.update_fee,
+                config.fee_collector,
                 admin,
                 config.add_permissioned.unwrap_or_default(),
+                config.review.unwrap_or_default(),
                 &config.required_fields.unwrap_or_default(),
+                &config.extension_limits.unwrap_or_default(),
                 updates,
             ),
+            // large admin removals have to be confirmed by a second admin
+            ListingMsg::Remove(denoms)
+                if admin
+                    && config
+                        .confirm_threshold
+                        .is_some_and(|threshold| denoms.len() > threshold as usize) =>
+            {
+                execute_propose(
+                    deps,
+                    env,
+                    &config,
+                    info.sender,
+                    PendingChange::RemoveListings(denoms),
+                )
+            }
             ListingMsg::Remove(denoms) => execute_remove_listings(
                 deps,
+                env.block.height,
                 &info.sender,
                 admin,
                 config.remove_permissioned.unwrap_or_default(),
                 denoms,
             ),
+            ListingMsg::Slash { denoms, reason } => {
+                if !admin {
+                    return Err(ContractError::DepositPermissioned);
+                }
+                // large slashes have to be confirmed by a second admin, like large removals
+                if config
+                    .confirm_threshold
+                    .is_some_and(|threshold| denoms.len() > threshold as usize)
+                {
+                    return execute_propose(
+                        deps,
+                        env,
+                        &config,
+                        info.sender,
+                        PendingChange::SlashListings { denoms, reason },
+                    );
+                }
+                execute_slash_listings(deps, env.block.height, config.fee_collector, denoms, reason)
+            }
+            ListingMsg::RefundDeposits(denoms) => {
+                if !admin {
+                    return Err(ContractError::DepositPermissioned);
+                }
+                execute_refund_deposits(deps, denoms)
+            }
+            ListingMsg::Renew(denoms) => execute_renew_listings(
+                deps,
+                &env,
+                &info.sender,
+                &info.funds,
+                config.renewal_fee,
+                config.fee_collector,
+                config.listing_ttl,
+                admin,
+                denoms,
+            ),
+            ListingMsg::PruneExpired { limit } => execute_prune_expired(deps, env, limit),
+            ListingMsg::Approve(denoms) => {
+                if !admin {
+                    return Err(ContractError::ReviewPermissioned);
+                }
+                execute_review_listings(deps, env.block.height, denoms, None)
+            }
+            ListingMsg::Reject { denoms, reason } => {
+                if !admin {
+                    return Err(ContractError::ReviewPermissioned);
+                }
+                execute_review_listings(deps, env.block.height, denoms, Some(reason))
+            }
+            ListingMsg::SetVerified { denoms, verified } => {
+                if !admin {
+                    return Err(ContractError::VerifyPermissioned);
+                }
+                execute_set_verified(deps, denoms, verified)
+            }
         },
+        ExecuteMsg::Chain(msg) => {
+            // the chain registry is curated, so only admins can manage it
+            if !admin {
+                return Err(ContractError::ChainPermissioned);
+            }
+            match msg {
+                ChainMsg::Set(chains) => execute_set_chains(deps, chains),
+                ChainMsg::Remove(chain_ids) => execute_remove_chains(deps, chain_ids),
+            }
+        }
+        ExecuteMsg::Verifier(msg) => {
+            if !admin {
+                return Err(ContractError::VerifierPermissioned);
+            }
+            match msg {
+                VerifierMsg::Set(verifiers) => execute_set_verifiers(deps, verifiers),
+                VerifierMsg::Remove(addresses) => execute_remove_verifiers(deps, addresses),
+            }
+        }
+        ExecuteMsg::Attestation(msg) => {
+            if !VERIFIERS.has(deps.storage, &info.sender) {
+                return Err(ContractError::NotVerifier);
+            }
+            match msg {
+                AttestationMsg::Add {
+                    denom,
+                    kind,
+                    attestation,
+                } => execute_attest(deps, &info.sender, denom, kind, attestation),
+                AttestationMsg::Revoke { denom, kind } => {
+                    execute_revoke_attestation(deps, &info.sender, denom, kind)
+                }
+            }
+        }
+        ExecuteMsg::Pending(msg) => {
+            if !admin {
+                return Err(ContractError::Unauthorized);
+            }
+            match msg {
+                PendingMsg::Confirm(id) => execute_confirm(deps, env, config, &info.sender, id),
+                PendingMsg::Cancel(id) => execute_cancel(deps, id),
+            }
+        }
+        ExecuteMsg::PruneHistory {
+            denoms,
+            keep_last,
+            before_height,
+        } => {
+            if Some(&info.sender) != config.owner.as_ref() {
+                return Err(ContractError::NotOwner);
+            }
+            execute_prune_history(deps, denoms, keep_last, before_height)
+        }
+        ExecuteMsg::WithdrawFees { to, amount } => {
+            if Some(&info.sender) != config.owner.as_ref() {
+                return Err(ContractError::NotOwner);
+            }
+            execute_withdraw_fees(deps, env, to, amount)
+        }
+        // a fee collector can't be cleared through UpdateConfig, where None leaves it unchanged.
+        // like any other change to it, clearing it has to be confirmed by a second admin
+        ExecuteMsg::ClearFeeCollector => {
+            if Some(&info.sender) != config.owner.as_ref() {
+                return Err(ContractError::NotOwner);
+            }
+            if config.confirm_threshold.is_some() && config.fee_collector.is_some() {
+                return execute_propose(
+                    deps,
+                    env,
+                    &config,
+                    info.sender,
+                    PendingChange::ClearFeeCollector,
+                );
+            }
+            execute_clear_fee_collector(deps, config)
+        }
+        // high impact config changes have to be confirmed by a second admin
+        ExecuteMsg::UpdateConfig(new_config) if needs_confirmation(&config, &new_config) => {
+            if Some(&info.sender) != config.owner.as_ref() {
+                return Err(ContractError::NotOwner);
+            }
+            execute_propose(
+                deps,
+                env,
+                &config,
+                info.sender,
+                PendingChange::UpdateConfig(Box::new(new_config)),
+            )
+        }
         ExecuteMsg::UpdateConfig(mut new_config) => {
             execute_update_config(deps, &info.sender, config, &mut new_config)
         }
     }
 }
 
+// whether a config update changes the fees, deposit, fee collector or owner, or the admins and
+// confirmation settings that would otherwise let a single owner undo the two admin rule
+fn needs_confirmation(config: &Config, new_config: &Config) -> bool {
+    fn changed<T: PartialEq>(new: &Option<T>, old: &Option<T>) -> bool {
+        new.as_ref().is_some_and(|new| Some(new) != old.as_ref())
+    }
+
+    // the owner is always kept as an admin, so it isn't expected in the new list
+    let admins_changed = new_config.admins.as_ref().is_some_and(|admins| {
+        let current = config.admins.clone().unwrap_or_default();
+        admins.iter().any(|admin| !current.contains(admin))
+            || current
+                .iter()
+                .any(|admin| Some(admin) != config.owner.as_ref() && !admins.contains(admin))
+    });
+
+    config.confirm_threshold.is_some()
+        && (changed(&new_config.fee, &config.fee)
+            || changed(&new_config.deposit, &config.deposit)
+            || changed(&new_config.update_fee, &config.update_fee)
+            || changed(&new_config.renewal_fee, &config.renewal_fee)
+            || changed(&new_config.fee_collector, &config.fee_collector)
+            || changed(&new_config.owner, &config.owner)
+            || changed(&new_config.confirm_threshold, &config.confirm_threshold)
+            || changed(&new_config.confirm_period, &config.confirm_period)
+            || admins_changed)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn execute_add_listings(
     deps: DepsMut,
+    env: &Env,
     sender: &Addr,
     funds: &[Coin],
     fee: Option<Vec<Coin>>,
+    deposit: Option<Vec<Coin>>,
+    fee_collector: Option<Addr>,
+    listing_ttl: Option<u64>,
     admin: bool,
     permissioned: bool,
+    review: bool,
     required_fields: &[Field],
+    extension_limits: &ExtensionLimits,
     new_listings: Vec<(String, Metadata)>,
 ) -> Result<Response, ContractError> {
-    if permissioned && !admin {
+    // in review mode submissions are queued instead of rejected
+    if permissioned && !admin && !review {
         return Err(ContractError::AddPermissioned);
     }
+    let status = if review && !admin {
+        ListingStatus::Pending
+    } else {
+        ListingStatus::Approved
+    };
 
-    // validate that the sender has paid the fee if required. Admins are exempt
-    if !admin && fee.is_some() {
-        if funds.is_empty() {
-            return Err(ContractError::MissingFee);
-        }
-
-        // for simplicity, although we can accept multiple fee coins we will only allow one to be used per tx
-        if funds.len() > 1 {
-            return Err(ContractError::MultipleFees);
-        }
+    // the per-listing deposit held, which replaces the fee when deposits are taken
+    let deposit = check_fee(deposit.as_deref(), funds, new_listings.len(), admin)?;
+    // the per-listing fee paid, escrowed so it can be refunded if governance takes the listing down
+    let listing_fee = match deposit {
+        Some(_) => None,
+        None => check_fee(fee.as_deref(), funds, new_listings.len(), admin)?,
+    };
 
-        if let Some(fee_token) = fee
-            .unwrap()
-            .iter()
-            .find(|coin| coin.denom == funds[0].denom)
-        {
-            if fee_token.amount * Uint128::from(new_listings.len() as u128) > funds[0].amount {
-                return Err(ContractError::InsufficientFee);
-            }
-        } else {
-            return Err(ContractError::InvalidFee);
-        }
+    let mut response = Response::new()
+        .add_attribute("action", "assetlist_add_listings")
+        .add_attribute("status", status.as_str());
+    if let Some(deposit) = &deposit {
+        response = response.add_attribute("deposit", deposit.to_string());
     }
+    // deposits are always held by the contract, never forwarded
+    let (response, forwarded) = settle_fees(
+        response,
+        sender,
+        funds,
+        deposit.as_ref().or(listing_fee.as_ref()),
+        new_listings.len(),
+        fee_collector.filter(|_| deposit.is_none()),
+    );
+    // a fee forwarded to the collector leaves nothing to escrow
+    let listing_fee = listing_fee.filter(|_| !forwarded);
+    let expires = listing_ttl.map(|ttl| env.block.time.plus_seconds(ttl));
 
     // validate new listings
     for listing in new_listings {
@@ -144,17 +445,20 @@ fn execute_add_listings(
         let metadata = listing.1.clone();
 
         // we don't want to allow duplicate listings by denom or symbol as they will be used as keys
-        if DENOM_MAP.has(deps.storage, denom.clone()) {
+        if listings().has(deps.storage, denom.clone()) {
             return Err(ContractError::DuplicateListing(denom));
         }
 
-        if SYMBOL_MAP.has(deps.storage, metadata.symbol.clone()) {
+        if symbol_taken(deps.storage, &metadata.symbol, &denom)? {
             return Err(ContractError::DuplicateListing(metadata.symbol));
         }
 
         check_required_fields(required_fields, &metadata)?;
+        check_extensions(extension_limits, &metadata)?;
+        check_chain(deps.as_ref(), &metadata)?;
 
-        DENOM_MAP.save(
+        add_entries(deps.storage, LISTINGS_COUNT, 1)?;
+        listings().save(
             deps.storage,
             denom.clone(),
             &Listing {
@@ -164,26 +468,147 @@ fn execute_add_listings(
                     Some(sender.to_string())
                 },
                 metadata: metadata.clone(),
+                verified: false,
+                status: status.clone(),
+                rejection_reason: None,
+                pending_edit: None,
+                expires,
             },
         )?;
-        SYMBOL_MAP.save(deps.storage, metadata.symbol, &denom)?;
+        HISTORY.save(
+            deps.storage,
+            (denom.clone(), env.block.height),
+            &Some(metadata),
+        )?;
+
+        if let Some(deposit) = &deposit {
+            hold_deposit(deps.storage, denom, sender, deposit)?;
+        } else if let Some(listing_fee) = &listing_fee {
+            escrow_fee(deps.storage, denom, sender, listing_fee)?;
+        }
+    }
+
+    Ok(response)
+}
+
+// the per-listing fee paid in one of the accepted tokens, if one is charged. Admins are exempt
+fn check_fee(
+    fee: Option<&[Coin]>,
+    funds: &[Coin],
+    count: usize,
+    admin: bool,
+) -> Result<Option<Coin>, ContractError> {
+    let Some(fee) = fee.filter(|_| !admin) else {
+        return Ok(None);
+    };
+
+    if funds.is_empty() {
+        return Err(ContractError::MissingFee);
+    }
+
+    // for simplicity, although we can accept multiple fee coins we will only allow one to be used per tx
+    if funds.len() > 1 {
+        return Err(ContractError::MultipleFees);
+    }
+
+    let Some(fee_token) = fee.iter().find(|coin| coin.denom == funds[0].denom) else {
+        return Err(ContractError::InvalidFee);
+    };
+    if fee_token.amount * Uint128::from(count as u128) > funds[0].amount {
+        return Err(ContractError::InsufficientFee);
+    }
+
+    Ok(Some(fee_token.clone()))
+}
+
+// returns anything attached beyond the fees owed, and forwards the fees to the fee collector if
+// one is set. also returns whether they were forwarded
+fn settle_fees(
+    mut response: Response,
+    sender: &Addr,
+    funds: &[Coin],
+    listing_fee: Option<&Coin>,
+    count: usize,
+    fee_collector: Option<Addr>,
+) -> (Response, bool) {
+    let owed = listing_fee.map(|fee| Coin {
+        denom: fee.denom.clone(),
+        amount: fee.amount * Uint128::from(count as u128),
+    });
+
+    let excess: Vec<Coin> = funds
+        .iter()
+        .map(|coin| Coin {
+            denom: coin.denom.clone(),
+            amount: match &owed {
+                Some(owed) if owed.denom == coin.denom => coin.amount - owed.amount,
+                _ => coin.amount,
+            },
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+    if !excess.is_empty() {
+        response = response
+            .add_attribute(
+                "refund",
+                excess
+                    .iter()
+                    .map(Coin::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+            .add_message(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: excess,
+            });
     }
 
-    Ok(Response::new().add_attribute("action", "assetlist_add_listings"))
+    match (fee_collector, owed) {
+        (Some(fee_collector), Some(owed)) if !owed.amount.is_zero() => (
+            response
+                .add_attribute("forwarded", owed.to_string())
+                .add_message(BankMsg::Send {
+                    to_address: fee_collector.into_string(),
+                    amount: vec![owed],
+                }),
+            true,
+        ),
+        _ => (response, false),
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_update_listings(
     deps: DepsMut,
+    height: u64,
     sender: &Addr,
+    funds: &[Coin],
+    update_fee: Option<Vec<Coin>>,
+    fee_collector: Option<Addr>,
     admin: bool,
     permissioned: bool,
+    review: bool,
     required_fields: &[Field],
+    extension_limits: &ExtensionLimits,
     updated_listings: Vec<(String, Metadata)>,
 ) -> Result<Response, ContractError> {
     // remove must be permissionless in order for creators to edit their own listings
-    if permissioned && !admin {
+    if permissioned && !admin && !review {
         return Err(ContractError::RemovePermissioned);
     }
+    // edits by authors in review mode have to be approved again
+    let pending = review && !admin;
+
+    // update fees are kept or forwarded, but not escrowed
+    let update_fee = check_fee(update_fee.as_deref(), funds, updated_listings.len(), admin)?;
+    let (mut response, _) = settle_fees(
+        Response::new().add_attribute("action", "assetlist_update_listings"),
+        sender,
+        funds,
+        update_fee.as_ref(),
+        updated_listings.len(),
+        fee_collector,
+    );
 
     // validate updated listings
     for update in updated_listings {
@@ -191,45 +616,86 @@ fn execute_update_listings(
         let denom = update.0.clone();
         let metadata = update.1.clone();
 
-        let Ok(current_listing) = DENOM_MAP.load(deps.storage, denom.clone()) else {
+        let Ok(current_listing) = listings().load(deps.storage, denom.clone()) else {
             return Err(ContractError::ListingNotFound(denom));
         };
 
         // make sure the sender is the creator of the listing or an admin
-        if current_listing.author.unwrap_or_default() != *sender && !admin {
+        if current_listing.author.as_deref() != Some(sender.as_str()) && !admin {
             return Err(ContractError::Unauthorized);
         }
 
-        // make sure the new symbol is not already in use for a different denom
-        if current_listing.metadata.symbol != metadata.symbol
-            && SYMBOL_MAP.has(deps.storage, metadata.symbol.clone())
-        {
+        // make sure the symbol is not already in use for a different denom. a symbol freed by a
+        // rejection may have been taken since
+        if symbol_taken(deps.storage, &metadata.symbol, &denom)? {
             return Err(ContractError::DuplicateListing(metadata.symbol));
         }
 
         check_required_fields(required_fields, &metadata)?;
+        check_extensions(extension_limits, &metadata)?;
+        check_chain(deps.as_ref(), &metadata)?;
 
-        DENOM_MAP.save(
+        let author = if admin {
+            None
+        } else {
+            Some(sender.to_string())
+        };
+
+        // an edit to a live listing waits for review without replacing its approved metadata
+        if pending && current_listing.approved() {
+            listings().save(
+                deps.storage,
+                denom,
+                &Listing {
+                    author,
+                    status: ListingStatus::Pending,
+                    rejection_reason: None,
+                    pending_edit: Some(metadata),
+                    ..current_listing
+                },
+            )?;
+            continue;
+        }
+
+        listings().save(
             deps.storage,
             denom.clone(),
             &Listing {
-                author: if admin {
+                author,
+                metadata: metadata.clone(),
+                // the metadata an admin verified can't be changed by the author
+                verified: admin && current_listing.verified,
+                status: if pending {
+                    ListingStatus::Pending
+                } else if current_listing.pending_edit.is_some() {
+                    // an admin edit replaces the edit waiting for review
+                    ListingStatus::Approved
+                } else {
+                    current_listing.status
+                },
+                rejection_reason: if pending {
                     None
                 } else {
-                    Some(sender.to_string())
+                    current_listing.rejection_reason
                 },
-                metadata: metadata.clone(),
+                pending_edit: None,
+                expires: current_listing.expires,
             },
         )?;
 
-        SYMBOL_MAP.save(deps.storage, metadata.symbol, &denom)?;
+        HISTORY.save(deps.storage, (denom, height), &Some(metadata))?;
     }
 
-    Ok(Response::new().add_attribute("action", "assetlist_update_listings"))
+    if pending {
+        response = response.add_attribute("status", ListingStatus::Pending.as_str());
+    }
+
+    Ok(response)
 }
 
 fn execute_remove_listings(
     deps: DepsMut,
+    height: u64,
     sender: &Addr,
     admin: bool,
     permissioned: bool,
@@ -239,8 +705,10 @@ fn execute_remove_listings(
         return Err(ContractError::RemovePermissioned);
     }
 
+    let mut response = Response::new().add_attribute("action", "assetlist_remove_listings");
+
     for denom in denoms {
-        let Ok(listing) = DENOM_MAP.load(deps.storage, denom.clone()) else {
+        let Ok(listing) = listings().load(deps.storage, denom.clone()) else {
             return Err(ContractError::ListingNotFound(denom));
         };
 
@@ -249,164 +717,1279 @@ fn execute_remove_listings(
             return Err(ContractError::Unauthorized);
         }
 
-        // remove the listing by denom and symbol
-        DENOM_MAP.remove(deps.storage, denom.clone());
-        SYMBOL_MAP.remove(deps.storage, listing.metadata.symbol);
+        // fees are not refunded for voluntary removals, but deposits are
+        let (_, deposit) = delete_listing(deps.storage, denom.clone(), height)?;
+        if let Some(deposit) = deposit {
+            response = refund(response, denom, deposit.depositor, deposit.amount);
+        }
     }
 
-    Ok(Response::new().add_attribute("action", "assetlist_remove_listings"))
+    Ok(response)
 }
 
-fn execute_update_config(
+// removes spam or scam listings. their deposits are kept, or forwarded to the fee collector
+fn execute_slash_listings(
     deps: DepsMut,
-    sender: &Addr,
-    old_config: Config,
-    new_config: &mut Config,
+    height: u64,
+    fee_collector: Option<Addr>,
+    denoms: Vec<String>,
+    reason: String,
 ) -> Result<Response, ContractError> {
-    // only the owner can update the config
-    if sender != old_config.owner.clone().unwrap() {
-        return Err(ContractError::NotOwner);
-    }
-
-    // set the new owner or default to the current owner
-    new_config.owner = match new_config.owner.clone() {
-        Some(owner) => {
-            deps.api.addr_validate(owner.as_str())?;
-            Some(owner)
-        }
-        None => old_config.owner.clone(),
-    };
+    let mut slashed = Coins::default();
 
-    // validate the new admins. if provided, will overwrite the current list
-    // (an empty list will clear all admins)
-    if let Some(mut admins) = new_config.admins.clone() {
-        for address in &admins {
-            deps.api.addr_validate(address.as_str())?;
+    for denom in denoms {
+        if !listings().has(deps.storage, denom.clone()) {
+            return Err(ContractError::ListingNotFound(denom));
         }
-        // add the owner to the list of admins if it has changed
-        admins.push(new_config.owner.clone().unwrap());
-        new_config.admins = Some(admins);
-    } else {
-        new_config.admins = old_config.admins.clone();
+        let (_, Some(deposit)) = delete_listing(deps.storage, denom.clone(), height)? else {
+            return Err(ContractError::NoDeposit(denom));
+        };
+        slashed.add(deposit.amount)?;
     }
 
-    // if other fields are None, they will not be updated
-    new_config.add_permissioned = new_config
-        .add_permissioned
-        .take()
-        .or(old_config.add_permissioned);
-
-    new_config.remove_permissioned = new_config
-        .remove_permissioned
-        .take()
-        .or(old_config.remove_permissioned);
-
-    new_config.required_fields = new_config
-        .required_fields
-        .take()
-        .or(old_config.required_fields);
-
-    new_config.fee = new_config.fee.take().or(old_config.fee);
-
-    CONFIG.save(deps.storage, new_config)?;
+    let response = Response::new()
+        .add_attribute("action", "assetlist_slash_listings")
+        .add_attribute("reason", reason)
+        .add_attribute("slashed", slashed.to_string());
 
-    Ok(Response::new().add_attribute("action", "assetlist_update_config"))
+    // otherwise the slashed deposits become withdrawable by the owner
+    Ok(match fee_collector {
+        Some(fee_collector) if !slashed.is_empty() => response
+            .add_attribute("forwarded", slashed.to_string())
+            .add_message(BankMsg::Send {
+                to_address: fee_collector.into_string(),
+                amount: slashed.into_vec(),
+            }),
+        _ => response,
+    })
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Listing(listing_query) => match listing_query {
-            ListingQuery::Denom(denoms) => to_json_binary(&query_listings_by_denom(deps, &denoms)?),
-            ListingQuery::Symbol(symbols) => {
-                to_json_binary(&query_listings_by_symbol(deps, &symbols)?)
-            }
-            ListingQuery::All { start_after, limit } => {
-                to_json_binary(&query_all_listings(deps, start_after, limit))
-            }
-        },
-        QueryMsg::Config => to_json_binary(&CONFIG.load(deps.storage)?),
+// returns deposits early, e.g. once a listing has proven itself, leaving nothing to slash
+fn execute_refund_deposits(deps: DepsMut, denoms: Vec<String>) -> Result<Response, ContractError> {
+    let mut response = Response::new().add_attribute("action", "assetlist_refund_deposits");
+
+    for denom in denoms {
+        let Some(deposit) = release_deposit(deps.storage, denom.clone())? else {
+            return Err(ContractError::NoDeposit(denom));
+        };
+        response = refund(response, denom, deposit.depositor, deposit.amount);
     }
+
+    Ok(response)
 }
 
-fn query_listings_by_denom(deps: Deps, denoms: &[String]) -> StdResult<Vec<(String, Metadata)>> {
-    let mut data = vec![];
+// approves pending listings and edits, or rejects them with a reason. a rejected edit leaves the
+// listing with its approved metadata
+fn execute_review_listings(
+    deps: DepsMut,
+    height: u64,
+    denoms: Vec<String>,
+    reason: Option<String>,
+) -> Result<Response, ContractError> {
     for denom in denoms {
-        match DENOM_MAP.load(deps.storage, denom.to_string()) {
-            Ok(denom_data) => data.push((denom.clone(), denom_data.metadata)),
-            Err(_) => {
-                return Err(cosmwasm_std::StdError::GenericErr {
-                    msg: format!("Listing not found for {denom}"),
-                })
+        let Ok(mut listing) = listings().load(deps.storage, denom.clone()) else {
+            return Err(ContractError::ListingNotFound(denom));
+        };
+        if listing.status != ListingStatus::Pending {
+            return Err(ContractError::NotPending(denom));
+        }
+
+        let edit = listing.pending_edit.take();
+        listing.status = match (&reason, &edit) {
+            (Some(_), None) => ListingStatus::Rejected,
+            _ => ListingStatus::Approved,
+        };
+        listing.rejection_reason = reason.clone();
+
+        if let (None, Some(metadata)) = (&reason, edit) {
+            // the edited symbol wasn't reserved while it waited for review
+            if symbol_taken(deps.storage, &metadata.symbol, &denom)? {
+                return Err(ContractError::DuplicateListing(metadata.symbol));
             }
+            // the verified metadata is being replaced
+            listing.verified = false;
+            listing.metadata = metadata.clone();
+            HISTORY.save(deps.storage, (denom.clone(), height), &Some(metadata))?;
         }
+
+        listings().save(deps.storage, denom, &listing)?;
     }
 
-    Ok(data)
+    Ok(match reason {
+        Some(reason) => Response::new()
+            .add_attribute("action", "assetlist_reject_listings")
+            .add_attribute("reason", reason),
+        None => Response::new().add_attribute("action", "assetlist_approve_listings"),
+    })
 }
 
-fn query_listings_by_symbol(deps: Deps, symbols: &[String]) -> StdResult<Vec<(String, Metadata)>> {
-    let mut data = vec![];
-    for symbol in symbols {
-        match SYMBOL_MAP.load(deps.storage, symbol.to_string()) {
-            Ok(denom) => match DENOM_MAP.load(deps.storage, denom.clone()) {
-                Ok(denom_data) => data.push((denom, denom_data.metadata)),
-                Err(_) => {
-                    return Err(cosmwasm_std::StdError::GenericErr {
-                        msg: format!("Listing not found for {symbol}"),
-                    })
-                }
-            },
-            Err(_) => {
-                return Err(cosmwasm_std::StdError::GenericErr {
-                    msg: format!("Listing not found for {symbol}"),
-                })
-            }
-        }
+#[allow(clippy::too_many_arguments)]
+fn execute_renew_listings(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    funds: &[Coin],
+    renewal_fee: Option<Vec<Coin>>,
+    fee_collector: Option<Addr>,
+    listing_ttl: Option<u64>,
+    admin: bool,
+    denoms: Vec<String>,
+) -> Result<Response, ContractError> {
+    let Some(listing_ttl) = listing_ttl else {
+        return Err(ContractError::NoExpiry);
+    };
+    let expires = env.block.time.plus_seconds(listing_ttl);
+
+    // renewal fees are kept or forwarded, but not escrowed
+    let renewal_fee = check_fee(renewal_fee.as_deref(), funds, denoms.len(), admin)?;
+    let (response, _) = settle_fees(
+        Response::new()
+            .add_attribute("action", "assetlist_renew_listings")
+            .add_attribute("expires", expires.to_string()),
+        sender,
+        funds,
+        renewal_fee.as_ref(),
+        denoms.len(),
+        fee_collector,
+    );
+
+    for denom in denoms {
+        let Ok(mut listing) = listings().load(deps.storage, denom.clone()) else {
+            return Err(ContractError::ListingNotFound(denom));
+        };
+        listing.expires = Some(expires);
+        listings().save(deps.storage, denom, &listing)?;
     }
 
-    Ok(data)
+    Ok(response)
 }
 
-fn query_all_listings(
-    deps: Deps,
-    start_after: Option<String>,
+// removes expired listings, soonest expired first. their escrowed fees are not refunded, but their
+// deposits are
+fn execute_prune_expired(
+    deps: DepsMut,
+    env: Env,
     limit: Option<u32>,
-) -> Vec<(String, Metadata)> {
+) -> Result<Response, ContractError> {
     let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
-    let start = start_after.map(Bound::exclusive);
 
-    DENOM_MAP
-        .range(deps.storage, start, None, Order::Ascending)
+    let expired = listings()
+        .idx
+        .expires
+        .prefix_range(
+            deps.storage,
+            None,
+            Some(PrefixBound::inclusive(env.block.time.nanos())),
+            Order::Ascending,
+        )
         .take(limit as usize)
-        .filter_map(Result::ok)
-        .map(|(denom, listing)| (denom, listing.metadata))
-        .collect()
+        .map(|item| item.map(|(denom, _)| denom))
+        .collect::<StdResult<Vec<_>>>()?;
 
-   // listings
+    let mut response = Response::new()
+        .add_attribute("action", "assetlist_prune_expired")
+        .add_attribute("pruned", expired.len().to_string());
+
+    for denom in expired {
+        let (_, deposit) = delete_listing(deps.storage, denom.clone(), env.block.height)?;
+        if let Some(deposit) = deposit {
+            response = refund(response, denom, deposit.depositor, deposit.amount);
+        }
+    }
+
+    Ok(response)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    let version = get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(StdError::generic_err("Can only upgrade from same type"));
-    };
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+fn execute_set_verified(
+    deps: DepsMut,
+    denoms: Vec<String>,
+    verified: bool,
+) -> Result<Response, ContractError> {
+    for denom in denoms {
+        let Ok(mut listing) = listings().load(deps.storage, denom.clone()) else {
+            return Err(ContractError::ListingNotFound(denom));
+        };
+        listing.verified = verified;
+        listings().save(deps.storage, denom, &listing)?;
+    }
 
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_attribute("action", "assetlist_set_verified")
+        .add_attribute("verified", verified.to_string()))
 }
 
-fn check_required_fields(
-    required_fields: &[Field],
-    metadata: &Metadata,
-) -> Result<(), ContractError> {
-    for field in required_fields {
-        match field {
-            Exp => {
-                if metadata.exp.is_none() {
-                    return Err(ContractError::MissingField(Exp));
-                }
+// whether a listing other than the denom's uses the symbol. rejected listings don't reserve theirs
+fn symbol_taken(storage: &dyn Storage, symbol: &str, denom: &str) -> StdResult<bool> {
+    for item in listings().idx.symbol.prefix(symbol.to_string()).range(
+        storage,
+        None,
+        None,
+        Order::Ascending,
+    ) {
+        let (other, listing) = item?;
+        if other != denom && listing.status != ListingStatus::Rejected {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn execute_set_chains(
+    deps: DepsMut,
+    chains: Vec<(String, ChainInfo)>,
+) -> Result<Response, ContractError> {
+    for (chain_id, info) in chains {
+        CHAINS.save(deps.storage, chain_id, &info)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "assetlist_set_chains"))
+}
+
+fn execute_remove_chains(deps: DepsMut, chain_ids: Vec<String>) -> Result<Response, ContractError> {
+    // listings referencing a removed chain are kept, but can't be updated until it is registered again
+    for chain_id in chain_ids {
+        CHAINS.remove(deps.storage, chain_id)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "assetlist_remove_chains"))
+}
+
+fn execute_set_verifiers(
+    deps: DepsMut,
+    verifiers: Vec<(String, String)>,
+) -> Result<Response, ContractError> {
+    for (address, name) in verifiers {
+        let address = deps.api.addr_validate(&address)?;
+        VERIFIERS.save(deps.storage, &address, &name)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "assetlist_set_verifiers"))
+}
+
+fn execute_remove_verifiers(
+    deps: DepsMut,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // attestations from a removed verifier are kept, but hidden until it is registered again
+    for address in addresses {
+        let address = deps.api.addr_validate(&address)?;
+        VERIFIERS.remove(deps.storage, &address)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "assetlist_remove_verifiers"))
+}
+
+fn execute_attest(
+    deps: DepsMut,
+    verifier: &Addr,
+    denom: String,
+    kind: String,
+    attestation: Attestation,
+) -> Result<Response, ContractError> {
+    if !listings().has(deps.storage, denom.clone()) {
+        return Err(ContractError::ListingNotFound(denom));
+    }
+
+    ATTESTATIONS.save(
+        deps.storage,
+        (denom.clone(), verifier, kind.clone()),
+        &attestation,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "assetlist_attest")
+        .add_attribute("denom", denom)
+        .add_attribute("verifier", verifier)
+        .add_attribute("kind", kind))
+}
+
+fn execute_revoke_attestation(
+    deps: DepsMut,
+    verifier: &Addr,
+    denom: String,
+    kind: String,
+) -> Result<Response, ContractError> {
+    let key = (denom.clone(), verifier, kind.clone());
+    if !ATTESTATIONS.has(deps.storage, key.clone()) {
+        return Err(ContractError::AttestationNotFound(kind));
+    }
+    ATTESTATIONS.remove(deps.storage, key)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "assetlist_revoke_attestation")
+        .add_attribute("denom", denom)
+        .add_attribute("verifier", verifier)
+        .add_attribute("kind", kind))
+}
+
+// attestations refer to the removed listing, so they don't carry over if the denom is listed again
+fn clear_attestations(storage: &mut dyn Storage, denom: String) -> StdResult<()> {
+    let keys = ATTESTATIONS
+        .sub_prefix(denom.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (verifier, kind) in keys {
+        ATTESTATIONS.remove(storage, (denom.clone(), &verifier, kind))?;
+    }
+
+    Ok(())
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    config: &Config,
+    proposer: Addr,
+    change: PendingChange,
+) -> Result<Response, ContractError> {
+    let id = PENDING_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+    let expires = env
+        .block
+        .time
+        .plus_seconds(config.confirm_period.unwrap_or(DEFAULT_CONFIRM_PERIOD));
+
+    PENDING_ID.save(deps.storage, &id)?;
+    PENDING_ACTIONS.save(
+        deps.storage,
+        id,
+        &PendingAction {
+            proposer,
+            expires,
+            change,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "assetlist_propose")
+        .add_attribute("pending_id", id.to_string())
+        .add_attribute("expires", expires.to_string()))
+}
+
+fn execute_confirm(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: &Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let Some(pending) = PENDING_ACTIONS.may_load(deps.storage, id)? else {
+        return Err(ContractError::PendingNotFound(id));
+    };
+    if pending.proposer == *sender {
+        return Err(ContractError::SelfConfirm);
+    }
+    if env.block.time >= pending.expires {
+        return Err(ContractError::PendingExpired(id));
+    }
+    PENDING_ACTIONS.remove(deps.storage, id)?;
+
+    // the action is executed with the proposer's authority
+    let response = match pending.change {
+        PendingChange::RemoveListings(denoms) => execute_remove_listings(
+            deps,
+            env.block.height,
+            &pending.proposer,
+            true,
+            config.remove_permissioned.unwrap_or_default(),
+            denoms,
+        )?,
+        PendingChange::UpdateConfig(mut new_config) => {
+            execute_update_config(deps, &pending.proposer, config, &mut new_config)?
+        }
+        PendingChange::SlashListings { denoms, reason } => {
+            execute_slash_listings(deps, env.block.height, config.fee_collector, denoms, reason)?
+        }
+        PendingChange::ClearFeeCollector => execute_clear_fee_collector(deps, config)?,
+    };
+
+    Ok(response
+        .add_attribute("action", "assetlist_confirm")
+        .add_attribute("pending_id", id.to_string()))
+}
+
+fn execute_cancel(deps: DepsMut, id: u64) -> Result<Response, ContractError> {
+    if !PENDING_ACTIONS.has(deps.storage, id) {
+        return Err(ContractError::PendingNotFound(id));
+    }
+    PENDING_ACTIONS.remove(deps.storage, id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "assetlist_cancel")
+        .add_attribute("pending_id", id.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::RemoveListings(denoms) => sudo_remove_listings(deps, env.block.height, denoms),
+    }
+}
+
+fn sudo_remove_listings(
+    deps: DepsMut,
+    height: u64,
+    denoms: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut response =
+        Response::new().add_attribute("action", "assetlist_governance_remove_listings");
+
+    for denom in denoms {
+        if !listings().has(deps.storage, denom.clone()) {
+            return Err(ContractError::ListingNotFound(denom));
+        }
+
+        // refund the escrowed listing fee and the deposit to whoever paid them, so projects caught
+        // in a sweep aren't penalized
+        let (fee, deposit) = delete_listing(deps.storage, denom.clone(), height)?;
+        let fee = fee.map(|fee| (fee.payer, fee.amount));
+        let deposit = deposit.map(|deposit| (deposit.depositor, deposit.amount));
+        for (recipient, amount) in fee.into_iter().chain(deposit) {
+            response = refund(response, denom.clone(), recipient, amount);
+        }
+    }
+
+    Ok(response)
+}
+
+// removes a listing along with its indexes and attestations, recording the removal in its history.
+// returns the fee and deposit released from escrow for the caller to refund or keep
+fn delete_listing(
+    storage: &mut dyn Storage,
+    denom: String,
+    height: u64,
+) -> StdResult<(Option<EscrowedFee>, Option<Deposit>)> {
+    listings().remove(storage, denom.clone())?;
+    add_entries(storage, LISTINGS_COUNT, -1)?;
+    HISTORY.save(storage, (denom.clone(), height), &None)?;
+    clear_attestations(storage, denom.clone())?;
+
+    Ok((
+        release_fee(storage, denom.clone())?,
+        release_deposit(storage, denom)?,
+    ))
+}
+
+// escrows the fee paid for a listing, keeping it out of what the owner can withdraw
+fn escrow_fee(storage: &mut dyn Storage, denom: String, payer: &Addr, fee: &Coin) -> StdResult<()> {
+    FEE_ESCROW.save(
+        storage,
+        denom,
+        &EscrowedFee {
+            payer: payer.clone(),
+            amount: fee.clone(),
+        },
+    )?;
+    ESCROW_TOTALS.update(storage, fee.denom.clone(), |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + fee.amount)
+    })?;
+
+    Ok(())
+}
+
+// releases the fee escrowed for a listing, to be refunded or become withdrawable
+fn release_fee(storage: &mut dyn Storage, denom: String) -> StdResult<Option<EscrowedFee>> {
+    let fee = FEE_ESCROW.may_load(storage, denom.clone())?;
+    if let Some(fee) = &fee {
+        FEE_ESCROW.remove(storage, denom)?;
+        ESCROW_TOTALS.update(storage, fee.amount.denom.clone(), |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().saturating_sub(fee.amount.amount))
+        })?;
+    }
+
+    Ok(fee)
+}
+
+// holds the deposit for a listing in escrow until it is refunded or slashed
+fn hold_deposit(
+    storage: &mut dyn Storage,
+    denom: String,
+    depositor: &Addr,
+    amount: &Coin,
+) -> StdResult<()> {
+    DEPOSITS.save(
+        storage,
+        denom,
+        &Deposit {
+            depositor: depositor.clone(),
+            amount: amount.clone(),
+        },
+    )?;
+    ESCROW_TOTALS.update(storage, amount.denom.clone(), |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + amount.amount)
+    })?;
+
+    Ok(())
+}
+
+// releases the deposit held for a listing, to be refunded or become withdrawable when slashed
+fn release_deposit(storage: &mut dyn Storage, denom: String) -> StdResult<Option<Deposit>> {
+    let deposit = DEPOSITS.may_load(storage, denom.clone())?;
+    if let Some(deposit) = &deposit {
+        DEPOSITS.remove(storage, denom)?;
+        ESCROW_TOTALS.update(
+            storage,
+            deposit.amount.denom.clone(),
+            |total| -> StdResult<_> {
+                Ok(total
+                    .unwrap_or_default()
+                    .saturating_sub(deposit.amount.amount))
+            },
+        )?;
+    }
+
+    Ok(deposit)
+}
+
+// sends escrowed funds released for a listing back to whoever paid them
+fn refund(response: Response, denom: String, recipient: Addr, amount: Coin) -> Response {
+    response
+        .add_attribute("refund_denom", denom)
+        .add_attribute("refund_recipient", recipient.clone())
+        .add_attribute("refund_amount", amount.to_string())
+        .add_message(BankMsg::Send {
+            to_address: recipient.into_string(),
+            amount: vec![amount],
+        })
+}
+
+fn execute_clear_fee_collector(deps: DepsMut, config: Config) -> Result<Response, ContractError> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            fee_collector: None,
+            ..config
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "assetlist_clear_fee_collector"))
+}
+
+fn execute_withdraw_fees(
+    deps: DepsMut,
+    env: Env,
+    to: String,
+    amount: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    let to = deps.api.addr_validate(&to)?;
+
+    for coin in &amount {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &coin.denom)?
+            .amount;
+        let escrowed = ESCROW_TOTALS
+            .may_load(deps.storage, coin.denom.clone())?
+            .unwrap_or_default();
+        let withdrawable = balance.saturating_sub(escrowed);
+        if coin.amount > withdrawable {
+            return Err(ContractError::WithdrawExceeded(format!(
+                "{}{}",
+                withdrawable, coin.denom
+            )));
+        }
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: to.to_string(),
+            amount: amount.clone(),
+        })
+        .add_attribute("action", "assetlist_withdraw_fees")
+        .add_attribute("to", to)
+        .add_attribute(
+            "amount",
+            amount
+                .iter()
+                .map(Coin::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        ))
+}
+
+fn execute_prune_history(
+    deps: DepsMut,
+    denoms: Vec<String>,
+    keep_last: Option<u32>,
+    before_height: Option<u64>,
+) -> Result<Response, ContractError> {
+    if keep_last.is_none() && before_height.is_none() {
+        return Err(ContractError::PruneCriteria);
+    }
+
+    let mut response = Response::new().add_attribute("action", "assetlist_prune_history");
+
+    for denom in denoms {
+        // newest first, so the revisions to keep can be skipped
+        let heights = HISTORY
+            .prefix(denom.clone())
+            .keys(deps.storage, None, None, Order::Descending)
+            .collect::<StdResult<Vec<u64>>>()?;
+
+        let pruned: Vec<u64> = heights
+            .into_iter()
+            .skip(keep_last.unwrap_or_default() as usize)
+            .filter(|height| before_height.is_none_or(|before| *height < before))
+            .collect();
+
+        for height in &pruned {
+            HISTORY.remove(deps.storage, (denom.clone(), *height))?;
+        }
+
+        response = response
+            .add_attribute("denom", denom)
+            .add_attribute("pruned", pruned.len().to_string());
+    }
+
+    Ok(response)
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    sender: &Addr,
+    old_config: Config,
+    new_config: &mut Config,
+) -> Result<Response, ContractError> {
+    // only the owner can update the config
+    if sender != old_config.owner.clone().unwrap() {
+        return Err(ContractError::NotOwner);
+    }
+
+    // set the new owner or default to the current owner
+    new_config.owner = match new_config.owner.clone() {
+        Some(owner) => {
+            deps.api.addr_validate(owner.as_str())?;
+            Some(owner)
+        }
+        None => old_config.owner.clone(),
+    };
+
+    // validate the new admins. if provided, will overwrite the current list
+    // (an empty list will clear all admins)
+    if let Some(mut admins) = new_config.admins.clone() {
+        for address in &admins {
+            deps.api.addr_validate(address.as_str())?;
+        }
+        // add the owner to the list of admins if it has changed
+        admins.push(new_config.owner.clone().unwrap());
+        new_config.admins = Some(admins);
+    } else {
+        new_config.admins = old_config.admins.clone();
+    }
+
+    // if other fields are None, they will not be updated
+    new_config.add_permissioned = new_config
+        .add_permissioned
+        .take()
+        .or(old_config.add_permissioned);
+
+    new_config.remove_permissioned = new_config
+        .remove_permissioned
+        .take()
+        .or(old_config.remove_permissioned);
+
+    new_config.required_fields = new_config
+        .required_fields
+        .take()
+        .or(old_config.required_fields);
+
+    new_config.fee = new_config.fee.take().or(old_config.fee);
+
+    new_config.deposit = new_config.deposit.take().or(old_config.deposit);
+
+    new_config.update_fee = new_config.update_fee.take().or(old_config.update_fee);
+
+    new_config.renewal_fee = new_config.renewal_fee.take().or(old_config.renewal_fee);
+
+    new_config.listing_ttl = new_config.listing_ttl.take().or(old_config.listing_ttl);
+
+    if let Some(fee_collector) = &new_config.fee_collector {
+        deps.api.addr_validate(fee_collector.as_str())?;
+    }
+    new_config.fee_collector = new_config.fee_collector.take().or(old_config.fee_collector);
+
+    new_config.confirm_threshold = new_config
+        .confirm_threshold
+        .take()
+        .or(old_config.confirm_threshold);
+
+    new_config.confirm_period = new_config
+        .confirm_period
+        .take()
+        .or(old_config.confirm_period);
+
+    new_config.review = new_config.review.take().or(old_config.review);
+
+    new_config.extension_limits = new_config
+        .extension_limits
+        .take()
+        .or(old_config.extension_limits);
+
+    CONFIG.save(deps.storage, new_config)?;
+
+    Ok(Response::new().add_attribute("action", "assetlist_update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Listing(listing_query) => match listing_query {
+            ListingQuery::Denom(denoms) => {
+                to_json_binary(&query_listings_by_denom(deps, env, &denoms)?)
+            }
+            ListingQuery::Symbol(symbols) => {
+                to_json_binary(&query_listings_by_symbol(deps, env, &symbols)?)
+            }
+            ListingQuery::All { start_after, limit } => {
+                to_json_binary(&query_all_listings(deps, env, start_after, limit))
+            }
+            ListingQuery::Author {
+                address,
+                start_after,
+                limit,
+            } => to_json_binary(&query_listings_by_author(
+                deps,
+                env,
+                address,
+                start_after,
+                limit,
+            )?),
+            ListingQuery::Chain {
+                chain_id,
+                start_after,
+                limit,
+            } => to_json_binary(&query_listings_by_chain(
+                deps,
+                env,
+                chain_id,
+                start_after,
+                limit,
+            )?),
+            ListingQuery::Verified { start_after, limit } => {
+                to_json_binary(&query_verified_listings(deps, env, start_after, limit)?)
+            }
+        },
+        QueryMsg::Status {
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_listings_by_status(
+            deps,
+            env,
+            status,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::History {
+            denom,
+            start_after,
+            limit,
+        } => to_json_binary(&query_history(deps, denom, start_after, limit)?),
+        QueryMsg::Diff {
+            denom,
+            from_height,
+            to_height,
+        } => to_json_binary(&query_diff(deps, denom, from_height, to_height)?),
+        QueryMsg::Attestations { denom } => to_json_binary(&query_attestations(deps, env, denom)?),
+        QueryMsg::Deposit(denom) => to_json_binary(&DEPOSITS.load(deps.storage, denom)?),
+        QueryMsg::Deposits { start_after, limit } => {
+            to_json_binary(&query_deposits(deps, start_after, limit)?)
+        }
+        QueryMsg::Chain(chain_id) => to_json_binary(&CHAINS.load(deps.storage, chain_id)?),
+        QueryMsg::Chains { start_after, limit } => {
+            to_json_binary(&query_chains(deps, start_after, limit)?)
+        }
+        QueryMsg::Verifiers { start_after, limit } => {
+            to_json_binary(&query_verifiers(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingActions { start_after, limit } => {
+            to_json_binary(&query_pending_actions(deps, start_after, limit)?)
+        }
+        QueryMsg::Config => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::StorageStats => to_json_binary(&query_storage_stats(deps)?),
+        QueryMsg::EventsSchema => to_json_binary(&query_events_schema()),
+    }
+}
+
+fn query_listings_by_denom(
+    deps: Deps,
+    env: Env,
+    denoms: &[String],
+) -> StdResult<Vec<(String, Metadata)>> {
+    let mut data = vec![];
+    for denom in denoms {
+        match listings().may_load(deps.storage, denom.to_string())? {
+            Some(denom_data) if denom_data.live(env.block.time) => {
+                data.push((denom.clone(), denom_data.metadata))
+            }
+            _ => {
+                return Err(cosmwasm_std::StdError::GenericErr {
+                    msg: format!("Listing not found for {denom}"),
+                })
+            }
+        }
+    }
+
+    Ok(data)
+}
+
+fn query_listings_by_symbol(
+    deps: Deps,
+    env: Env,
+    symbols: &[String],
+) -> StdResult<Vec<(String, Metadata)>> {
+    let mut data = vec![];
+    for symbol in symbols {
+        // a symbol can also be held by rejected listings, which aren't live
+        let live_listing = listings()
+            .idx
+            .symbol
+            .prefix(symbol.to_string())
+            .range(deps.storage, None, None, Order::Ascending)
+            .find(|item| live(item, env.block.time))
+            .transpose()?;
+        match live_listing {
+            Some((denom, listing)) => data.push((denom, listing.metadata)),
+            _ => {
+                return Err(cosmwasm_std::StdError::GenericErr {
+                    msg: format!("Listing not found for {symbol}"),
+                })
+            }
+        }
+    }
+
+    Ok(data)
+}
+
+fn query_all_listings(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<(String, Metadata)> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    listings()
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter_map(Result::ok)
+        .filter(|(_, listing)| listing.live(env.block.time))
+        .take(limit as usize)
+        .map(|(denom, listing)| (denom, listing.metadata))
+        .collect()
+
+   // listings
+}
+
+fn query_listings_by_author(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Metadata)>> {
+    // listings added by admins are indexed under an empty author, which doesn't validate
+    let address = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    listings()
+        .idx
+        .author
+        .prefix(address.into_string())
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| live(item, env.block.time))
+        .take(limit as usize)
+        .map(|item| item.map(|(denom, listing)| (denom, listing.metadata)))
+        .collect()
+}
+
+fn query_listings_by_chain(
+    deps: Deps,
+    env: Env,
+    chain_id: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Metadata)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    listings()
+        .idx
+        .chain
+        .prefix(chain_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| live(item, env.block.time))
+        .take(limit as usize)
+        .map(|item| item.map(|(denom, listing)| (denom, listing.metadata)))
+        .collect()
+}
+
+fn query_verified_listings(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Metadata)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    listings()
+        .idx
+        .verified
+        .prefix(1)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| live(item, env.block.time))
+        .take(limit as usize)
+        .map(|item| item.map(|(denom, listing)| (denom, listing.metadata)))
+        .collect()
+}
+
+fn query_listings_by_status(
+    deps: Deps,
+    env: Env,
+    status: ListingStatus,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Listing)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    listings()
+        .idx
+        .status
+        .prefix(status.as_str().to_string())
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, listing)) if listing.expired(env.block.time)))
+        .take(limit as usize)
+        .collect()
+}
+
+// leaves expired and unapproved listings out of paginated queries, keeping errors
+fn live(item: &StdResult<(String, Listing)>, now: Timestamp) -> bool {
+    !matches!(item, Ok((_, listing)) if !listing.live(now))
+}
+
+fn query_history(
+    deps: Deps,
+    denom: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Option<Metadata>)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    HISTORY
+        .prefix(denom)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_diff(
+    deps: Deps,
+    denom: String,
+    from_height: u64,
+    to_height: u64,
+) -> StdResult<DiffResponse> {
+    let from = metadata_at(deps, denom.clone(), from_height)?;
+    let to = metadata_at(deps, denom, to_height)?;
+
+    let fields = |metadata: &Option<Metadata>| {
+        let metadata = metadata.as_ref();
+        [
+            ("symbol", metadata.map(|m| m.symbol.clone())),
+            (
+                "exp",
+                metadata.and_then(|m| m.exp).map(|exp| exp.to_string()),
+            ),
+            ("logo", metadata.and_then(|m| m.logo.clone())),
+            ("chain", metadata.and_then(|m| m.chain.clone())),
+        ]
+    };
+
+    let mut changes: Vec<FieldChange> = fields(&from)
+        .into_iter()
+        .zip(fields(&to))
+        .filter(|((_, from), (_, to))| from != to)
+        .map(|((field, from), (_, to))| FieldChange {
+            field: field.to_string(),
+            from,
+            to,
+        })
+        .collect();
+
+    // extensions are compared by key, reported as extensions.{key}
+    let extensions = |metadata: &Option<Metadata>| {
+        metadata
+            .as_ref()
+            .and_then(|m| m.extensions.clone())
+            .unwrap_or_default()
+    };
+    let (from_extensions, to_extensions) = (extensions(&from), extensions(&to));
+    let value = |extensions: &[(String, String)], key: &str| {
+        extensions
+            .iter()
+            .find(|(other, _)| other == key)
+            .map(|(_, value)| value.clone())
+    };
+    let mut keys: Vec<&String> = from_extensions
+        .iter()
+        .chain(&to_extensions)
+        .map(|(key, _)| key)
+        .collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let (from, to) = (value(&from_extensions, key), value(&to_extensions, key));
+        if from != to {
+            changes.push(FieldChange {
+                field: format!("extensions.{key}"),
+                from,
+                to,
+            });
+        }
+    }
+
+    Ok(DiffResponse { from, to, changes })
+}
+
+// the metadata of a listing as of the end of the given block
+fn metadata_at(deps: Deps, denom: String, height: u64) -> StdResult<Option<Metadata>> {
+    Ok(HISTORY
+        .prefix(denom)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(height)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?
+        .and_then(|(_, metadata)| metadata))
+}
+
+fn query_deposits(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Deposit)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    DEPOSITS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_chains(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, ChainInfo)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    CHAINS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_verifiers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, String)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    VERIFIERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_attestations(deps: Deps, env: Env, denom: String) -> StdResult<Vec<AttestationResponse>> {
+    let mut attestations = vec![];
+    for item in ATTESTATIONS
+        .sub_prefix(denom)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let ((verifier, kind), attestation) = item?;
+        let Some(verifier_name) = VERIFIERS.may_load(deps.storage, &verifier)? else {
+            continue;
+        };
+        attestations.push(AttestationResponse {
+            expired: attestation
+                .expiry
+                .is_some_and(|expiry| expiry <= env.block.time),
+            verifier,
+            verifier_name,
+            kind,
+            attestation,
+        });
+    }
+
+    Ok(attestations)
+}
+
+fn query_pending_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, PendingAction)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    PENDING_ACTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_storage_stats(deps: Deps) -> StdResult<StorageStatsResponse> {
+    // every listing has exactly one symbol index entry
+    let maps = [
+        ("listings", LISTINGS_COUNT),
+        ("symbols", LISTINGS_COUNT),
+        ("fee_escrow", FEE_ESCROW.name()),
+        ("deposits", DEPOSITS.name()),
+        ("escrow_totals", ESCROW_TOTALS.name()),
+        ("history", HISTORY.name()),
+        ("chains", CHAINS.name()),
+        ("verifiers", VERIFIERS.name()),
+        ("attestations", ATTESTATIONS.name()),
+        ("pending_actions", PENDING_ACTIONS.name()),
+    ];
+
+    Ok(StorageStatsResponse {
+        maps: maps
+            .into_iter()
+            .map(|(name, counter)| {
+                let entries = ENTRY_COUNTS.may_load(deps.storage, counter)?;
+                Ok((name.to_string(), entries.unwrap_or_default()))
+            })
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+fn query_events_schema() -> EventsSchemaResponse {
+    EventsSchemaResponse {
+        version: CONTRACT_VERSION.to_string(),
+        events: EVENTS
+            .iter()
+            .map(|(action, attributes)| EventSchema {
+                event_type: "wasm".to_string(),
+                action: action.to_string(),
+                attributes: attributes.iter().map(|key| key.to_string()).collect(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let version = get_contract_version(deps.storage)?;
+    if version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err("Can only upgrade from same type"));
+    };
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // listings used to be looked up by symbol through a separate map
+    let legacy_symbols = LEGACY_SYMBOL_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for symbol in legacy_symbols {
+        LEGACY_SYMBOL_MAP.remove(deps.storage, symbol);
+    }
+
+    // saving every listing again indexes it, including under indexes added since it was stored
+    let all = listings()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (denom, listing) in all {
+        listings().replace(deps.storage, denom, Some(&listing), Some(&listing))?;
+    }
+
+    // entries used to be counted by iterating over each map when queried
+    let counts = [
+        (
+            LISTINGS_COUNT,
+            listings()
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            FEE_ESCROW.name(),
+            FEE_ESCROW
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            DEPOSITS.name(),
+            DEPOSITS
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            ESCROW_TOTALS.name(),
+            ESCROW_TOTALS
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            HISTORY.name(),
+            HISTORY
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            CHAINS.name(),
+            CHAINS
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            VERIFIERS.name(),
+            VERIFIERS
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            ATTESTATIONS.name(),
+            ATTESTATIONS
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+        (
+            PENDING_ACTIONS.name(),
+            PENDING_ACTIONS
+                .keys_raw(deps.storage, None, None, Order::Ascending)
+                .count(),
+        ),
+    ];
+    for (name, count) in counts {
+        ENTRY_COUNTS.save(deps.storage, name, &(count as u64))?;
+    }
+
+    Ok(Response::default())
+}
+
+fn check_required_fields(
+    required_fields: &[Field],
+    metadata: &Metadata,
+) -> Result<(), ContractError> {
+    for field in required_fields {
+        match field {
+            Exp => {
+                if metadata.exp.is_none() {
+                    return Err(ContractError::MissingField(Exp));
+                }
             }
             Logo => {
                 if metadata.logo.is_none() {
@@ -423,3 +2006,34 @@ fn check_required_fields(
 
     Ok(())
 }
+
+// extensions must stay within the configured limits and can't repeat a key
+fn check_extensions(limits: &ExtensionLimits, metadata: &Metadata) -> Result<(), ContractError> {
+    let extensions = metadata.extensions.as_deref().unwrap_or_default();
+    if extensions.len() > limits.max_entries as usize {
+        return Err(ContractError::TooManyExtensions(limits.max_entries));
+    }
+
+    for (i, (key, value)) in extensions.iter().enumerate() {
+        if key.is_empty()
+            || key.len() > limits.max_key_length as usize
+            || value.len() > limits.max_value_length as usize
+            || extensions[..i].iter().any(|(other, _)| other == key)
+        {
+            return Err(ContractError::InvalidExtension(key.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+// once the chain registry is in use, a listing's chain must be one of the registered chains
+fn check_chain(deps: Deps, metadata: &Metadata) -> Result<(), ContractError> {
+    if let Some(chain) = &metadata.chain {
+        if !CHAINS.is_empty(deps.storage) && !CHAINS.has(deps.storage, chain.clone()) {
+            return Err(ContractError::UnknownChain(chain.clone()));
+        }
+    }
+
+    Ok(())
+}
@@ -16,6 +16,51 @@ pub enum ContractError {
     #[error("Must be an admin to edit/remove listings")]
     RemovePermissioned,
 
+    #[error("Must be an admin to manage the chain registry")]
+    ChainPermissioned,
+
+    #[error("Must be an admin to manage verifiers")]
+    VerifierPermissioned,
+
+    #[error("Must be an admin to verify listings")]
+    VerifyPermissioned,
+
+    #[error("Must be an admin to review listings")]
+    ReviewPermissioned,
+
+    #[error("Must be an admin to slash or refund deposits")]
+    DepositPermissioned,
+
+    #[error("No deposit is held for {}", .0)]
+    NoDeposit(String),
+
+    #[error("Listings don't expire, so they can't be renewed")]
+    NoExpiry,
+
+    #[error("Listing {} is not pending review", .0)]
+    NotPending(String),
+
+    #[error("Must be a registered verifier to attest listings")]
+    NotVerifier,
+
+    #[error("Attestation {} not found", .0)]
+    AttestationNotFound(String),
+
+    #[error("Chain {} is not registered", .0)]
+    UnknownChain(String),
+
+    #[error("Pending action {} not found", .0)]
+    PendingNotFound(u64),
+
+    #[error("Pending action {} has expired", .0)]
+    PendingExpired(u64),
+
+    #[error("A second admin must confirm this action")]
+    SelfConfirm,
+
+    #[error("Pruning requires keep_last or before_height to be set")]
+    PruneCriteria,
+
     #[error("Not authorized to edit/remove this listing")]
     Unauthorized,
 
@@ -31,6 +76,9 @@ pub enum ContractError {
     #[error("Insufficient fee paid")]
     InsufficientFee,
 
+    #[error("Only {} can be withdrawn, the rest is escrowed", .0)]
+    WithdrawExceeded(String),
+
     #[error("Duplicate listing found for {}", 0)]
     DuplicateListing(String),
 
@@ -39,4 +87,10 @@ pub enum ContractError {
 
     #[error("Required field {} is missing", 0)]
     MissingField(Field),
+
+    #[error("Invalid extension {}, keys must be unique and within the size limits", .0)]
+    InvalidExtension(String),
+
+    #[error("Listings can have at most {} extensions", .0)]
+    TooManyExtensions(u32),
 }
@@ -1,5 +1,8 @@
-use crate::state::{Config, Metadata};
+use crate::state::{
+    Attestation, ChainInfo, Config, Deposit, Listing, ListingStatus, Metadata, PendingAction,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin};
 
 #[cw_serde]
 pub struct InstantiateMsg(pub Config);
@@ -7,7 +10,28 @@ pub struct InstantiateMsg(pub Config);
 #[cw_serde]
 pub enum ExecuteMsg {
     Listing(ListingMsg),
+    Chain(ChainMsg),
+    Verifier(VerifierMsg),
+    Attestation(AttestationMsg),
+    Pending(PendingMsg),
+    // Deletes old revisions of listings from their history. Must be done by the owner
+    PruneHistory {
+        denoms: Vec<String>,
+        // The number of most recent revisions to keep for each listing
+        keep_last: Option<u32>,
+        // Only revisions recorded below this block height are deleted
+        before_height: Option<u64>,
+    },
     UpdateConfig(Config),
+    // Stops forwarding fees to the fee collector, so new listing fees are escrowed again. Must be done
+    // by the owner, and confirmed by a second admin when a confirm threshold is set
+    ClearFeeCollector,
+    // Sends collected listing fees and slashed deposits to an address. Fees still escrowed for a
+    // governance refund and held deposits can't be withdrawn. Must be done by the owner
+    WithdrawFees {
+        to: String,
+        amount: Vec<Coin>,
+    },
 }
 
 #[cw_serde]
@@ -16,17 +40,142 @@ pub enum ListingMsg {
     Add(Vec<(String, Metadata)>),
     // Update existing listings
     Update(Vec<(String, Metadata)>),
-    // Removes listings from the assetlist by denom. Must be done by the listing creator or an admin
+    // Removes listings from the assetlist by denom, refunding their deposits. Must be done by the
+    // listing creator or an admin
     Remove(Vec<String>),
+    // Removes spam or scam listings, keeping their deposits. Must be done by an admin
+    Slash { denoms: Vec<String>, reason: String },
+    // Returns the deposits of listings in good standing while keeping them listed. Must be done by an
+    // admin
+    RefundDeposits(Vec<String>),
+    // Approves pending listings. Must be done by an admin
+    Approve(Vec<String>),
+    // Rejects pending listings. The author can update them to submit them for review again. Must be
+    // done by an admin
+    Reject { denoms: Vec<String>, reason: String },
+    // Extends listings for another listing_ttl from now, paying the renewal fee. Can be done by anyone
+    Renew(Vec<String>),
+    // Removes up to limit expired listings. Can be done by anyone
+    PruneExpired { limit: Option<u32> },
+    // Marks listings as verified, or clears it. Must be done by an admin
+    SetVerified { denoms: Vec<String>, verified: bool },
+}
+
+#[cw_serde]
+pub enum ChainMsg {
+    // Adds or replaces chains in the registry. Must be done by an admin
+    Set(Vec<(String, ChainInfo)>),
+    // Removes chains from the registry by chain id. Must be done by an admin
+    Remove(Vec<String>),
+}
+
+#[cw_serde]
+pub enum VerifierMsg {
+    // Registers verifiers as (address, name) pairs, or renames them. Must be done by an admin
+    Set(Vec<(String, String)>),
+    // Removes verifiers by address. Must be done by an admin
+    Remove(Vec<String>),
+}
+
+#[cw_serde]
+pub enum AttestationMsg {
+    // Attaches an attestation of the given type to a listing, replacing any previous one from the
+    // same verifier. Must be done by a registered verifier
+    Add {
+        denom: String,
+        kind: String,
+        attestation: Attestation,
+    },
+    // Withdraws the sender's attestation of the given type from a listing
+    Revoke {
+        denom: String,
+        kind: String,
+    },
+}
+
+#[cw_serde]
+pub enum PendingMsg {
+    // Executes a pending action. Must be done by an admin other than the proposer, before it expires
+    Confirm(u64),
+    // Discards a pending action. Must be done by an admin
+    Cancel(u64),
+}
+
+// Messages that can only be sent by chain governance
+#[cw_serde]
+pub enum SudoMsg {
+    // Removes listings, refunding any escrowed listing fee to the author and deposit to the depositor
+    RemoveListings(Vec<String>),
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    // Returns a list of (denom, metadata) pairs
     #[returns(Vec<(String, Metadata)>)]
     Listing(ListingQuery),
+    // Returns a paginated list of (denom, listing) pairs with a review status
+    #[returns(Vec<(String, Listing)>)]
+    Status {
+        status: ListingStatus,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns a paginated list of a listing's revisions by block height. None marks a removal
+    #[returns(Vec<(u64, Option<Metadata>)>)]
+    History {
+        denom: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Returns the changes to a listing's metadata between two block heights
+    #[returns(DiffResponse)]
+    Diff {
+        denom: String,
+        from_height: u64,
+        to_height: u64,
+    },
+    // Returns the attestations attached to a listing by currently registered verifiers
+    #[returns(Vec<AttestationResponse>)]
+    Attestations { denom: String },
+    // Returns the deposit held for a listing
+    #[returns(Deposit)]
+    Deposit(String),
+    // Returns a paginated list of (denom, deposit) pairs
+    #[returns(Vec<(String, Deposit)>)]
+    Deposits {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns info for a registered chain id
+    #[returns(ChainInfo)]
+    Chain(String),
+    // Returns a paginated list of registered chains
+    #[returns(Vec<(String, ChainInfo)>)]
+    Chains {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns a paginated list of registered verifiers as (address, name) pairs
+    #[returns(Vec<(Addr, String)>)]
+    Verifiers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns a paginated list of actions waiting for a second admin to confirm them
+    #[returns(Vec<(u64, PendingAction)>)]
+    PendingActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     #[returns(Config)]
     Config,
+    // Returns the number of entries in each storage map, to monitor state growth
+    #[returns(StorageStatsResponse)]
+    StorageStats,
+    // Returns the events and attribute keys emitted by this contract version
+    #[returns(EventsSchemaResponse)]
+    EventsSchema,
 }
 
 #[cw_serde]
@@ -40,7 +189,71 @@ pub enum ListingQuery {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    // Returns a paginated list of the listings published by an address
+    Author {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns a paginated list of the listings originating from a chain
+    Chain {
+        chain_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns a paginated list of the listings verified by an admin
+    Verified {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct AttestationResponse {
+    pub verifier: Addr,
+    pub verifier_name: String,
+    pub kind: String,
+    pub attestation: Attestation,
+    // Whether the attestation's expiry has passed
+    pub expired: bool,
+}
+
+#[cw_serde]
+pub struct DiffResponse {
+    // The metadata at each height. None if the denom was not listed
+    pub from: Option<Metadata>,
+    pub to: Option<Metadata>,
+    pub changes: Vec<FieldChange>,
+}
+
+#[cw_serde]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
 }
 
 #[cw_serde]
 pub struct MigrateMsg {}
+
+#[cw_serde]
+pub struct StorageStatsResponse {
+    // (map name, entry count) pairs
+    pub maps: Vec<(String, u64)>,
+}
+
+#[cw_serde]
+pub struct EventsSchemaResponse {
+    pub version: String,
+    pub events: Vec<EventSchema>,
+}
+
+#[cw_serde]
+pub struct EventSchema {
+    // The type of the emitted event
+    pub event_type: String,
+    // The value of the `action` attribute identifying the operation
+    pub action: String,
+    // Other attribute keys that can be emitted with this action
+    pub attributes: Vec<String>,
+}
@@ -1,12 +1,31 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, StdError, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, PrimaryKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::Deref;
 
 #[repr(u8)]
 pub enum TopKey {
     Config = b'a',
     DenomMap = b'b',
     SymbolMap = b'c',
+    Chains = b'e',
+    History = b'f',
+    Verifiers = b'g',
+    Attestations = b'h',
+    PendingId = b'i',
+    PendingActions = b'j',
+    ListingAuthors = b'l',
+    ListingChains = b'm',
+    ListingVerified = b'n',
+    ListingStatus = b'o',
+    EscrowTotals = b'p',
+    Deposits = b'r',
+    EscrowedFees = b's',
+    ListingExpiryNanos = b't',
+    ListingSymbolsMulti = b'u',
+    EntryCounts = b'v',
 }
 
 impl TopKey {
@@ -20,10 +39,104 @@ impl TopKey {
 }
 
 pub const CONFIG: Item<Config> = Item::new(TopKey::Config.as_str());
-// maps on chain denom to metadata
-pub const DENOM_MAP: Map<String, Listing> = Map::new(TopKey::DenomMap.as_str());
-// maps symbols to denoms, to allow reverse lookup without iterating over or re-storing all metadata
-pub const SYMBOL_MAP: Map<String, String> = Map::new(TopKey::SymbolMap.as_str());
+// maps symbols to denoms. replaced by the symbol index of the listings, only read when migrating
+pub const LEGACY_SYMBOL_MAP: Map<String, String> = Map::new(TopKey::SymbolMap.as_str());
+// maps denoms to the listing fee paid for them, refunded to the payer if the listing is removed by
+// governance
+pub const FEE_ESCROW: CountedMap<String, EscrowedFee> =
+    CountedMap::new(TopKey::EscrowedFees.as_str(), "fee_escrow");
+// maps denoms to the deposit held for them, refunded when the listing is removed unless slashed
+pub const DEPOSITS: CountedMap<String, Deposit> =
+    CountedMap::new(TopKey::Deposits.as_str(), "deposits");
+// the total escrowed in each fee token, including deposits, which the owner can't withdraw
+pub const ESCROW_TOTALS: CountedMap<String, Uint128> =
+    CountedMap::new(TopKey::EscrowTotals.as_str(), "escrow_totals");
+// listing metadata by (denom, block height) after each change. None records a removal
+pub const HISTORY: CountedMap<(String, u64), Option<Metadata>> =
+    CountedMap::new(TopKey::History.as_str(), "history");
+// maps chain ids to chain info. once any chain is registered, listings must reference a registered chain
+pub const CHAINS: CountedMap<String, ChainInfo> =
+    CountedMap::new(TopKey::Chains.as_str(), "chains");
+// maps verifier addresses (e.g. audit firms) to their display name
+pub const VERIFIERS: CountedMap<&Addr, String> =
+    CountedMap::new(TopKey::Verifiers.as_str(), "verifiers");
+// attestations by (denom, verifier, attestation type)
+pub const ATTESTATIONS: CountedMap<(String, &Addr, String), Attestation> =
+    CountedMap::new(TopKey::Attestations.as_str(), "attestations");
+// high impact actions waiting for a second admin to confirm them, by id
+pub const PENDING_ACTIONS: CountedMap<u64, PendingAction> =
+    CountedMap::new(TopKey::PendingActions.as_str(), "pending_actions");
+pub const PENDING_ID: Item<u64> = Item::new(TopKey::PendingId.as_str());
+// entry counts by map name, kept up to date by the CountedMaps and by adding or deleting listings
+pub const ENTRY_COUNTS: Map<&str, u64> = Map::new(TopKey::EntryCounts.as_str());
+pub const LISTINGS_COUNT: &str = "listings";
+
+pub struct ListingIndexes<'a> {
+    // symbols are only unique among listings that haven't been rejected, which is checked when
+    // listing so that rejected listings don't hold on to their symbol
+    pub symbol: MultiIndex<'a, String, Listing, String>,
+    // listings added by an admin have no author and are indexed under an empty one
+    pub author: MultiIndex<'a, String, Listing, String>,
+    // listings without a chain are indexed under an empty one
+    pub chain: MultiIndex<'a, String, Listing, String>,
+    // 1 for verified listings
+    pub verified: MultiIndex<'a, u8, Listing, String>,
+    pub status: MultiIndex<'a, String, Listing, String>,
+    // the expiry in nanoseconds, matching Listing::expired. listings that don't expire are indexed
+    // under u64::MAX
+    pub expires: MultiIndex<'a, u64, Listing, String>,
+}
+
+impl IndexList<Listing> for ListingIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
+        let v: Vec<&dyn Index<Listing>> = vec![
+            &self.symbol,
+            &self.author,
+            &self.chain,
+            &self.verified,
+            &self.status,
+            &self.expires,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+// maps on chain denom to metadata, indexed by symbol, author and chain
+pub fn listings<'a>() -> IndexedMap<'a, String, Listing, ListingIndexes<'a>> {
+    let indexes = ListingIndexes {
+        symbol: MultiIndex::new(
+            |_, listing| listing.metadata.symbol.clone(),
+            TopKey::DenomMap.as_str(),
+            TopKey::ListingSymbolsMulti.as_str(),
+        ),
+        author: MultiIndex::new(
+            |_, listing| listing.author.clone().unwrap_or_default(),
+            TopKey::DenomMap.as_str(),
+            TopKey::ListingAuthors.as_str(),
+        ),
+        chain: MultiIndex::new(
+            |_, listing| listing.metadata.chain.clone().unwrap_or_default(),
+            TopKey::DenomMap.as_str(),
+            TopKey::ListingChains.as_str(),
+        ),
+        verified: MultiIndex::new(
+            |_, listing| listing.verified as u8,
+            TopKey::DenomMap.as_str(),
+            TopKey::ListingVerified.as_str(),
+        ),
+        status: MultiIndex::new(
+            |_, listing| listing.status.as_str().to_string(),
+            TopKey::DenomMap.as_str(),
+            TopKey::ListingStatus.as_str(),
+        ),
+        expires: MultiIndex::new(
+            |_, listing| listing.expires.map_or(u64::MAX, |expires| expires.nanos()),
+            TopKey::DenomMap.as_str(),
+            TopKey::ListingExpiryNanos.as_str(),
+        ),
+    };
+    IndexedMap::new(TopKey::DenomMap.as_str(), indexes)
+}
 
 #[cw_serde]
 pub struct Config {
@@ -35,10 +148,51 @@ pub struct Config {
     pub required_fields: Option<Vec<Field>>,
     // A list of accepted fees that can be charged per listing to prevent spam
     pub fee: Option<Vec<Coin>>,
+    // A list of accepted deposits held per listing instead of the fee. Deposits are refunded when the
+    // listing is removed, unless an admin slashes them for spam or scams
+    pub deposit: Option<Vec<Coin>>,
+    // A list of accepted fees charged per listing updated, to disincentivize spam edits. Not escrowed
+    pub update_fee: Option<Vec<Coin>>,
+    // A list of accepted fees charged per listing renewed. Not escrowed
+    pub renewal_fee: Option<Vec<Coin>>,
+    // When set, listings expire this many seconds after they are added or renewed. Expired
+    // listings are left out of queries and can be pruned by anyone
+    pub listing_ttl: Option<u64>,
+    // When set, fees are forwarded here as they are paid instead of being escrowed, so listings
+    // removed by governance are not refunded
+    pub fee_collector: Option<Addr>,
     // Admins who can manage the asset list. The contract owner will be assigned automatically
     pub admins: Option<Vec<Addr>>,
     // The owner of the contract. Defaults to the instantiator
     pub owner: Option<Addr>,
+    // When set, admin removals of more than this many listings and changes to the fees, the fee
+    // collector or the owner must be confirmed by a second admin
+    pub confirm_threshold: Option<u32>,
+    // Seconds a pending action can be confirmed for. Defaults to one day
+    pub confirm_period: Option<u64>,
+    // When true listings added or updated by non-admins wait for an admin to approve them, even if
+    // adding is permissioned. Only approved listings are curated, so wallets should query by status
+    pub review: Option<bool>,
+    // Size limits on the extensions of each listing. Defaults to 16 entries, with keys of up to 64
+    // bytes and values of up to 256 bytes
+    pub extension_limits: Option<ExtensionLimits>,
+}
+
+#[cw_serde]
+pub struct ExtensionLimits {
+    pub max_entries: u32,
+    pub max_key_length: u32,
+    pub max_value_length: u32,
+}
+
+impl Default for ExtensionLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 16,
+            max_key_length: 64,
+            max_value_length: 256,
+        }
+    }
 }
 
 #[cw_serde]
@@ -53,6 +207,55 @@ pub struct Listing {
     // The address of the contract that published this listing. None if it was added by an admin
     pub author: Option<String>,
     pub metadata: Metadata,
+    // Set by an admin for curated listings. Cleared when the author updates the listing
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub status: ListingStatus,
+    // Why an admin rejected the listing or its last edit
+    pub rejection_reason: Option<String>,
+    // An edit by the author waiting for review. The approved metadata is served until it is approved
+    #[serde(default)]
+    pub pending_edit: Option<Metadata>,
+    // When the listing expires unless renewed. None if it doesn't
+    pub expires: Option<Timestamp>,
+}
+
+impl Listing {
+    pub fn expired(&self, now: Timestamp) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    // whether the listing has approved metadata. a listing waiting for review only has approved
+    // metadata when the pending change is an edit
+    pub fn approved(&self) -> bool {
+        self.status == ListingStatus::Approved || self.pending_edit.is_some()
+    }
+
+    // whether the listing is served by the curated queries
+    pub fn live(&self, now: Timestamp) -> bool {
+        self.approved() && !self.expired(now)
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum ListingStatus {
+    #[default]
+    Approved,
+    // Waiting for an admin to review it
+    Pending,
+    Rejected,
+}
+
+impl ListingStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ListingStatus::Approved => "approved",
+            ListingStatus::Pending => "pending",
+            ListingStatus::Rejected => "rejected",
+        }
+    }
 }
 
 #[cw_serde]
@@ -65,4 +268,125 @@ pub struct Metadata {
     pub logo: Option<String>,
     // source chain identifier
     pub chain: Option<String>,
+    // custom (key, value) properties for integrators, e.g. bridge info or display hints. Keys are unique
+    pub extensions: Option<Vec<(String, String)>>,
+}
+
+#[cw_serde]
+pub struct EscrowedFee {
+    // who paid the fee and gets it back
+    pub payer: Addr,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct Deposit {
+    // who paid the deposit and gets it back
+    pub depositor: Addr,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct ChainInfo {
+    // human readable chain name
+    pub name: String,
+    pub bech32_prefix: String,
+    // explorer URL with a {tx} or {address} placeholder, e.g. https://mintscan.io/osmosis/tx/{tx}
+    pub explorer_url: Option<String>,
+}
+
+#[cw_serde]
+pub struct Attestation {
+    // where the full report can be found
+    pub uri: String,
+    // hash of the report, so it can be checked against the uri
+    pub hash: String,
+    // when the attestation stops being valid. None if it doesn't expire
+    pub expiry: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct PendingAction {
+    // The admin that proposed the action, who can't also confirm it
+    pub proposer: Addr,
+    // The action can't be confirmed after this time
+    pub expires: Timestamp,
+    pub change: PendingChange,
+}
+
+#[cw_serde]
+pub enum PendingChange {
+    RemoveListings(Vec<String>),
+    UpdateConfig(Box<Config>),
+    SlashListings { denoms: Vec<String>, reason: String },
+    ClearFeeCollector,
+}
+
+// a Map that keeps its entry count in ENTRY_COUNTS. reads go through the wrapped Map, writes have
+// to use save, update and remove from here so the count stays in sync
+pub struct CountedMap<'a, K, T> {
+    map: Map<'a, K, T>,
+    name: &'a str,
+}
+
+impl<'a, K, T> CountedMap<'a, K, T> {
+    pub const fn new(namespace: &'a str, name: &'a str) -> Self {
+        CountedMap {
+            map: Map::new(namespace),
+            name,
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+}
+
+impl<'a, K, T> Deref for CountedMap<'a, K, T> {
+    type Target = Map<'a, K, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<'a, K, T> CountedMap<'a, K, T>
+where
+    K: PrimaryKey<'a>,
+    T: Serialize + DeserializeOwned,
+{
+    pub fn save(&self, store: &mut dyn Storage, k: K, data: &T) -> StdResult<()> {
+        if !self.map.has(store, k.clone()) {
+            add_entries(store, self.name, 1)?;
+        }
+        self.map.save(store, k, data)
+    }
+
+    pub fn update<A, E>(&self, store: &mut dyn Storage, k: K, action: A) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        let existed = self.map.has(store, k.clone());
+        let data = self.map.update(store, k, action)?;
+        if !existed {
+            add_entries(store, self.name, 1)?;
+        }
+        Ok(data)
+    }
+
+    pub fn remove(&self, store: &mut dyn Storage, k: K) -> StdResult<()> {
+        if self.map.has(store, k.clone()) {
+            self.map.remove(store, k);
+            add_entries(store, self.name, -1)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn add_entries(store: &mut dyn Storage, name: &str, delta: i64) -> StdResult<()> {
+    ENTRY_COUNTS.update(store, name, |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default().saturating_add_signed(delta))
+    })?;
+    Ok(())
 }
@@ -1,9 +1,19 @@
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, ListingMsg, QueryMsg},
-    state::{Config, Field, Metadata},
+    msg::{
+        AttestationMsg, AttestationResponse, ChainMsg, DiffResponse, EventsSchemaResponse,
+        ExecuteMsg, FieldChange, InstantiateMsg, ListingMsg, ListingQuery, PendingMsg, QueryMsg,
+        StorageStatsResponse, SudoMsg, VerifierMsg,
+    },
+    state::{
+        Attestation, ChainInfo, Config, Deposit, Field, Listing, ListingStatus, Metadata,
+        PendingAction, PendingChange,
+    },
+};
+use cosmwasm_std::{coin, Addr, Coin, Timestamp};
+use osmosis_test_tube::{
+    osmosis_std::types::cosmos::bank::v1beta1::QueryBalanceRequest, Account, Bank, Module,
+    OsmosisTestApp, SigningAccount, Wasm,
 };
-use cosmwasm_std::{coin, Addr, Coin};
-use osmosis_test_tube::{Account, Module, OsmosisTestApp, SigningAccount, Wasm};
 
 struct TestEnv {
     app: OsmosisTestApp,
@@ -16,6 +26,41 @@ fn wasm(app: &OsmosisTestApp) -> Wasm<OsmosisTestApp> {
     Wasm::new(app)
 }
 
+fn balance(app: &OsmosisTestApp, address: &str, denom: &str) -> u128 {
+    Bank::new(app)
+        .query_balance(&QueryBalanceRequest {
+            address: address.to_string(),
+            denom: denom.to_string(),
+        })
+        .unwrap()
+        .balance
+        .unwrap()
+        .amount
+        .parse::<u128>()
+        .unwrap()
+}
+
+// a config that changes nothing, for tests to override the fields they update
+fn base_config() -> Config {
+    Config {
+        add_permissioned: None,
+        remove_permissioned: None,
+        required_fields: None,
+        fee: None,
+        deposit: None,
+        update_fee: None,
+        renewal_fee: None,
+        listing_ttl: None,
+        fee_collector: None,
+        admins: None,
+        owner: None,
+        confirm_threshold: None,
+        confirm_period: None,
+        review: None,
+        extension_limits: None,
+    }
+}
+
 fn instantiate_contract() -> TestEnv {
     let app = OsmosisTestApp::new();
 
@@ -56,12 +101,9 @@ fn instantiate_contract() -> TestEnv {
         .instantiate(
             code_id,
             &InstantiateMsg(Config {
-                add_permissioned: None,
-                remove_permissioned: None,
                 required_fields: vec![Field::Exp, Field::Logo, Field::Chain].into(),
                 fee: Some(vec![Coin::new(1_000_000, "uosmo")]),
-                admins: None,
-                owner: None,
+                ..base_config()
             }),
             Some(&test_env.admin.address()),
             Some("test"),
@@ -86,6 +128,7 @@ fn get_valid_listings() -> Vec<(String, Metadata)> {
                 exp: Some(6),
                 logo: Some("https://osmosis.zone/logo.png".to_string()),
                 chain: Some("osmosis-1".to_string()),
+                extensions: None,
             },
         ),
         (
@@ -95,6 +138,7 @@ fn get_valid_listings() -> Vec<(String, Metadata)> {
                 exp: Some(6),
                 logo: Some("https://osmosis.zone/logo.png".to_string()),
                 chain: Some("ion-1".to_string()),
+                extensions: None,
             },
         ),
     ]
@@ -113,6 +157,7 @@ fn add_listings() -> TestEnv {
             exp: Some(6),
             logo: Some("https://osmosis.zone/logo.png".to_string()),
             chain: None,
+            extensions: None,
         },
     )]);
 
@@ -218,13 +263,8 @@ fn test_admin() {
         .execute(
             &test_env.contract_addr,
             &ExecuteMsg::UpdateConfig(Config {
-                add_permissioned: None,
-                remove_permissioned: None,
-                required_fields: None,
-                fee: None,
                 admins: Some(vec![Addr::unchecked(test_env.users[1].address())]),
-                // no update to owner
-                owner: None,
+                ..base_config()
             }),
             &[],
             &test_env.admin,
@@ -252,6 +292,7 @@ fn test_admin() {
                     exp: Some(6),
                     logo: Some("https://osmosis.zone/logo.png".to_string()),
                     chain: Some("osmosis-1".to_string()),
+                    extensions: None,
                 },
             )])),
             &[],
@@ -264,13 +305,8 @@ fn test_admin() {
         .execute(
             &test_env.contract_addr,
             &ExecuteMsg::UpdateConfig(Config {
-                add_permissioned: None,
-                remove_permissioned: None,
-                required_fields: None,
-                fee: None,
                 admins: Some(vec![]),
-                // no update to owner
-                owner: None,
+                ..base_config()
             }),
             &[],
             &test_env.admin,
@@ -292,12 +328,13 @@ fn test_admin() {
 fn test_query() {
     let test_env = add_listings();
 
-    let res: Vec<(String, Metadata)>  = wasm(&test_env.app)
+    let res: Vec<(String, Metadata)> = wasm(&test_env.app)
         .query(
             &test_env.contract_addr,
-            &QueryMsg::Listing(
-                crate::msg::ListingQuery::Denom(vec!["uosmo".to_string(), "uion".to_string()]),
-            ),
+            &QueryMsg::Listing(crate::msg::ListingQuery::Denom(vec![
+                "uosmo".to_string(),
+                "uion".to_string(),
+            ])),
         )
         .unwrap();
 
@@ -309,9 +346,7 @@ fn test_query() {
     let res: Vec<(String, Metadata)> = wasm(&test_env.app)
         .query(
             &test_env.contract_addr,
-            &QueryMsg::Listing(
-                crate::msg::ListingQuery::Symbol(vec!["ION".to_string()]),
-            ),
+            &QueryMsg::Listing(crate::msg::ListingQuery::Symbol(vec!["ION".to_string()])),
         )
         .unwrap();
 
@@ -321,12 +356,10 @@ fn test_query() {
     let res: Vec<(String, Metadata)> = wasm(&test_env.app)
         .query(
             &test_env.contract_addr,
-            &QueryMsg::Listing(
-                crate::msg::ListingQuery::All {
-                    start_after: None,
-                    limit: None,
-                },
-            ),
+            &QueryMsg::Listing(crate::msg::ListingQuery::All {
+                start_after: None,
+                limit: None,
+            }),
         )
         .unwrap();
 
@@ -334,3 +367,1269 @@ fn test_query() {
     assert_eq!(res[0].1, get_valid_listings()[1].1);
     assert_eq!(res[1].1, get_valid_listings()[0].1);
 }
+
+#[test]
+fn test_events_schema() {
+    let test_env = instantiate_contract();
+
+    let res: EventsSchemaResponse = wasm(&test_env.app)
+        .query(&test_env.contract_addr, &QueryMsg::EventsSchema)
+        .unwrap();
+
+    assert_eq!(res.version, env!("CARGO_PKG_VERSION"));
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.action == "assetlist_add_listings"));
+}
+
+#[test]
+fn test_storage_stats() {
+    let test_env = add_listings();
+
+    let res: StorageStatsResponse = wasm(&test_env.app)
+        .query(&test_env.contract_addr, &QueryMsg::StorageStats)
+        .unwrap();
+
+    let count = |name: &str| {
+        res.maps
+            .iter()
+            .find(|(map, _)| map == name)
+            .map(|(_, entries)| *entries)
+            .unwrap()
+    };
+
+    // each added listing also records one revision
+    assert_eq!(count("listings"), 2);
+    assert_eq!(count("history"), 2);
+    assert_eq!(count("pending_actions"), 0);
+}
+
+#[test]
+fn test_governance_remove() {
+    let test_env = add_listings();
+
+    let balance_before = balance(&test_env.app, &test_env.users[0].address(), "uosmo");
+
+    // governance removes one of user[0]'s listings
+    test_env
+        .app
+        .wasm_sudo(
+            &test_env.contract_addr,
+            SudoMsg::RemoveListings(vec!["uosmo".to_string()]),
+        )
+        .unwrap();
+
+    // the listing is gone
+    let res: Result<Vec<(String, Metadata)>, _> = wasm(&test_env.app).query(
+        &test_env.contract_addr,
+        &QueryMsg::Listing(crate::msg::ListingQuery::Denom(vec!["uosmo".to_string()])),
+    );
+
+    assert!(res.is_err());
+
+    // and the listing fee has been refunded to the author
+    assert_eq!(
+        balance(&test_env.app, &test_env.users[0].address(), "uosmo"),
+        balance_before + 1_000_000
+    );
+}
+
+#[test]
+fn test_governance_remove_after_admin_edit() {
+    let test_env = add_listings();
+
+    // an admin edit takes over the listing's authorship
+    let mut listing = get_valid_listings().remove(0);
+    listing.1.logo = Some("https://osmosis.zone/new-logo.png".to_string());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Update(vec![listing])),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let balance_before = balance(&test_env.app, &test_env.users[0].address(), "uosmo");
+
+    test_env
+        .app
+        .wasm_sudo(
+            &test_env.contract_addr,
+            SudoMsg::RemoveListings(vec!["uosmo".to_string()]),
+        )
+        .unwrap();
+
+    // but the fee is still refunded to whoever paid it
+    assert_eq!(
+        balance(&test_env.app, &test_env.users[0].address(), "uosmo"),
+        balance_before + 1_000_000
+    );
+}
+
+#[test]
+fn test_chain_registry() {
+    let test_env = instantiate_contract();
+
+    let osmosis = ChainInfo {
+        name: "Osmosis".to_string(),
+        bech32_prefix: "osmo".to_string(),
+        explorer_url: Some("https://www.mintscan.io/osmosis/tx/{tx}".to_string()),
+    };
+
+    // only admins can manage the registry
+    let res = wasm(&test_env.app).execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Chain(ChainMsg::Set(vec![(
+            "osmosis-1".to_string(),
+            osmosis.clone(),
+        )])),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Chain(ChainMsg::Set(vec![(
+                "osmosis-1".to_string(),
+                osmosis.clone(),
+            )])),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Vec<(String, ChainInfo)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Chains {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res, vec![("osmosis-1".to_string(), osmosis)]);
+
+    // ion-1 is not registered, so the batch is rejected
+    let res = wasm(&test_env.app).execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Listing(ListingMsg::Add(get_valid_listings())),
+        &[coin(2_000_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // a listing on a registered chain can be added
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![get_valid_listings().remove(0)])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_history_diff() {
+    let test_env = add_listings();
+
+    // user[0] changes the logo of their listing
+    let mut listing = get_valid_listings().remove(0);
+    listing.1.logo = Some("https://osmosis.zone/new-logo.png".to_string());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Update(vec![listing.clone()])),
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let history: Vec<(u64, Option<Metadata>)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                denom: "uosmo".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].1, Some(listing.1.clone()));
+
+    let diff: DiffResponse = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Diff {
+                denom: "uosmo".to_string(),
+                from_height: history[0].0,
+                to_height: history[1].0,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        diff.changes,
+        vec![FieldChange {
+            field: "logo".to_string(),
+            from: Some("https://osmosis.zone/logo.png".to_string()),
+            to: Some("https://osmosis.zone/new-logo.png".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_prune_history() {
+    let test_env = add_listings();
+
+    // user[0] updates their listing twice, leaving three revisions
+    let mut listing = get_valid_listings().remove(0);
+    for logo in ["https://osmosis.zone/a.png", "https://osmosis.zone/b.png"] {
+        listing.1.logo = Some(logo.to_string());
+        let _ = wasm(&test_env.app)
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::Listing(ListingMsg::Update(vec![listing.clone()])),
+                &[],
+                &test_env.users[0],
+            )
+            .unwrap();
+    }
+
+    let prune_msg = ExecuteMsg::PruneHistory {
+        denoms: vec!["uosmo".to_string()],
+        keep_last: Some(1),
+        before_height: None,
+    };
+
+    // only the owner can prune
+    let res =
+        wasm(&test_env.app).execute(&test_env.contract_addr, &prune_msg, &[], &test_env.users[0]);
+
+    assert!(res.is_err());
+
+    let res = wasm(&test_env.app)
+        .execute(&test_env.contract_addr, &prune_msg, &[], &test_env.admin)
+        .unwrap();
+
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "pruned" && attribute.value == "2")));
+
+    let history: Vec<(u64, Option<Metadata>)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                denom: "uosmo".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(history, vec![(history[0].0, Some(listing.1))]);
+}
+
+#[test]
+fn test_attestations() {
+    let test_env = add_listings();
+    let auditor = &test_env.users[1];
+
+    let attest = |expiry: Option<Timestamp>| {
+        ExecuteMsg::Attestation(AttestationMsg::Add {
+            denom: "uosmo".to_string(),
+            kind: "audit".to_string(),
+            attestation: Attestation {
+                uri: "https://auditor.example/reports/uosmo.pdf".to_string(),
+                hash: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                    .to_string(),
+                expiry,
+            },
+        })
+    };
+
+    // only registered verifiers can attest
+    let res = wasm(&test_env.app).execute(&test_env.contract_addr, &attest(None), &[], auditor);
+
+    assert!(res.is_err());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Verifier(VerifierMsg::Set(vec![(
+                auditor.address(),
+                "Auditor".to_string(),
+            )])),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let expiry = test_env.app.get_block_timestamp().plus_seconds(86_400);
+    let _ = wasm(&test_env.app)
+        .execute(&test_env.contract_addr, &attest(Some(expiry)), &[], auditor)
+        .unwrap();
+
+    let query = || -> Vec<AttestationResponse> {
+        wasm(&test_env.app)
+            .query(
+                &test_env.contract_addr,
+                &QueryMsg::Attestations {
+                    denom: "uosmo".to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    let res = query();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].verifier, Addr::unchecked(auditor.address()));
+    assert_eq!(res[0].verifier_name, "Auditor");
+    assert!(!res[0].expired);
+
+    test_env.app.increase_time(86_400);
+
+    assert!(query()[0].expired);
+
+    // attestations from a removed verifier are no longer shown
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Verifier(VerifierMsg::Remove(vec![auditor.address()])),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert!(query().is_empty());
+}
+
+#[test]
+fn test_pending_confirmation() {
+    let test_env = add_listings();
+
+    // add user[1] as a second admin, and require confirmation for removals of more than 1 listing
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                admins: Some(vec![Addr::unchecked(test_env.users[1].address())]),
+                confirm_threshold: Some(1),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the bulk removal is held until another admin confirms it
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Remove(vec![
+                "uosmo".to_string(),
+                "uion".to_string(),
+            ])),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let pending: Vec<(u64, PendingAction)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::PendingActions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(pending.len(), 1);
+    assert_eq!(
+        pending[0].1.change,
+        PendingChange::RemoveListings(vec!["uosmo".to_string(), "uion".to_string()])
+    );
+
+    // the proposer can't confirm their own action
+    let res = wasm(&test_env.app).execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Pending(PendingMsg::Confirm(pending[0].0)),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Pending(PendingMsg::Confirm(pending[0].0)),
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let res: Vec<(String, Metadata)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Listing(ListingQuery::All {
+                start_after: None,
+                limit: None,
+            }),
+        )
+        .unwrap();
+
+    assert!(res.is_empty());
+
+    // raising the threshold would undo the rule, so it has to be confirmed as well
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                confirm_threshold: Some(100),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let config: Config = wasm(&test_env.app)
+        .query(&test_env.contract_addr, &QueryMsg::Config)
+        .unwrap();
+
+    assert_eq!(config.confirm_threshold, Some(1));
+
+    // and so does redirecting the fees and slashed deposits
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                fee_collector: Some(Addr::unchecked(test_env.admin.address())),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let config: Config = wasm(&test_env.app)
+        .query(&test_env.contract_addr, &QueryMsg::Config)
+        .unwrap();
+
+    assert_eq!(config.fee_collector, None);
+
+    let pending = || -> Vec<(u64, PendingAction)> {
+        wasm(&test_env.app)
+            .query(
+                &test_env.contract_addr,
+                &QueryMsg::PendingActions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    let (id, _) = pending().pop().unwrap();
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Pending(PendingMsg::Confirm(id)),
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    // clearing the collector can't be used to get around the rule either
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ClearFeeCollector,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let config: Config = wasm(&test_env.app)
+        .query(&test_env.contract_addr, &QueryMsg::Config)
+        .unwrap();
+
+    assert_eq!(
+        config.fee_collector,
+        Some(Addr::unchecked(test_env.admin.address()))
+    );
+    assert_eq!(
+        pending().pop().unwrap().1.change,
+        PendingChange::ClearFeeCollector
+    );
+}
+
+#[test]
+fn test_symbol_index() {
+    let test_env = add_listings();
+
+    let mut metadata = get_valid_listings()[1].1.clone();
+    metadata.symbol = "ION2".to_string();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Update(vec![(
+                "uion".to_string(),
+                metadata.clone(),
+            )])),
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let symbol_query = |symbol: &str| {
+        wasm(&test_env.app).query::<_, Vec<(String, Metadata)>>(
+            &test_env.contract_addr,
+            &QueryMsg::Listing(ListingQuery::Symbol(vec![symbol.to_string()])),
+        )
+    };
+
+    // the index follows the new symbol, and the old one is free to be listed again
+    assert_eq!(
+        symbol_query("ION2").unwrap(),
+        vec![("uion".to_string(), metadata)]
+    );
+    assert!(symbol_query("ION").is_err());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![(
+                "uion2".to_string(),
+                get_valid_listings()[1].1.clone(),
+            )])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(symbol_query("ION").unwrap()[0].0, "uion2");
+}
+
+#[test]
+fn test_query_by_author() {
+    let test_env = add_listings();
+
+    let author_query = |address: String, start_after: Option<String>| {
+        wasm(&test_env.app)
+            .query::<_, Vec<(String, Metadata)>>(
+                &test_env.contract_addr,
+                &QueryMsg::Listing(ListingQuery::Author {
+                    address,
+                    start_after,
+                    limit: None,
+                }),
+            )
+            .unwrap()
+    };
+
+    // sorted by denom, so uion comes before uosmo
+    let res = author_query(test_env.users[0].address(), None);
+    assert_eq!(
+        res,
+        vec![
+            get_valid_listings()[1].clone(),
+            get_valid_listings()[0].clone()
+        ]
+    );
+
+    let res = author_query(test_env.users[0].address(), Some("uion".to_string()));
+    assert_eq!(res, vec![get_valid_listings()[0].clone()]);
+
+    assert!(author_query(test_env.users[1].address(), None).is_empty());
+}
+
+#[test]
+fn test_query_by_chain() {
+    let test_env = add_listings();
+
+    let chain_query = |chain_id: &str| {
+        wasm(&test_env.app)
+            .query::<_, Vec<(String, Metadata)>>(
+                &test_env.contract_addr,
+                &QueryMsg::Listing(ListingQuery::Chain {
+                    chain_id: chain_id.to_string(),
+                    start_after: None,
+                    limit: None,
+                }),
+            )
+            .unwrap()
+    };
+
+    assert_eq!(
+        chain_query("osmosis-1"),
+        vec![get_valid_listings()[0].clone()]
+    );
+    assert_eq!(chain_query("ion-1"), vec![get_valid_listings()[1].clone()]);
+    assert!(chain_query("noble-1").is_empty());
+}
+
+#[test]
+fn test_extensions() {
+    let test_env = add_listings();
+
+    let update = |extensions: Vec<(String, String)>| {
+        let mut listing = get_valid_listings().remove(0);
+        listing.1.extensions = Some(extensions);
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Update(vec![listing])),
+            &[],
+            &test_env.users[0],
+        )
+    };
+
+    let bridge = ("bridge".to_string(), "ibc".to_string());
+
+    // keys must be unique, and the default limit is 16 entries
+    assert!(update(vec![bridge.clone(), bridge.clone()]).is_err());
+    assert!(update(
+        (0..17)
+            .map(|i| (format!("key{i}"), "value".to_string()))
+            .collect()
+    )
+    .is_err());
+    assert!(update(vec![("bridge".to_string(), "x".repeat(257))]).is_err());
+
+    let _ = update(vec![bridge.clone()]).unwrap();
+
+    let res: Vec<(String, Metadata)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Listing(ListingQuery::Denom(vec!["uosmo".to_string()])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1.extensions, Some(vec![bridge]));
+
+    let history: Vec<(u64, Option<Metadata>)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                denom: "uosmo".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    let diff: DiffResponse = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Diff {
+                denom: "uosmo".to_string(),
+                from_height: history[0].0,
+                to_height: history[1].0,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        diff.changes,
+        vec![FieldChange {
+            field: "extensions.bridge".to_string(),
+            from: None,
+            to: Some("ibc".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_verified() {
+    let test_env = add_listings();
+
+    let set_verified = |verified: bool, sender: &SigningAccount| {
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::SetVerified {
+                denoms: vec!["uosmo".to_string()],
+                verified,
+            }),
+            &[],
+            sender,
+        )
+    };
+
+    let verified_query = || {
+        wasm(&test_env.app)
+            .query::<_, Vec<(String, Metadata)>>(
+                &test_env.contract_addr,
+                &QueryMsg::Listing(ListingQuery::Verified {
+                    start_after: None,
+                    limit: None,
+                }),
+            )
+            .unwrap()
+    };
+
+    // only admins can verify listings
+    assert!(set_verified(true, &test_env.users[0]).is_err());
+    let _ = set_verified(true, &test_env.admin).unwrap();
+
+    assert_eq!(verified_query(), vec![get_valid_listings()[0].clone()]);
+
+    // an update by the author clears the verification
+    let mut listing = get_valid_listings().remove(0);
+    listing.1.logo = Some("https://osmosis.zone/new-logo.png".to_string());
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Update(vec![listing])),
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert!(verified_query().is_empty());
+}
+
+#[test]
+fn test_review() {
+    let test_env = add_listings();
+
+    // admins can add listings even when adding is permissioned, others have to be reviewed
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                add_permissioned: Some(true),
+                review: Some(true),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mut listing = get_valid_listings().remove(0);
+    listing.0 = "uosmo2".to_string();
+    listing.1.symbol = "OSMO2".to_string();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![listing.clone()])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let status_query = |status: ListingStatus| {
+        wasm(&test_env.app)
+            .query::<_, Vec<(String, Listing)>>(
+                &test_env.contract_addr,
+                &QueryMsg::Status {
+                    status,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    let pending = status_query(ListingStatus::Pending);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].0, "uosmo2");
+    assert_eq!(status_query(ListingStatus::Approved).len(), 2);
+
+    let review = |msg: ListingMsg, sender: &SigningAccount| {
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(msg),
+            &[],
+            sender,
+        )
+    };
+
+    let reject = || ListingMsg::Reject {
+        denoms: vec!["uosmo2".to_string()],
+        reason: "missing logo".to_string(),
+    };
+
+    // only admins can review listings
+    assert!(review(reject(), &test_env.users[0]).is_err());
+    let _ = review(reject(), &test_env.admin).unwrap();
+
+    let rejected = status_query(ListingStatus::Rejected);
+    assert_eq!(
+        rejected[0].1.rejection_reason,
+        Some("missing logo".to_string())
+    );
+
+    // an update by the author submits it for review again
+    let _ = review(
+        ListingMsg::Update(vec![listing.clone()]),
+        &test_env.users[1],
+    )
+    .unwrap();
+    assert_eq!(status_query(ListingStatus::Pending).len(), 1);
+
+    let _ = review(
+        ListingMsg::Approve(vec!["uosmo2".to_string()]),
+        &test_env.admin,
+    )
+    .unwrap();
+
+    assert!(status_query(ListingStatus::Pending).is_empty());
+    assert_eq!(status_query(ListingStatus::Approved).len(), 3);
+
+    // approved listings are no longer pending
+    assert!(review(reject(), &test_env.admin).is_err());
+
+    let denom_query = |denom: &str| {
+        wasm(&test_env.app).query::<_, Vec<(String, Metadata)>>(
+            &test_env.contract_addr,
+            &QueryMsg::Listing(ListingQuery::Denom(vec![denom.to_string()])),
+        )
+    };
+
+    // an edit keeps serving the approved metadata until it is approved as well
+    let mut edit = listing.clone();
+    edit.1.logo = Some("https://osmosis.zone/scam.png".to_string());
+    let _ = review(ListingMsg::Update(vec![edit]), &test_env.users[1]).unwrap();
+
+    assert_eq!(denom_query("uosmo2").unwrap()[0].1, listing.1);
+    assert_eq!(status_query(ListingStatus::Pending).len(), 1);
+
+    let _ = review(reject(), &test_env.admin).unwrap();
+    assert_eq!(denom_query("uosmo2").unwrap()[0].1, listing.1);
+
+    // pending submissions aren't served, and rejected ones free their symbol
+    let mut other = get_valid_listings().remove(0);
+    other.0 = "uosmo3".to_string();
+    other.1.symbol = "OSMO3".to_string();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![other.clone()])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[1],
+        )
+        .unwrap();
+    assert!(denom_query("uosmo3").is_err());
+
+    let _ = review(
+        ListingMsg::Reject {
+            denoms: vec!["uosmo3".to_string()],
+            reason: "scam".to_string(),
+        },
+        &test_env.admin,
+    )
+    .unwrap();
+
+    other.0 = "uosmo4".to_string();
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![other])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_withdraw_fees() {
+    let test_env = add_listings();
+
+    let withdraw = |amount: u128, sender: &SigningAccount| {
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::WithdrawFees {
+                to: test_env.users[1].address(),
+                amount: vec![coin(amount, "uosmo")],
+            },
+            &[],
+            sender,
+        )
+    };
+
+    // both listing fees are escrowed for a possible governance refund
+    assert!(withdraw(1, &test_env.admin).is_err());
+
+    // fees are kept when a listing is removed voluntarily
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Remove(vec!["uosmo".to_string()])),
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // only the owner can withdraw
+    assert!(withdraw(1_000_000, &test_env.users[0]).is_err());
+    assert!(withdraw(1_000_001, &test_env.admin).is_err());
+
+    let before = balance(&test_env.app, &test_env.users[1].address(), "uosmo");
+    let _ = withdraw(1_000_000, &test_env.admin).unwrap();
+
+    assert_eq!(
+        balance(&test_env.app, &test_env.users[1].address(), "uosmo"),
+        before + 1_000_000
+    );
+}
+
+#[test]
+fn test_fee_collector() {
+    let test_env = add_listings();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                fee_collector: Some(Addr::unchecked(test_env.users[1].address())),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mut listing = get_valid_listings().remove(0);
+    listing.0 = "uosmo2".to_string();
+    listing.1.symbol = "OSMO2".to_string();
+
+    let collector_before = balance(&test_env.app, &test_env.users[1].address(), "uosmo");
+    let contract_before = balance(&test_env.app, &test_env.contract_addr, "uosmo");
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![listing])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // the fee is forwarded in the same transaction instead of being escrowed
+    assert_eq!(
+        balance(&test_env.app, &test_env.users[1].address(), "uosmo"),
+        collector_before + 1_000_000
+    );
+    assert_eq!(
+        balance(&test_env.app, &test_env.contract_addr, "uosmo"),
+        contract_before
+    );
+
+    // once the collector is cleared, fees are escrowed again
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ClearFeeCollector,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mut listing = get_valid_listings().remove(0);
+    listing.0 = "uosmo3".to_string();
+    listing.1.symbol = "OSMO3".to_string();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![listing])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env.app, &test_env.contract_addr, "uosmo"),
+        contract_before + 1_000_000
+    );
+}
+
+#[test]
+fn test_fee_refund() {
+    let test_env = add_listings();
+
+    let mut listing = get_valid_listings().remove(0);
+    listing.0 = "uosmo2".to_string();
+    listing.1.symbol = "OSMO2".to_string();
+
+    let contract_before = balance(&test_env.app, &test_env.contract_addr, "uosmo");
+
+    let res = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![listing])),
+            &[coin(3_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // only the fee for one listing is kept
+    assert_eq!(
+        balance(&test_env.app, &test_env.contract_addr, "uosmo"),
+        contract_before + 1_000_000
+    );
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "refund" && attr.value == "2000000uosmo")));
+}
+
+#[test]
+fn test_update_fee() {
+    let test_env = add_listings();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                update_fee: Some(vec![Coin::new(500_000, "uosmo")]),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mut listing = get_valid_listings().remove(0);
+    listing.1.logo = Some("https://osmosis.zone/new-logo.png".to_string());
+
+    let update = |funds: &[Coin]| {
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Update(vec![listing.clone()])),
+            funds,
+            &test_env.users[0],
+        )
+    };
+
+    // updates are now charged as well
+    assert!(update(&[]).is_err());
+
+    let contract_before = balance(&test_env.app, &test_env.contract_addr, "uosmo");
+    let _ = update(&[coin(500_000, "uosmo")]).unwrap();
+
+    assert_eq!(
+        balance(&test_env.app, &test_env.contract_addr, "uosmo"),
+        contract_before + 500_000
+    );
+}
+
+#[test]
+fn test_expiry() {
+    let test_env = add_listings();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                renewal_fee: Some(vec![Coin::new(100_000, "uosmo")]),
+                listing_ttl: Some(86_400),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mut listing = get_valid_listings().remove(0);
+    listing.0 = "uosmo2".to_string();
+    listing.1.symbol = "OSMO2".to_string();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(vec![listing])),
+            &[coin(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let query = || {
+        wasm(&test_env.app).query::<_, Vec<(String, Metadata)>>(
+            &test_env.contract_addr,
+            &QueryMsg::Listing(ListingQuery::Denom(vec!["uosmo2".to_string()])),
+        )
+    };
+    let renew = |funds: &[Coin]| {
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Renew(vec!["uosmo2".to_string()])),
+            funds,
+            &test_env.users[1],
+        )
+    };
+
+    assert!(query().is_ok());
+
+    // anyone can renew a listing, but must pay the renewal fee
+    test_env.app.increase_time(43_200);
+    assert!(renew(&[]).is_err());
+    let _ = renew(&[coin(100_000, "uosmo")]).unwrap();
+
+    // the listing would have expired without the renewal
+    test_env.app.increase_time(43_200);
+    assert!(query().is_ok());
+
+    test_env.app.increase_time(86_400);
+    assert!(query().is_err());
+
+    // listings added before the ttl was set don't expire
+    let res: Vec<(String, Metadata)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Listing(ListingQuery::All {
+                start_after: None,
+                limit: None,
+            }),
+        )
+        .unwrap();
+    assert_eq!(res.len(), get_valid_listings().len());
+
+    let res = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::PruneExpired { limit: None }),
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "pruned" && attr.value == "1")));
+
+    // pruned listings can't be renewed
+    assert!(renew(&[coin(100_000, "uosmo")]).is_err());
+}
+
+#[test]
+fn test_deposits() {
+    let test_env = add_listings();
+
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateConfig(Config {
+                deposit: Some(vec![Coin::new(5_000_000, "uatom")]),
+                ..base_config()
+            }),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let new_listings: Vec<(String, Metadata)> = ["2", "3"]
+        .iter()
+        .map(|suffix| {
+            let mut listing = get_valid_listings().remove(0);
+            listing.0 = format!("uosmo{suffix}");
+            listing.1.symbol = format!("OSMO{suffix}");
+            listing
+        })
+        .collect();
+
+    // the deposit is taken instead of the listing fee
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Add(new_listings)),
+            &[coin(10_000_000, "uatom")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let deposit: Deposit = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Deposit("uosmo2".to_string()),
+        )
+        .unwrap();
+    assert_eq!(deposit.depositor.as_str(), test_env.users[0].address());
+    assert_eq!(deposit.amount, coin(5_000_000, "uatom"));
+
+    let deposits: Vec<(String, Deposit)> = wasm(&test_env.app)
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Deposits {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(deposits.len(), 2);
+
+    // removing a listing in good standing refunds the deposit
+    let user_before = balance(&test_env.app, &test_env.users[0].address(), "uatom");
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Remove(vec!["uosmo2".to_string()])),
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+    assert_eq!(
+        balance(&test_env.app, &test_env.users[0].address(), "uatom"),
+        user_before + 5_000_000
+    );
+
+    let slash = |sender: &SigningAccount| {
+        wasm(&test_env.app).execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Listing(ListingMsg::Slash {
+                denoms: vec!["uosmo3".to_string()],
+                reason: "spam".to_string(),
+            }),
+            &[],
+            sender,
+        )
+    };
+
+    // only admins can slash
+    assert!(slash(&test_env.users[1]).is_err());
+    let _ = slash(&test_env.admin).unwrap();
+
+    // the listing is gone and its deposit is kept
+    let res: Result<Deposit, _> = wasm(&test_env.app).query(
+        &test_env.contract_addr,
+        &QueryMsg::Deposit("uosmo3".to_string()),
+    );
+    assert!(res.is_err());
+    assert_eq!(
+        balance(&test_env.app, &test_env.users[0].address(), "uatom"),
+        user_before + 5_000_000
+    );
+
+    // where the owner can withdraw it
+    let _ = wasm(&test_env.app)
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::WithdrawFees {
+                to: test_env.admin.address(),
+                amount: vec![coin(5_000_000, "uatom")],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+}
@@ -1,21 +1,292 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, Receiver};
-use crate::state::{ADMIN, DENOM, MAX_SUPPLY, SYMBOL, TOTAL_MINTED};
+use crate::helpers;
+use crate::msg::{
+    AllowlistResponse, AssetlistRegistration, BucketInfo, BucketsResponse, CanMintResponse,
+    ContractInfoResponse, DeadManSwitchResponse, DenomInfo, DenomMetadata, DenomPreviewResponse,
+    DenomsResponse, EmissionResponse, EventSchema, EventsSchemaResponse, ExecuteMsg,
+    InstantiateMsg, InstantiateResponse, MaxWalletResponse, MintCostResponse,
+    MultisigInstantiateMsg, PhaseResponse, PriceResponse, ProceedsVestingResponse,
+    ProposalResponse, PurchasesResponse, QueryMsg, Receiver, ReceiverCheck, RolesResponse,
+    SendPolicyResponse, StakesResponse, StorageStatsResponse, SudoMsg, SupportedInterface,
+    SupportedInterfacesResponse, TakerFeeProposalResponse, TransferBurnMsg, TransferTaxMsg,
+    VestingResponse,
+};
+use crate::state::{
+    Allowance, Bucket, ClPool, Curve, DeadManSwitch, Distribution, Emission, Governance,
+    HistoryEntry, LockTier, ManagedDenom, MintTax, PendingPosition, PendingRevoke, ProceedsVesting,
+    ProjectInfo, Proposal, PublicMint, QueuedAction, Role, StakePosition, Staker, Staking, Stats,
+    SymbolRules, TakerFeeProposal, VestingGrant, VestingSchedule, ALLOWANCES, ALLOWLIST, BUCKETS,
+    CLAWBACK, CL_POOL, CRANKER, CRANK_REWARD, DEAD_MAN_SWITCH, DENOM, DENOMS, DENOM_MINTERS,
+    DISTRIBUTIONS, DISTRIBUTION_CLAIMS, DISTRIBUTION_HELD, DISTRIBUTION_ID, EMISSION,
+    EMISSION_MINTED, ENTRY_COUNTS, EXPONENT, GOVERNANCE, HISTORY, HISTORY_ID, IBC_HOOKS,
+    LAST_ACTIVITY, MAX_SUPPLY, MAX_WALLET, MAX_WALLET_EXEMPT, MINTED_TO, MINT_END, MINT_FEE,
+    MINT_LIMIT, MINT_START, MINT_TAX, NULL_ADDRESS, PAUSED, PENDING_POSITION, PENDING_REVOKE,
+    PENDING_TAKER_FEE, PHASE_MINTED, PRESALE, PRESALE_ALLOWLIST, PRESALE_PURCHASED,
+    PROCEEDS_VESTING, PROJECT_INFO, PROPOSALS, PROPOSAL_DEPOSITS, PROPOSAL_ID, PUBLIC_MINT,
+    PUBLIC_MINTED, QUEUED_ACTIONS, QUEUE_ID, REDEEMED, RESERVE, ROLES, SCHEDULE, SEND_DESTINATIONS,
+    SEND_DISABLED, SEND_POLICY, STAKERS, STAKE_ID, STAKE_POSITIONS, STAKING, STATS, SUPPLY_LOCKED,
+    SUPPORTED_INTERFACES, SYMBOL, SYMBOL_RULES, TAKER_FEE_PROPOSALS, TFA_PREFIX, TIMELOCK,
+    TOTAL_MINTED, TRADING_START, TRANSFER_BURN, TRANSFER_BURNED, TRANSFER_FEE_EXEMPT, TRANSFER_TAX,
+    TRANSFER_TAX_RENOUNCED, UNLOCK_WEIGHTS, VESTING_CLAIMED, VESTING_DEPOSITED, VESTING_GRANTS,
+    VESTING_LOCKED, VOTES,
+};
+use crate::tokenfactory;
+use assetlist::msg::{ExecuteMsg as AssetlistExecuteMsg, ListingMsg};
 use bech32::{decode, encode};
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128,
+    entry_point, to_json_binary, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Decimal, Decimal256,
+    Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, QuerierWrapper, Reply, Response,
+    StdError, StdResult, Storage, SubMsg, SubMsgResult, Timestamp, Uint128, Uint256, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use cw_utils::{nonpayable, parse_reply_instantiate_data};
+use osmosis_std::shim::{Duration as ProtoDuration, Timestamp as ProtoTimestamp};
+use osmosis_std::types::cosmos::{
+    bank::v1beta1::{BankQuerier, DenomUnit, Metadata as BankMetadata},
+    base::v1beta1::Coin as ProtoCoin,
+    gov::v1beta1::{GovQuerier, MsgSubmitProposal, MsgSubmitProposalResponse, ProposalStatus},
+};
+use osmosis_std::types::osmosis::concentratedliquidity::{
+    poolmodel::concentrated::v1beta1::{
+        MsgCreateConcentratedPool, MsgCreateConcentratedPoolResponse,
+    },
+    v1beta1::{
+        ConcentratedliquidityQuerier, MsgCreatePosition, MsgCreatePositionResponse,
+        MsgWithdrawPosition, Pool as ClPoolInfo,
+    },
+};
+use osmosis_std::types::osmosis::incentives::MsgCreateGauge;
+use osmosis_std::types::osmosis::lockup::{LockQueryType, QueryCondition};
+use osmosis_std::types::osmosis::poolmanager::v1beta1::{
+    DenomPairTakerFee, DenomPairTakerFeeProposal, MsgSwapExactAmountIn,
+    MsgSwapExactAmountInResponse, PoolmanagerQuerier, SwapAmountInRoute,
 };
-use cw2::set_contract_version;
-use osmosis_std::types::cosmos::{bank::v1beta1::BankQuerier, base::v1beta1::Coin};
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::{
-    MsgBurn, MsgChangeAdmin, MsgCreateDenom, MsgMint, TokenfactoryQuerier,
+    MsgForceTransfer, MsgSetBeforeSendHook, MsgSetDenomMetadata, TokenfactoryQuerier,
 };
+use osmosis_std::types::osmosis::twap::v1beta1::TwapQuerier;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
 
 // version info
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Reply ids
+const TAKER_FEE_PROPOSAL_REPLY_ID: u64 = 1;
+const CREATE_DENOM_REPLY_ID: u64 = 2;
+const CREATE_POOL_REPLY_ID: u64 = 3;
+const CREATE_POSITION_REPLY_ID: u64 = 4;
+const BUYBACK_REPLY_ID: u64 = 5;
+const MULTISIG_REPLY_ID: u64 = 6;
+// the crank replies with the id of the scheduled action added to this
+const CRANK_REPLY_ID_OFFSET: u64 = 1 << 32;
+
+// The full tick range of a concentrated liquidity pool, compatible with the usual tick spacings
+const MIN_TICK: i64 = -108_000_000;
+const MAX_TICK: i64 = 342_000_000;
+
+// The exponent of the display unit when none is given, as used by most tokenfactory denoms
+const DEFAULT_EXPONENT: u32 = 6;
+
+// The name the gov module account address is derived from
+const GOV_MODULE_NAME: &str = "gov";
+
+// Tokenfactory's limit on subdenom length
+const MAX_SUBDENOM_LENGTH: usize = 44;
+
+// Pagination for queries
+const MAX_PAGE_LIMIT: u32 = 250;
+
+// how many mints, burns and sends are kept in the history
+const HISTORY_LIMIT: u64 = 1000;
+
+// the window the Price query averages over, in seconds
+const TWAP_WINDOW: u64 = 3_600;
+
+// the longest each project info field can be
+const MAX_PROJECT_INFO_LENGTH: usize = 512;
+
+// the most actions a proposal can execute, and the longest its title and description can be
+const MAX_PROPOSAL_MSGS: usize = 16;
+const MAX_PROPOSAL_TITLE_LENGTH: usize = 128;
+const MAX_PROPOSAL_DESCRIPTION_LENGTH: usize = 4096;
+
+// The typed events emitted by this contract version and their attribute keys
+const TYPED_EVENTS: &[(&str, &[&str])] = &[
+    ("tfa-mint", &["denom", "recipient", "amount", "new_total"]),
+    ("tfa-burn", &["denom", "amount"]),
+    ("tfa-send", &["denom", "recipient", "amount"]),
+    ("tfa-revoke", &["denom", "new_admin"]),
+    ("tfa-symbol", &["denom", "old_symbol", "new_symbol"]),
+    (
+        "tfa-reconcile",
+        &[
+            "denom",
+            "supply",
+            "minted",
+            "burned",
+            "minted_delta",
+            "cap_exceeded",
+        ],
+    ),
+];
+
+// The actions emitted by this contract version and the attribute keys that accompany them
+const EVENTS: &[(&str, &[&str])] = &[
+    (
+        "factory_instantiate",
+        &[
+            "trading_start",
+            "initial_mint",
+            "creation_fee",
+            "assetlist",
+            "refund",
+            "multisig_code_id",
+        ],
+    ),
+    ("factory_denom_created", &["denom"]),
+    ("factory_multisig", &["multisig"]),
+    (
+        "factory_mint",
+        &[
+            "recipient",
+            "amount",
+            "tax",
+            "bucket",
+            "contract",
+            "vesting_receiver",
+            "total_minted",
+            "buyer",
+            "payment",
+            "treasury",
+            "vested",
+            "mint_fee",
+            "fee_collector",
+        ],
+    ),
+    ("factory_set_bucket", &["bucket", "amount"]),
+    ("factory_set_bucket_minters", &["bucket", "minters"]),
+    ("factory_burn", &["amount", "burner"]),
+    (
+        "factory_transfer_funds",
+        &["sender", "recipient", "amount", "burned", "tax"],
+    ),
+    (
+        "factory_transfer",
+        &["recipient", "amount", "total_transferred"],
+    ),
+    ("factory_send_and_call", &["contract", "amount"]),
+    (
+        "factory_increase_allowance",
+        &["spender", "amount", "allowance"],
+    ),
+    (
+        "factory_decrease_allowance",
+        &["spender", "amount", "allowance"],
+    ),
+    (
+        "factory_transfer_from",
+        &["spender", "recipient", "amount", "allowance"],
+    ),
+    ("factory_sweep", &["recipient", "amount"]),
+    ("factory_rescue", &["recipient", "amount"]),
+    ("factory_update_supply", &[]),
+    ("factory_set_mint_tax", &["rate", "treasury"]),
+    ("factory_set_mint_limit", &["limit"]),
+    ("factory_set_emission", &["recipient", "total"]),
+    ("factory_mint_emission", &["recipient", "amount"]),
+    ("factory_lock_supply", &["max_supply"]),
+    ("factory_revoke", &["new_admin"]),
+    ("factory_renounce_contract", &["sweep_to", "amount"]),
+    ("factory_handover_to_gov", &["new_admin"]),
+    ("factory_add_role", &["role", "address"]),
+    ("factory_remove_role", &["role", "address"]),
+    ("factory_pause", &[]),
+    ("factory_unpause", &[]),
+    ("factory_set_denom_metadata", &["name"]),
+    ("factory_set_project_info", &[]),
+    ("factory_redeem", &["amount", "payout"]),
+    ("factory_claim_vested", &["beneficiary", "amount"]),
+    ("factory_claim", &["receiver", "amount"]),
+    ("factory_clawback", &["from", "to", "amount", "reason"]),
+    (
+        "factory_configure_staking",
+        &["reward_denom", "reward_rate"],
+    ),
+    ("factory_fund_staking", &["funder", "amount"]),
+    ("factory_stake", &["staker", "id", "amount", "unlock"]),
+    ("factory_unstake", &["staker", "id", "amount"]),
+    ("factory_claim_rewards", &["staker", "amount"]),
+    (
+        "factory_configure_governance",
+        &["voting_period", "quorum", "threshold", "execution_period"],
+    ),
+    ("factory_propose", &["id", "proposer", "end", "expires"]),
+    ("factory_vote", &["id", "voter", "approve", "power"]),
+    ("factory_execute_proposal", &["id"]),
+    ("factory_settle_deposit", &["id", "refunded", "burned"]),
+    (
+        "factory_create_distribution",
+        &["id", "snapshot_height", "rewards", "expiry"],
+    ),
+    ("factory_claim_distribution", &["id", "holder", "balance"]),
+    ("factory_sweep_distribution", &["id", "recipient"]),
+    ("factory_heartbeat", &[]),
+    ("factory_claim_ownership", &["fallback"]),
+    (
+        "factory_create_cl_pool",
+        &["amount", "quote", "locked_until"],
+    ),
+    ("factory_cl_pool_created", &["pool_id"]),
+    ("factory_cl_position_created", &["position_id"]),
+    ("factory_withdraw_position", &["position_id"]),
+    ("factory_buyback", &["offer", "pool_id", "min_out"]),
+    ("factory_buyback_burn", &["amount"]),
+    ("factory_finalize", &["recipient", "assetlist"]),
+    (
+        "factory_schedule_revoke",
+        &["executable_after", "new_admin"],
+    ),
+    ("factory_cancel_revoke", &[]),
+    ("factory_update_allowlist", &["added", "removed"]),
+    ("factory_update_max_wallet_exempt", &["added", "removed"]),
+    ("factory_update_transfer_fee_exempt", &["added", "removed"]),
+    ("factory_reconcile", &["minted_delta"]),
+    ("factory_update_symbol", &["old_symbol", "new_symbol"]),
+    (
+        "factory_update_send_policy",
+        &["enabled", "added", "removed"],
+    ),
+    ("factory_disable_send", &[]),
+    ("factory_disable_transfer_burn", &[]),
+    ("factory_set_transfer_tax", &["rate", "treasury", "exempt"]),
+    ("factory_renounce_transfer_tax", &[]),
+    ("factory_create_gauge", &["pool_id", "amount", "epochs"]),
+    ("factory_create_denom", &["symbol", "denom", "max_supply"]),
+    ("factory_mint_denom", &["symbol", "recipient", "amount"]),
+    ("factory_burn_denom", &["symbol", "amount"]),
+    (
+        "factory_update_denom_minters",
+        &["symbol", "added", "removed"],
+    ),
+    ("factory_multi", &[]),
+    ("factory_update_ibc_hooks", &["channel", "senders"]),
+    ("factory_queue", &["id", "executable_after"]),
+    ("factory_execute_queued", &["id"]),
+    ("factory_cancel_queued", &["id"]),
+    ("factory_schedule", &["id", "executable_after"]),
+    ("factory_crank", &["id"]),
+    ("factory_crank_failed", &["id", "error"]),
+    ("factory_crank_reward", &["reward"]),
+    ("factory_set_crank_reward", &["reward"]),
+    ("factory_propose_taker_fee", &["quote_denom", "taker_fee"]),
+    ("factory_taker_fee_proposal", &["proposal_id"]),
+];
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -23,8 +294,12 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    // use the sender as the admin, unless one is provided. validate the admin address
-    let admin = msg.admin.unwrap_or(info.sender);
+    // a multisig becomes the only owner, so no admin can be provided alongside it
+    if msg.multisig.is_some() && msg.admin.is_some() {
+        return Err(ContractError::InvalidMultisig);
+    }
+    // use the sender as the owner, unless one is provided. validate the owner address
+    let admin = msg.admin.unwrap_or(info.sender.clone());
     deps.api.addr_validate(admin.as_str())?;
 
     let initial_supply = msg.initial_supply.unwrap_or(Uint128::zero());
@@ -35,276 +310,5960 @@ pub fn instantiate(
         return Err(ContractError::SupplyCap);
     }
 
-    // tokenfactory denoms are in the format "factory/{creator_address}/{subdenom}".
-    // we add the custom subspace '/tfa/' to identify it as created by this contract
-    let subdenom = format!("tfa/{}", msg.symbol);
-    let denom = format!(
-        "factory/{}/{}",
-        env.contract.address.clone().into_string(),
-        subdenom
+    let symbol_rules = msg.symbol_rules.unwrap_or_default();
+    if symbol_rules.min_length == 0
+        || symbol_rules.min_length > symbol_rules.max_length
+        || symbol_rules.extra_chars.contains('/')
+    {
+        return Err(ContractError::InvalidSymbolRules);
+    }
+    validate_symbol(&symbol_rules, &msg.symbol)?;
+
+    let tfa_prefix = msg.tfa_prefix.unwrap_or_default();
+    let subdenom = helpers::subdenom(&msg.symbol, msg.subdenom.as_deref(), tfa_prefix);
+    validate_subdenom(&subdenom)?;
+    let denom = helpers::denom(
+        env.contract.address.as_str(),
+        &msg.symbol,
+        msg.subdenom.as_deref(),
+        tfa_prefix,
     );
 
-    ADMIN.save(deps.storage, &admin)?;
+    // with a multisig, the owner is only known once it is instantiated
+    if msg.multisig.is_none() {
+        ROLES.save(deps.storage, (Role::Owner.as_str(), &admin), &Empty {})?;
+    }
     DENOM.save(deps.storage, &denom)?;
+    if tfa_prefix {
+        TFA_PREFIX.save(deps.storage, &true)?;
+    }
     SYMBOL.save(deps.storage, &msg.symbol)?;
+    SYMBOL_RULES.save(deps.storage, &symbol_rules)?;
     MAX_SUPPLY.save(deps.storage, &max_supply.u128())?;
+    EXPONENT.save(deps.storage, &msg.exponent.unwrap_or(DEFAULT_EXPONENT))?;
     TOTAL_MINTED.save(deps.storage, &initial_supply.u128())?;
+    if let (Some(start), Some(end)) = (msg.mint_start, msg.mint_end) {
+        if end <= start {
+            return Err(ContractError::InvalidMintWindow);
+        }
+    }
+    if let Some(mint_start) = msg.mint_start {
+        MINT_START.save(deps.storage, &mint_start)?;
+    }
+    if let Some(mint_end) = msg.mint_end {
+        MINT_END.save(deps.storage, &mint_end)?;
+    }
+    if let Some(timelock) = msg.timelock {
+        TIMELOCK.save(deps.storage, &timelock)?;
+    }
+    if let Some(null_address) = msg.null_address {
+        NULL_ADDRESS.save(deps.storage, &deps.api.addr_validate(&null_address)?)?;
+    }
+    if msg.clawback.unwrap_or_default() {
+        if msg.timelock.is_none() {
+            return Err(ContractError::ClawbackTimelock);
+        }
+        CLAWBACK.save(deps.storage, &true)?;
+    }
+    if let Some(dead_man_switch) = msg.dead_man_switch {
+        if let Some(fallback) = &dead_man_switch.fallback {
+            deps.api.addr_validate(fallback.as_str())?;
+        }
+        DEAD_MAN_SWITCH.save(deps.storage, &dead_man_switch)?;
+        LAST_ACTIVITY.save(deps.storage, &env.block.time)?;
+    }
+    if let Some(public_mint) = msg.public_mint {
+        if let Some(treasury) = &public_mint.treasury {
+            deps.api.addr_validate(treasury.as_str())?;
+        }
+        match &public_mint.curve {
+            Some(_) if public_mint.treasury.is_some() => {
+                return Err(ContractError::ReserveTreasury)
+            }
+            Some(Curve::Exponential { step, .. }) if step.is_zero() => {
+                return Err(ContractError::InvalidCurve)
+            }
+            _ => {}
+        }
+        if let Some(root) = &public_mint.merkle_root {
+            if !matches!(hex::decode(root), Ok(root) if root.len() == 32) {
+                return Err(ContractError::InvalidMerkleRoot);
+            }
+        }
+        PUBLIC_MINT.save(deps.storage, &public_mint)?;
+    }
+    if let Some(vesting) = msg.proceeds_vesting {
+        // only proceeds headed to a treasury can be vested. a reserve has to stay redeemable
+        let has_treasury = PUBLIC_MINT
+            .may_load(deps.storage)?
+            .is_some_and(|public_mint| public_mint.treasury.is_some());
+        if !has_treasury || vesting.share > Decimal::one() || vesting.cliff > vesting.duration {
+            return Err(ContractError::InvalidVesting);
+        }
+        deps.api.addr_validate(vesting.beneficiary.as_str())?;
+        PROCEEDS_VESTING.save(deps.storage, &vesting)?;
+        VESTING_DEPOSITED.save(deps.storage, &Uint128::zero())?;
+        VESTING_CLAIMED.save(deps.storage, &Uint128::zero())?;
+    }
+    if let Some(presale) = msg.presale {
+        if !PUBLIC_MINT.exists(deps.storage) || presale.presale.end <= presale.presale.start {
+            return Err(ContractError::InvalidPresale);
+        }
+        for address in &presale.allowlist {
+            let address = validate_address(deps.api, env.contract.address.as_str(), address)?;
+            PRESALE_ALLOWLIST.save(deps.storage, &address, &Empty {})?;
+        }
+        PRESALE.save(deps.storage, &presale.presale)?;
+    }
+    if let Some(mint_fee) = msg.mint_fee {
+        deps.api.addr_validate(mint_fee.collector.as_str())?;
+        MINT_FEE.save(deps.storage, &mint_fee)?;
+    }
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    SUPPORTED_INTERFACES.save(
+        deps.storage,
+        &format!("crates.io:{CONTRACT_NAME}"),
+        &CONTRACT_VERSION.to_string(),
+    )?;
+
+    let create_msg = tokenfactory::create_denom(env.contract.address.as_str(), &subdenom);
+
+    // the denom saved above is predicted from the subdenom. the reply records the one actually created
+    let mut response = Response::new()
+        .add_submessage(SubMsg::reply_on_success(create_msg, CREATE_DENOM_REPLY_ID))
+        .set_data(to_json_binary(&InstantiateResponse {
+            denom: denom.clone(),
+            symbol: msg.symbol.clone(),
+            admin: admin.clone(),
+        })?)
+        .add_attribute("action", "factory_instantiate");
 
-    let create_msg: CosmosMsg = MsgCreateDenom {
-        sender: env.contract.address.clone().into_string(),
-        subdenom,
+    // some chains charge a fee to create a denom, taken from this contract's balance. it has to be
+    // attached when instantiating, and anything attached beyond it is refunded
+    let creation_fee = TokenfactoryQuerier::new(&deps.querier)
+        .params()?
+        .params
+        .map(|params| params.denom_creation_fee)
+        .unwrap_or_default();
+    let mut excess = info.funds;
+    for fee in creation_fee {
+        let amount = Uint128::from_str(&fee.amount)?;
+        match excess.iter_mut().find(|coin| coin.denom == fee.denom) {
+            Some(paid) if paid.amount >= amount => paid.amount -= amount,
+            _ => {
+                return Err(ContractError::CreationFee(format!(
+                    "{}{}",
+                    amount, fee.denom
+                )))
+            }
+        }
+        response = response.add_attribute("creation_fee", format!("{}{}", amount, fee.denom));
+    }
+
+    // the listing is added once the denom exists, with its fee also taken from the attached funds
+    if let Some(assetlist) = msg.assetlist {
+        for fee in &assetlist.fee {
+            match excess.iter_mut().find(|coin| coin.denom == fee.denom) {
+                Some(paid) if paid.amount >= fee.amount => paid.amount -= fee.amount,
+                _ => return Err(ContractError::ListingFee(fee.to_string())),
+            }
+        }
+        let (listing_msg, contract_addr) =
+            assetlist_listing(deps.as_ref(), denom.clone(), assetlist)?;
+        response = response
+            .add_message(listing_msg)
+            .add_attribute("assetlist", contract_addr);
+    }
+    excess.retain(|coin| !coin.amount.is_zero());
+    if !excess.is_empty() {
+        response = response
+            .add_attribute(
+                "refund",
+                excess
+                    .iter()
+                    .map(Coin::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+            .add_message(BankMsg::Send {
+                to_address: info.sender.into_string(),
+                amount: excess,
+            });
+    }
+
+    if let Some(project_info) = msg.project_info {
+        validate_project_info(&project_info)?;
+        PROJECT_INFO.save(deps.storage, &project_info)?;
+    }
+
+    // during a pre-launch phase this contract acts as the denom's before-send hook,
+    // restricting transfers to allowlisted recipients until trading starts
+    let send_hook =
+        msg.pre_launch.is_some() || msg.max_wallet.is_some() || msg.transfer_burn.is_some();
+    if let Some(pre_launch) = msg.pre_launch {
+        for address in &pre_launch.allowlist {
+            let address = validate_address(deps.api, env.contract.address.as_str(), address)?;
+            ALLOWLIST.save(deps.storage, &address, &Empty {})?;
+        }
+        TRADING_START.save(deps.storage, &pre_launch.trading_start)?;
+        response = response.add_attribute("trading_start", pre_launch.trading_start.to_string());
+    }
+
+    // the hook also caps balances during the launch period
+    if let Some(max_wallet) = msg.max_wallet {
+        if max_wallet.max_wallet.amount.is_zero() {
+            return Err(ContractError::InvalidMaxWallet);
+        }
+        for address in &max_wallet.exempt {
+            let address = validate_address(deps.api, env.contract.address.as_str(), address)?;
+            MAX_WALLET_EXEMPT.save(deps.storage, &address, &Empty {})?;
+        }
+        MAX_WALLET.save(deps.storage, &max_wallet.max_wallet)?;
+        response = response.add_attribute("max_wallet", max_wallet.max_wallet.amount);
+    }
+
+    // and burns a share of every transfer. the pools, routers and ibc escrows the token moves
+    // through are exempted along with it, or the hook rejects every transfer through them
+    if let Some(TransferBurnMsg {
+        rate: transfer_burn,
+        exempt,
+    }) = msg.transfer_burn
+    {
+        if transfer_burn.is_zero() || transfer_burn >= Decimal::one() {
+            return Err(ContractError::InvalidTransferBurn);
+        }
+        for address in &exempt {
+            let address = validate_address(deps.api, env.contract.address.as_str(), address)?;
+            TRANSFER_FEE_EXEMPT.save(deps.storage, &address, &Empty {})?;
+        }
+        TRANSFER_BURN.save(deps.storage, &transfer_burn)?;
+        TRANSFER_BURNED.save(deps.storage, &Uint128::zero())?;
+        response = response.add_attribute("transfer_burn", transfer_burn.to_string());
+    }
+
+    if send_hook {
+        let hook_msg: CosmosMsg = MsgSetBeforeSendHook {
+            sender: env.contract.address.clone().into_string(),
+            denom: denom.clone(),
+            cosmwasm_address: env.contract.address.clone().into_string(),
+        }
+        .into();
+        response = response.add_message(hook_msg);
+    }
+
+    // the multisig is instantiated without a wasm admin, as cw3-fixed-multisig can't be changed.
+    // the reply makes it the owner
+    if let Some(multisig) = msg.multisig {
+        let total_weight = multisig
+            .voters
+            .iter()
+            .try_fold(0u64, |total, voter| total.checked_add(voter.weight));
+        let valid = match total_weight {
+            Some(total_weight) => {
+                multisig.voters.iter().all(|voter| voter.weight > 0)
+                    && multisig.threshold.validate(total_weight).is_ok()
+            }
+            None => false,
+        };
+        if !valid {
+            return Err(ContractError::InvalidMultisig);
+        }
+        for voter in &multisig.voters {
+            deps.api.addr_validate(&voter.addr)?;
+        }
+        let instantiate_msg = WasmMsg::Instantiate {
+            admin: None,
+            code_id: multisig.code_id,
+            msg: to_json_binary(&MultisigInstantiateMsg {
+                voters: multisig.voters,
+                threshold: multisig.threshold,
+                max_voting_period: multisig.max_voting_period,
+            })?,
+            funds: vec![],
+            label: format!("{} multisig", msg.symbol),
+        };
+        response = response
+            .add_submessage(SubMsg::reply_on_success(instantiate_msg, MULTISIG_REPLY_ID))
+            .add_attribute("multisig_code_id", multisig.code_id.to_string());
     }
-    .into();
 
     // if initial supply is zero, we are done
     if initial_supply.is_zero() {
-        return Ok(Response::new()
-            .add_message(create_msg)
-            .add_attribute("action", "factory_instantiate")
-            .add_attribute("action", "factory_create_denom"));
+        return Ok(response);
     };
 
     // otherwise mint the initial supply to the contract address
-    let mint_msg: CosmosMsg = MsgMint {
-        sender: env.contract.address.clone().into_string(),
-        amount: Some(Coin {
-            denom,
-            amount: initial_supply.to_string(),
-        }),
-        mint_to_address: env.contract.address.into_string(),
-    }
-    .into();
+    let mint_msg = tokenfactory::mint(
+        env.contract.address.as_str(),
+        &denom,
+        initial_supply,
+        env.contract.address.as_str(),
+    );
 
-    Ok(Response::new()
-        .add_message(create_msg)
+    let response = response
         .add_message(mint_msg)
-        .add_attribute("action", "factory_instantiate")
-        .add_attribute("action", "factory_create_denom")
-        .add_attribute("initial_mint", initial_supply.to_string()))
+        .add_attribute("initial_mint", initial_supply.to_string())
+        .add_event(mint_event(
+            &denom,
+            env.contract.address.as_str(),
+            initial_supply,
+            initial_supply.u128(),
+        ));
+    count_mint(deps.storage, env.block.time, initial_supply)?;
+    record_history(deps.storage, &env, &response.events)?;
+    Ok(response)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    // only admin can execute
-    if info.sender != ADMIN.load(deps.storage)? {
-        return Err(ContractError::Unauthorized);
+    // funds attached to anything else would be stranded in the contract
+    if !is_payable(&msg) {
+        nonpayable(&info)?;
     }
-    let contract = env.contract.address;
 
-    match msg {
-        ExecuteMsg::Mint(receivers) => execute_mint(deps, &contract, &receivers),
-        ExecuteMsg::Burn(amount) => execute_burn(deps, contract, &amount),
-        ExecuteMsg::Send(receivers) => execute_transfer(deps, &receivers),
-        ExecuteMsg::UpdateSupply(new_max) => execute_update_supply(deps, &new_max),
-        ExecuteMsg::Revoke => execute_revoke(deps, contract),
-    }
+    let response = execute_action(deps.branch(), env.clone(), info, msg)?;
+    record_history(deps.storage, &env, &response.events)?;
+    Ok(response)
 }
 
-fn execute_mint(
-    deps: DepsMut,
-    contract: &Addr,
-    receivers: &[Receiver],
+fn execute_action(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    let denom = DENOM.load(deps.storage)?;
-    let max_supply = MAX_SUPPLY.load(deps.storage)?;
-    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+    if let ExecuteMsg::Multi(msgs) = msg {
+        return execute_multi(deps, env, info, msgs);
+    }
 
-    let mut msgs: Vec<CosmosMsg> = vec![];
-    let mut attributes: Vec<cosmwasm_std::Attribute> = vec![];
-    let mut total_to_mint: u128 = 0;
+    // queued actions can be executed by anyone once their timelock has passed
+    if let ExecuteMsg::ExecuteQueued { id } = msg {
+        return execute_queued(deps, env, id);
+    }
 
-    for (i, receiver) in receivers.iter().enumerate() {
-        let amount = receiver.amount;
-        let address = &receiver.address;
-        if amount.is_zero() || deps.api.addr_validate(address.as_str()).is_err() {
-            return Err(ContractError::MintInvalid(i));
-        }
-        total_to_mint += amount.u128();
-        let msg: CosmosMsg = MsgMint {
-            sender: contract.clone().to_string(),
-            amount: Some(Coin {
-                denom: denom.clone(),
-                amount: amount.to_string(),
-            }),
-            mint_to_address: address.clone(),
+    // the emission is minted by whoever gets to it first, to its fixed recipient
+    if let ExecuteMsg::MintEmission = msg {
+        return execute_mint_emission(deps, env);
+    }
+
+    // so can scheduled ones, and the crank executes whatever is due
+    if let ExecuteMsg::Crank { limit } = msg {
+        return execute_crank(deps, env, info, limit);
+    }
+
+    // governance is open to stakers, and passed proposals can be executed by anyone
+    let msg = match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            msgs,
+        } => return execute_propose(deps, env, info, title, description, msgs),
+        ExecuteMsg::Vote { id, approve } => return execute_vote(deps, env, info, id, approve),
+        ExecuteMsg::ExecuteProposal { id } => return execute_proposal(deps, env, id),
+        ExecuteMsg::SettleDeposit { id } => return execute_settle_deposit(deps, env, id),
+        msg => msg,
+    };
+
+    // mints over ibc-hooks are public mints, once the remote sender is authenticated and allowed
+    let msg = match msg {
+        ExecuteMsg::IbcMint {
+            channel,
+            original_sender,
+            receivers,
+        } => {
+            check_ibc_hook(
+                deps.as_ref(),
+                &env.contract.address,
+                &info.sender,
+                &channel,
+                &original_sender,
+            )?;
+            ExecuteMsg::Mint(receivers)
         }
-        .into();
-        msgs.push(msg);
-        attributes.push(cosmwasm_std::Attribute {
-            key: String::from("recipient"),
-            value: address.to_string(),
-        });
-        attributes.push(cosmwasm_std::Attribute {
-            key: String::from("amount"),
-            value: amount.to_string(),
-        });
+        msg => msg,
+    };
+
+    // amounts given in whole tokens are converted to base units before anything else looks at them
+    let msg = to_base_units(deps.as_ref(), msg)?;
+
+    // holders can always redeem against the reserve
+    if let ExecuteMsg::Redeem = msg {
+        return execute_redeem(deps, env, info);
     }
 
-    // check if attempting to mint more than max supply, unless max supply is 0
-    if max_supply < total_to_mint + total_minted && max_supply != 0 {
-        return Err(ContractError::SupplyCap);
+    if let ExecuteMsg::BurnFunds = msg {
+        return execute_burn_funds(deps, env, info);
     }
 
-    // update the total minted amount
-    TOTAL_MINTED.save(deps.storage, &(total_to_mint + total_minted))?;
+    if let ExecuteMsg::TransferFunds { recipient } = msg {
+        return execute_transfer_funds(deps, env, info, recipient);
+    }
 
-    Ok(Response::new()
-        .add_messages(msgs)
-        .add_attribute("action", "factory_mint")
-        .add_attributes(attributes)
-        .add_attribute("total_minted", total_minted.to_string()))
-}
+    // the tokens are burned from the owner's own wallet, so the owner has to be the sender
+    if let ExecuteMsg::BurnFromWallet(amount) = msg {
+        if !has_role(deps.as_ref(), &info.sender, Role::Owner)? {
+            return Err(ContractError::Unauthorized);
+        }
+        if amount.is_zero() {
+            return Err(ContractError::InvalidBurnFunds);
+        }
+        return Ok(
+            execute_burn_from(deps, env.contract.address, info.sender.as_str(), &amount)?
+                .add_attribute("burner", info.sender),
+        );
+    }
 
-fn execute_burn(
-    deps: DepsMut,
-    contract: Addr,
-    burn_amount: &Uint128,
-) -> Result<Response, ContractError> {
-    let msg: CosmosMsg = MsgBurn {
-        sender: contract.clone().to_string(),
-        amount: Some(Coin {
-            denom: DENOM.load(deps.storage)?,
-            amount: burn_amount.to_string(),
-        }),
-        burn_from_address: contract.into_string(),
+    if let ExecuteMsg::ClaimVested = msg {
+        return execute_claim_vested(deps, env, info);
     }
-    .into();
 
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "factory_burn")
-        .add_attribute("amount", burn_amount.to_string()))
-}
+    if let ExecuteMsg::Claim = msg {
+        return execute_claim(deps, env, info);
+    }
 
-fn execute_transfer(deps: DepsMut, messages: &[Receiver]) -> Result<Response, ContractError> {
-    let denom = DENOM.load(deps.storage)?;
+    // staking is open to every holder
+    match msg {
+        ExecuteMsg::FundStaking => return execute_fund_staking(deps, env, info),
+        ExecuteMsg::Stake { duration } => return execute_stake(deps, env, info, duration),
+        ExecuteMsg::Unstake { id } => return execute_unstake(deps, env, info, id),
+        ExecuteMsg::ClaimRewards => return execute_claim_rewards(deps, env, info),
+        _ => {}
+    }
 
-    let mut msgs: Vec<CosmosMsg> = vec![];
-    let mut attributes: Vec<cosmwasm_std::Attribute> = vec![];
+    // spenders pull from the treasury with their own allowance, not a role
+    if let ExecuteMsg::TransferFrom { recipient, amount } = msg {
+        return execute_transfer_from(deps, env, info, recipient, amount);
+    }
 
-    let mut total_to_transfer = Uint128::zero();
+    if let ExecuteMsg::ClaimDistribution { id, balance, proof } = msg {
+        return execute_claim_distribution(deps, env, info, id, balance, proof);
+    }
+
+    // the rewards are the attached funds, so the distribution can't be queued or batched
+    if let ExecuteMsg::CreateDistribution {
+        merkle_root,
+        snapshot_height,
+        total,
+        expiry,
+    } = msg
+    {
+        if !has_role(deps.as_ref(), &info.sender, Role::Owner)? {
+            return Err(ContractError::Unauthorized);
+        }
+        return execute_create_distribution(
+            deps,
+            env,
+            info,
+            merkle_root,
+            snapshot_height,
+            total,
+            expiry,
+        );
+    }
 
-    for (i, msg) in messages.iter().enumerate() {
-        let amount = msg.amount;
-        let address = &msg.address;
+    // the dead man switch can be triggered by anyone once the owners have gone inactive
+    if let ExecuteMsg::ClaimOwnership = msg {
+        return execute_claim_ownership(deps, env);
+    }
 
-        if amount.is_zero() || deps.api.addr_validate(address.as_str()).is_err() {
-            return Err(ContractError::TransferInvalid(i));
+    // the sender must hold the role required by the action, except for bucket minters drawing
+    // from their own bucket, minters of a further denom and anyone minting through the public mint
+    let authorized = has_role(deps.as_ref(), &info.sender, required_role(&msg))?;
+    let mut public_mint = None;
+    if !authorized {
+        match &msg {
+            ExecuteMsg::MintFromBucket { bucket, .. }
+                if is_bucket_minter(deps.as_ref(), bucket, &info.sender)? => {}
+            ExecuteMsg::MintDenom { symbol, .. }
+                if DENOM_MINTERS.has(deps.storage, (symbol, &info.sender)) => {}
+            ExecuteMsg::Mint(receivers) if PUBLIC_MINT.exists(deps.storage) => {
+                public_mint = Some(public_mint_payment(
+                    deps.branch(),
+                    env.block.time,
+                    &info.sender,
+                    receivers,
+                    None,
+                )?);
+            }
+            ExecuteMsg::MintWithProof {
+                receivers,
+                cap,
+                proof,
+            } if PUBLIC_MINT.exists(deps.storage) => {
+                public_mint = Some(public_mint_payment(
+                    deps.branch(),
+                    env.block.time,
+                    &info.sender,
+                    receivers,
+                    Some((*cap, proof)),
+                )?);
+            }
+            _ => return Err(ContractError::Unauthorized),
         }
-        msgs.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: address.clone(),
-            amount: vec![cosmwasm_std::Coin {
-                denom: denom.clone(),
-                amount,
-            }],
-        }));
-        attributes.push(cosmwasm_std::Attribute {
-            key: String::from("recipient"),
-            value: address.to_string(),
-        });
-        attributes.push(cosmwasm_std::Attribute {
-            key: String::from("amount"),
-            value: amount.to_string(),
-        });
-        total_to_transfer += amount;
     }
-    Ok(Response::new()
-        .add_messages(msgs)
-        .add_attribute("action", "factory_transfer")
-        .add_attributes(attributes)
-        .add_attribute("total_transferred", total_to_transfer.to_string()))
-}
 
-fn execute_revoke(deps: DepsMut, contract: Addr) -> Result<Response, ContractError> {
-    let sender = contract.into_string();
-    let denom = DENOM.load(deps.storage)?;
+    // when a timelock is configured, privileged actions have to be queued first. bucket and denom
+    // minters were only added through actions that were already subject to the timelock
+    if authorized && TIMELOCK.exists(deps.storage) && is_timelocked(&msg) {
+        return Err(ContractError::TimelockRequired);
+    }
 
-    // use the contract address to deduce a burn address for whatever chain this contract is on
-    let (hrp, _) = decode(&sender).unwrap();
-    let null_address = encode::<bech32::Bech32>(hrp, &[0u8; 20]).unwrap();
-    let msg: CosmosMsg = MsgChangeAdmin {
-        sender,
-        denom,
-        new_admin: null_address,
+    // mints by anyone other than an owner pay the protocol fee, if one is configured
+    let mint_fee =
+        if is_mint(&msg) && !ROLES.has(deps.storage, (Role::Owner.as_str(), &info.sender)) {
+            MINT_FEE.may_load(deps.storage)?
+        } else {
+            None
+        };
+
+    // the exact payment and fee must be attached
+    let mut required = vec![];
+    if let Some((payment, _)) = &public_mint {
+        required.push(payment.clone());
+    }
+    if let Some(mint_fee) = &mint_fee {
+        required.push(mint_fee.fee.clone());
+    }
+    if !required.is_empty() {
+        check_mint_funds(&info.funds, required)?;
+    } else if is_mint(&msg) {
+        nonpayable(&info)?;
     }
-    .into();
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "factory_revoke"))
-}
 
-fn execute_update_supply(deps: DepsMut, new_max: &Uint128) -> Result<Response, ContractError> {
-    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+    // a share of the payment to the treasury can be held back and vested to the team instead
+    let mut vested = Uint128::zero();
+    if let Some((payment, Some(_))) = &public_mint {
+        if let Some(vesting) = PROCEEDS_VESTING.may_load(deps.storage)? {
+            vested = payment.amount.mul_floor(vesting.share);
+            VESTING_DEPOSITED.update(deps.storage, |deposited| -> StdResult<_> {
+                Ok(deposited.checked_add(vested)?)
+            })?;
+        }
+    }
 
-    // make sure that the max supply is not reduced below the total minted amount, unless the new max is 0 (uncapped)
-    if new_max.u128() < total_minted && !new_max.is_zero() {
-        return Err(ContractError::CurrentSupply);
+    // any owner action keeps the dead man switch from being triggered
+    if DEAD_MAN_SWITCH.exists(deps.storage)
+        && ROLES.has(deps.storage, (Role::Owner.as_str(), &info.sender))
+    {
+        LAST_ACTIVITY.save(deps.storage, &env.block.time)?;
     }
 
-    MAX_SUPPLY.save(deps.storage, &new_max.u128())?;
-    Ok(Response::new().add_attribute("action", "factory_update_supply"))
-}
+    let mut response = dispatch(deps, env, msg)?;
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::TokenInfo => to_json_binary(&query_info(deps)?),
-        QueryMsg::Mintable => to_json_binary(&query_mintable(deps, env)?),
+    if let Some((payment, treasury)) = public_mint {
+        response = response
+            .add_attribute("buyer", info.sender)
+            .add_attribute("payment", payment.to_string());
+        // payments stay in the contract unless a treasury is configured
+        if let Some(treasury) = treasury {
+            let forwarded = payment.amount - vested;
+            if !forwarded.is_zero() {
+                response = response.add_message(BankMsg::Send {
+                    to_address: treasury.to_string(),
+                    amount: vec![Coin {
+                        denom: payment.denom,
+                        amount: forwarded,
+                    }],
+                });
+            }
+            response = response.add_attribute("treasury", treasury);
+            if !vested.is_zero() {
+                response = response.add_attribute("vested", vested);
+            }
+        }
+    }
+
+    if let Some(mint_fee) = mint_fee {
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: mint_fee.collector.to_string(),
+                amount: vec![mint_fee.fee.clone()],
+            })
+            .add_attribute("mint_fee", mint_fee.fee.to_string())
+            .add_attribute("fee_collector", mint_fee.collector);
     }
+
+    Ok(response)
 }
 
-fn query_info(deps: Deps) -> StdResult<crate::msg::TokenInfoResponse> {
-    let symbol = SYMBOL.load(deps.storage)?;
-    let denom = DENOM.load(deps.storage)?;
-    let current_supply = query_bank_supply(deps, denom.clone());
-    let max_supply = MAX_SUPPLY.load(deps.storage)?;
-    let minted = TOTAL_MINTED.load(deps.storage)?;
-    // this is redundant. remove it?
-    let burned = minted - current_supply;
+// converts receiver amounts given in display units into base units
+fn to_base_units(deps: Deps, msg: ExecuteMsg) -> Result<ExecuteMsg, ContractError> {
+    let unit = || -> Result<Uint128, ContractError> {
+        Ok(Uint128::from(10u128).checked_pow(EXPONENT.load(deps.storage)?)?)
+    };
+    let convert = |receivers: Vec<Receiver>| -> Result<Vec<Receiver>, ContractError> {
+        if !receivers
+            .iter()
+            .any(|r| r.display_units.unwrap_or_default())
+        {
+            return Ok(receivers);
+        }
+        let unit = unit()?;
+        receivers
+            .into_iter()
+            .map(|receiver| match receiver.display_units {
+                Some(true) => Ok(Receiver {
+                    amount: receiver.amount.checked_mul(unit)?,
+                    display_units: None,
+                    ..receiver
+                }),
+                _ => Ok(receiver),
+            })
+            .collect()
+    };
 
-    Ok(crate::msg::TokenInfoResponse {
-        symbol,
-        denom,
-        current_supply: current_supply.into(),
-        max_supply: max_supply.into(),
-        minted: minted.into(),
-        burned: burned.into(),
+    Ok(match msg {
+        ExecuteMsg::Mint(receivers) => ExecuteMsg::Mint(convert(receivers)?),
+        ExecuteMsg::MintWithProof {
+            receivers,
+            cap,
+            proof,
+        } => ExecuteMsg::MintWithProof {
+            receivers: convert(receivers)?,
+            cap,
+            proof,
+        },
+        ExecuteMsg::MintFromBucket { bucket, receivers } => ExecuteMsg::MintFromBucket {
+            bucket,
+            receivers: convert(receivers)?,
+        },
+        ExecuteMsg::Send(receivers) => ExecuteMsg::Send(convert(receivers)?),
+        ExecuteMsg::UpdateSupplyDisplay(new_max) => {
+            ExecuteMsg::UpdateSupply(new_max.checked_mul(unit()?)?)
+        }
+        ExecuteMsg::Queue(msg) => ExecuteMsg::Queue(Box::new(to_base_units(deps, *msg)?)),
+        ExecuteMsg::Schedule { msg, execute_at } => ExecuteMsg::Schedule {
+            msg: Box::new(to_base_units(deps, *msg)?),
+            execute_at,
+        },
+        msg => msg,
     })
 }
 
-fn query_mintable(deps: Deps, env: Env) -> StdResult<crate::msg::MintableResponse> {
-    let denom = DENOM.load(deps.storage)?;
-    let max_supply = MAX_SUPPLY.load(deps.storage)?;
-    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+// executes an authorized message. shared by direct admin calls and matured queued actions
+fn dispatch(deps: DepsMut, env: Env, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    let contract = env.contract.address.clone();
 
-    let mut cap_reached = false;
-    let mut revoked = false;
+    if is_paused(&msg) && PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused);
+    }
 
-    // check if the max supply has been reached
-    if max_supply != 0 && total_minted == max_supply {
-        cap_reached = true;
+    if moves_treasury(&msg) && SEND_DISABLED.exists(deps.storage) {
+        return Err(ContractError::SendDisabled);
     }
 
-    // check if the admin has been revoked
-    let admin = TokenfactoryQuerier::new(&deps.querier)
-        .denom_authority_metadata(denom)?
-        .authority_metadata
-        .unwrap()
-        .admin;
-    if admin != env.contract.address.into_string() {
-        revoked = true;
+    if matches!(
+        msg,
+        ExecuteMsg::Mint(_)
+            | ExecuteMsg::MintWithProof { .. }
+            | ExecuteMsg::MintFromBucket { .. }
+            | ExecuteMsg::MintTo { .. }
+            | ExecuteMsg::MintVested { .. }
+    ) {
+        check_mint_window(deps.as_ref(), env.block.time)?;
     }
 
-    Ok(crate::msg::MintableResponse {
-        cap_reached,
-        revoked,
-    })
+    match msg {
+        ExecuteMsg::Mint(receivers) | ExecuteMsg::MintWithProof { receivers, .. } => {
+            // once allocation buckets are configured, every mint has to draw from one
+            if !BUCKETS.is_empty(deps.storage) {
+                return Err(ContractError::BucketRequired);
+            }
+            execute_mint(
+                deps,
+                &contract,
+                env.block.time,
+                &receivers,
+                None,
+                Charge::Receivers,
+            )
+        }
+        ExecuteMsg::MintFromBucket { bucket, receivers } => execute_mint(
+            deps,
+            &contract,
+            env.block.time,
+            &receivers,
+            Some(&bucket),
+            Charge::Receivers,
+        ),
+        ExecuteMsg::MintTo {
+            contract: target,
+            amount,
+            msg,
+        } => execute_mint_to(deps, &contract, env.block.time, target, amount, msg),
+        ExecuteMsg::MintVested {
+            receiver,
+            amount,
+            schedule,
+        } => execute_mint_vested(deps, &contract, env.block.time, receiver, amount, schedule),
+        ExecuteMsg::Clawback {
+            from,
+            to,
+            amount,
+            reason,
+        } => execute_clawback(deps, contract, from, to, amount, reason),
+        ExecuteMsg::SweepDistribution { id, to } => execute_sweep_distribution(deps, env, id, to),
+        ExecuteMsg::ConfigureStaking {
+            reward_denom,
+            reward_rate,
+            tiers,
+        } => execute_configure_staking(deps, env, reward_denom, reward_rate, tiers),
+        ExecuteMsg::ConfigureGovernance(governance) => {
+            execute_configure_governance(deps, governance)
+        }
+        // converted to Mint before anything is dispatched
+        ExecuteMsg::IbcMint { .. } => Err(ContractError::InvalidQueuedAction),
+        ExecuteMsg::UpdateIbcHooks { channel, senders } => {
+            execute_update_ibc_hooks(deps, channel, senders)
+        }
+        ExecuteMsg::SetBucket { label, amount } => execute_set_bucket(deps, label, amount),
+        ExecuteMsg::SetBucketMinters { label, minters } => {
+            execute_set_bucket_minters(deps, &contract, label, minters)
+        }
+        ExecuteMsg::Burn(Some(amount)) => {
+            check_spendable(deps.as_ref(), &contract, amount)?;
+            execute_burn(deps, contract, &amount)
+        }
+        ExecuteMsg::Burn(None) => {
+            // the balance is read when the burn executes, so nothing can arrive in between
+            let amount = free_balance(deps.as_ref(), &contract, &DENOM.load(deps.storage)?)?;
+            if amount.is_zero() {
+                return Err(ContractError::EmptyBalance);
+            }
+            execute_burn(deps, contract, &amount)
+        }
+        ExecuteMsg::Send(receivers) => {
+            let total = receivers
+                .iter()
+                .try_fold(Uint128::zero(), |total, receiver| {
+                    total.checked_add(receiver.amount)
+                })?;
+            check_spendable(deps.as_ref(), &contract, total)?;
+            execute_transfer(deps, &contract, &receivers)
+        }
+        ExecuteMsg::SendAndCall {
+            contract: target,
+            amount,
+            msg,
+        } => {
+            check_spendable(deps.as_ref(), &contract, amount)?;
+            execute_send_and_call(deps, &contract, target, amount, msg)
+        }
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_increase_allowance(deps, &contract, spender, amount, expires),
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_decrease_allowance(deps, &contract, spender, amount, expires),
+        ExecuteMsg::Sweep { to } => execute_sweep(deps, contract, to),
+        ExecuteMsg::Rescue { denom, to } => execute_rescue(deps, contract, denom, to),
+        ExecuteMsg::UpdateSupply(new_max) => execute_update_supply(deps, &new_max),
+        // converted to UpdateSupply before anything is dispatched
+        ExecuteMsg::UpdateSupplyDisplay(_) => Err(ContractError::InvalidQueuedAction),
+        ExecuteMsg::LockSupply => execute_lock_supply(deps),
+        ExecuteMsg::SetMintTax(mint_tax) => execute_set_mint_tax(deps, &contract, mint_tax),
+        ExecuteMsg::SetMintLimit(limit) => execute_set_mint_limit(deps, limit),
+        ExecuteMsg::SetEmission(emission) => execute_set_emission(deps, &contract, emission),
+        ExecuteMsg::Revoke => execute_revoke(deps, contract, None),
+        ExecuteMsg::RevokeTo { new_admin } => execute_revoke(deps, contract, Some(new_admin)),
+        ExecuteMsg::HandoverToGov { gov_module } => {
+            execute_handover_to_gov(deps, contract, gov_module)
+        }
+        ExecuteMsg::ScheduleRevoke { delay, new_admin } => {
+            execute_schedule_revoke(deps, env, delay, new_admin)
+        }
+        ExecuteMsg::ConfirmRevoke => execute_confirm_revoke(deps, env),
+        ExecuteMsg::CancelRevoke => execute_cancel_revoke(deps),
+        ExecuteMsg::UpdateAllowlist { add, remove } => {
+            execute_update_allowlist(deps, &contract, add, remove)
+        }
+        ExecuteMsg::Reconcile => execute_reconcile(deps),
+        ExecuteMsg::UpdateSendPolicy {
+            enabled,
+            add,
+            remove,
+        } => execute_update_send_policy(deps, &contract, enabled, add, remove),
+        ExecuteMsg::DisableSend => execute_disable_send(deps),
+        ExecuteMsg::DisableTransferBurn => execute_disable_transfer_burn(deps),
+        ExecuteMsg::SetTransferTax(transfer_tax) => {
+            execute_set_transfer_tax(deps, env, transfer_tax)
+        }
+        ExecuteMsg::RenounceTransferTax => execute_renounce_transfer_tax(deps),
+        ExecuteMsg::UpdateTransferFeeExempt { add, remove } => {
+            execute_update_transfer_fee_exempt(deps, &contract, add, remove)
+        }
+        ExecuteMsg::UpdateMaxWalletExempt { add, remove } => {
+            execute_update_max_wallet_exempt(deps, &contract, add, remove)
+        }
+        ExecuteMsg::AddRole { role, address } => execute_add_role(deps, &contract, role, address),
+        ExecuteMsg::RemoveRole { role, address } => execute_remove_role(deps, role, address),
+        ExecuteMsg::Pause => execute_set_paused(deps, true),
+        ExecuteMsg::Unpause => execute_set_paused(deps, false),
+        ExecuteMsg::SetProjectInfo(project_info) => execute_set_project_info(deps, project_info),
+        ExecuteMsg::UpdateSymbol(symbol) => execute_update_symbol(deps, contract, symbol),
+        ExecuteMsg::SetDenomMetadata(metadata) => {
+            execute_set_denom_metadata(deps, contract, metadata)
+        }
+        ExecuteMsg::Redeem
+        | ExecuteMsg::BurnFunds
+        | ExecuteMsg::TransferFunds { .. }
+        | ExecuteMsg::BurnFromWallet(_)
+        | ExecuteMsg::MintEmission
+        | ExecuteMsg::ClaimVested
+        | ExecuteMsg::Claim
+        | ExecuteMsg::ClaimDistribution { .. }
+        | ExecuteMsg::CreateDistribution { .. }
+        | ExecuteMsg::FundStaking
+        | ExecuteMsg::Stake { .. }
+        | ExecuteMsg::Unstake { .. }
+        | ExecuteMsg::ClaimRewards
+        | ExecuteMsg::TransferFrom { .. }
+        | ExecuteMsg::Propose { .. }
+        | ExecuteMsg::Vote { .. }
+        | ExecuteMsg::ExecuteProposal { .. }
+        | ExecuteMsg::SettleDeposit { .. } => Err(ContractError::InvalidQueuedAction),
+        ExecuteMsg::Heartbeat => Ok(Response::new().add_attribute("action", "factory_heartbeat")),
+        ExecuteMsg::ClaimOwnership => execute_claim_ownership(deps, env),
+        ExecuteMsg::Queue(msg) => execute_queue(deps, env, *msg),
+        ExecuteMsg::CancelQueued { id } => execute_cancel_queued(deps, id),
+        ExecuteMsg::Schedule { msg, execute_at } => execute_schedule(deps, env, *msg, execute_at),
+        ExecuteMsg::SetCrankReward(reward) => execute_set_crank_reward(deps, reward),
+        ExecuteMsg::Crank { .. } => Err(ContractError::InvalidQueuedAction),
+        ExecuteMsg::ExecuteQueued { .. } => Err(ContractError::InvalidQueuedAction),
+        ExecuteMsg::Multi(_) => Err(ContractError::InvalidQueuedAction),
+        ExecuteMsg::CreateDenom {
+            symbol,
+            subdenom,
+            max_supply,
+        } => execute_create_denom(deps, env, symbol, subdenom, max_supply),
+        ExecuteMsg::MintDenom { symbol, receivers } => {
+            execute_mint_denom(deps, env, symbol, receivers)
+        }
+        ExecuteMsg::BurnDenom { symbol, amount } => execute_burn_denom(deps, env, symbol, amount),
+        ExecuteMsg::UpdateDenomMinters {
+            symbol,
+            add,
+            remove,
+        } => execute_update_denom_minters(deps, &contract, symbol, add, remove),
+        ExecuteMsg::CreateGauge {
+            pool_id,
+            amount,
+            epochs,
+            lock_duration,
+            start_time,
+            mint,
+        } => execute_create_gauge(
+            deps,
+            env,
+            pool_id,
+            amount,
+            epochs,
+            lock_duration,
+            start_time,
+            mint,
+        ),
+        ExecuteMsg::CreateClPool {
+            amount,
+            quote,
+            tick_spacing,
+            spread_factor,
+            locked_until,
+        } => execute_create_cl_pool(
+            deps,
+            contract,
+            amount,
+            quote,
+            tick_spacing,
+            spread_factor,
+            locked_until,
+        ),
+        ExecuteMsg::WithdrawPosition => execute_withdraw_position(deps, env, contract),
+        ExecuteMsg::Buyback {
+            offer,
+            pool_id,
+            min_out,
+        } => execute_buyback(deps, contract, offer, pool_id, min_out),
+        ExecuteMsg::Finalize {
+            recipient,
+            assetlist,
+        } => execute_finalize(deps, env, recipient, assetlist),
+        ExecuteMsg::RenounceContract { sweep_to } => {
+            execute_renounce_contract(deps, contract, sweep_to)
+        }
+        ExecuteMsg::ProposeTakerFee {
+            quote_denom,
+            taker_fee,
+            deposit,
+            title,
+            description,
+        } => execute_propose_taker_fee(
+            deps,
+            contract,
+            quote_denom,
+            taker_fee,
+            deposit,
+            title,
+            description,
+        ),
+    }
+}
+
+// actions that can change the supply, move the treasury or exempt an address from the token's
+// restrictions, and so are subject to the timelock
+fn is_timelocked(msg: &ExecuteMsg) -> bool {
+    match msg {
+        ExecuteMsg::Mint(_)
+        | ExecuteMsg::MintWithProof { .. }
+        | ExecuteMsg::MintFromBucket { .. }
+        | ExecuteMsg::MintTo { .. }
+        | ExecuteMsg::MintVested { .. }
+        | ExecuteMsg::CreateDenom { .. }
+        | ExecuteMsg::MintDenom { .. }
+        | ExecuteMsg::UpdateDenomMinters { .. }
+        | ExecuteMsg::Clawback { .. }
+        | ExecuteMsg::ConfigureGovernance(_)
+        | ExecuteMsg::SetBucket { .. }
+        | ExecuteMsg::SetBucketMinters { .. }
+        | ExecuteMsg::Send(_)
+        | ExecuteMsg::SendAndCall { .. }
+        | ExecuteMsg::IncreaseAllowance { .. }
+        | ExecuteMsg::Sweep { .. }
+        | ExecuteMsg::Rescue { .. }
+        | ExecuteMsg::CreateClPool { .. }
+        | ExecuteMsg::CreateGauge { .. }
+        | ExecuteMsg::WithdrawPosition
+        | ExecuteMsg::Buyback { .. }
+        | ExecuteMsg::UpdateSupply(_)
+        | ExecuteMsg::SetMintTax(_)
+        | ExecuteMsg::SetTransferTax(_)
+        | ExecuteMsg::SetEmission(_)
+        | ExecuteMsg::RevokeTo { .. }
+        | ExecuteMsg::HandoverToGov { .. }
+        | ExecuteMsg::AddRole { .. }
+        | ExecuteMsg::RemoveRole { .. }
+        | ExecuteMsg::Finalize { .. }
+        | ExecuteMsg::RenounceContract { .. }
+        | ExecuteMsg::UpdateSendPolicy { .. }
+        | ExecuteMsg::UpdateAllowlist { .. }
+        | ExecuteMsg::UpdateTransferFeeExempt { .. }
+        | ExecuteMsg::UpdateMaxWalletExempt { .. }
+        | ExecuteMsg::UpdateIbcHooks { .. }
+        | ExecuteMsg::SetCrankReward(_)
+        | ExecuteMsg::ProposeTakerFee { .. } => true,
+        ExecuteMsg::ScheduleRevoke { new_admin, .. } => new_admin.is_some(),
+        _ => false,
+    }
+}
+
+// the role an address must hold to execute an action. owners can execute everything
+fn required_role(msg: &ExecuteMsg) -> Role {
+    match msg {
+        ExecuteMsg::Mint(_)
+        | ExecuteMsg::MintWithProof { .. }
+        | ExecuteMsg::MintFromBucket { .. }
+        | ExecuteMsg::MintTo { .. }
+        | ExecuteMsg::MintVested { .. } => Role::Minter,
+        ExecuteMsg::Pause | ExecuteMsg::Unpause => Role::Pauser,
+        ExecuteMsg::SetDenomMetadata(_) | ExecuteMsg::SetProjectInfo(_) => Role::MetadataManager,
+        _ => Role::Owner,
+    }
+}
+
+// the price of a public mint, and where the payment should be forwarded to. counts the
+// purchase against the buyer's limit, and against the cap from their merkle leaf if there is an
+// allowlist
+fn public_mint_payment(
+    deps: DepsMut,
+    now: Timestamp,
+    buyer: &Addr,
+    receivers: &[Receiver],
+    allowance: Option<(Uint128, &[String])>,
+) -> Result<(Coin, Option<Addr>), ContractError> {
+    let public_mint = PUBLIC_MINT.load(deps.storage)?;
+    let amount = receivers
+        .iter()
+        .try_fold(Uint128::zero(), |total, receiver| {
+            total.checked_add(receiver.amount)
+        })?;
+
+    let leaf_cap = match (&public_mint.merkle_root, allowance) {
+        (Some(root), Some((cap, proof))) => {
+            verify_proof(root, buyer, cap, proof)?;
+            Some(cap)
+        }
+        (Some(_), None) => return Err(ContractError::MerkleProofRequired),
+        (None, _) => None,
+    };
+
+    // during the presale only allowlisted addresses can buy, at the presale price
+    let (phase, cap, limit, purchases) = match PRESALE.may_load(deps.storage)? {
+        Some(presale) if now < presale.start => {
+            return Err(ContractError::PublicMintClosed(presale.start))
+        }
+        Some(presale) if now < presale.end => {
+            if !PRESALE_ALLOWLIST.has(deps.storage, buyer) {
+                return Err(ContractError::PresaleRestricted);
+            }
+            (
+                Phase::Presale(presale.price),
+                presale.cap,
+                presale.limit_per_address,
+                &PRESALE_PURCHASED,
+            )
+        }
+        _ => (
+            Phase::Public,
+            public_mint.cap,
+            public_mint.limit_per_address,
+            &PUBLIC_MINTED,
+        ),
+    };
+
+    let purchased = purchases
+        .may_load(deps.storage, buyer)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    if let Some(limit) = limit {
+        if purchased > limit {
+            return Err(ContractError::PublicMintLimit(limit));
+        }
+    }
+
+    // the opening of the public phase has its own, tighter limits
+    if let (Phase::Public, Some(anti_snipe)) = (&phase, &public_mint.anti_snipe) {
+        if now < anti_snipe.until {
+            if let Some(max) = anti_snipe.max_per_tx.filter(|max| amount > *max) {
+                return Err(ContractError::AntiSnipeLimit(max, anti_snipe.until));
+            }
+            if let Some(max) = anti_snipe.max_per_address.filter(|max| purchased > *max) {
+                return Err(ContractError::AntiSnipeLimit(max, anti_snipe.until));
+            }
+        }
+    }
+    purchases.save(deps.storage, buyer, &purchased)?;
+
+    // the leaf cap covers everything the address buys, in either phase
+    if let Some(cap) = leaf_cap {
+        let total = PRESALE_PURCHASED
+            .may_load(deps.storage, buyer)?
+            .unwrap_or_default()
+            .checked_add(
+                PUBLIC_MINTED
+                    .may_load(deps.storage, buyer)?
+                    .unwrap_or_default(),
+            )?;
+        if total > cap {
+            return Err(ContractError::PublicMintLimit(cap));
+        }
+    }
+
+    let sold = PHASE_MINTED
+        .may_load(deps.storage, phase.as_str())?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    if let Some(cap) = cap {
+        if sold > cap {
+            return Err(ContractError::PhaseCap(phase.as_str().to_string()));
+        }
+    }
+    PHASE_MINTED.save(deps.storage, phase.as_str(), &sold)?;
+
+    let cost = match phase {
+        Phase::Presale(price) => amount
+            .checked_mul_ceil(price)
+            .map_err(|err| StdError::generic_err(err.to_string()))?,
+        Phase::Public => mint_cost(&public_mint, curve_supply(deps.storage)?, amount.u128())?,
+    };
+
+    if public_mint.treasury.is_none() {
+        let reserve = RESERVE.may_load(deps.storage)?.unwrap_or_default();
+        RESERVE.save(deps.storage, &reserve.checked_add(cost)?)?;
+    }
+
+    Ok((
+        Coin {
+            denom: public_mint.payment_denom,
+            amount: cost,
+        },
+        public_mint.treasury,
+    ))
+}
+
+// checks that sha256(buyer + cap) is a leaf of the merkle tree. pairs are sorted before hashing
+fn verify_proof(
+    root: &str,
+    address: &Addr,
+    amount: Uint128,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    let leaf: [u8; 32] = Sha256::digest(format!("{}{}", address, amount)).into();
+    let hash = proof.iter().try_fold(leaf, |hash, sibling| {
+        let mut sibling_hash = [0u8; 32];
+        hex::decode_to_slice(sibling, &mut sibling_hash)
+            .map_err(|_| ContractError::InvalidMerkleProof)?;
+        let mut pair = [hash, sibling_hash];
+        pair.sort_unstable();
+        Ok::<[u8; 32], ContractError>(Sha256::digest(pair.concat()).into())
+    })?;
+
+    if !hex::encode(hash).eq_ignore_ascii_case(root) {
+        return Err(ContractError::InvalidMerkleProof);
+    }
+
+    Ok(())
+}
+
+enum Phase {
+    // carries the presale price
+    Presale(Decimal),
+    Public,
+}
+
+impl Phase {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Presale(_) => "presale",
+            Phase::Public => "public",
+        }
+    }
+}
+
+// the supply the bonding curve is priced at, which redemptions move back down
+fn curve_supply(storage: &dyn Storage) -> StdResult<u128> {
+    let redeemed = REDEEMED.may_load(storage)?.unwrap_or_default();
+    Ok(TOTAL_MINTED.load(storage)?.saturating_sub(redeemed))
+}
+
+// the public mint price of one base unit once `supply` has been minted
+fn spot_price(public_mint: &PublicMint, supply: u128) -> StdResult<Decimal256> {
+    let base = Decimal256::from(public_mint.price);
+
+    match &public_mint.curve {
+        None => Ok(base),
+        Some(Curve::Linear { slope }) => Ok(base.checked_add(
+            Decimal256::from(*slope).checked_mul(Decimal256::from_ratio(supply, 1u128))?,
+        )?),
+        Some(Curve::Exponential { growth, step }) => {
+            step_price(base, *growth, supply / step.u128())
+        }
+    }
+}
+
+// the price on the nth step of an exponential curve
+fn step_price(base: Decimal256, growth: Decimal, n: u128) -> StdResult<Decimal256> {
+    let n = u32::try_from(n).map_err(|_| StdError::generic_err("Bonding curve overflow"))?;
+    Ok(base.checked_mul((Decimal256::one() + Decimal256::from(growth)).checked_pow(n)?)?)
+}
+
+// the total price of minting `amount` base units once `supply` has been minted
+fn mint_cost(public_mint: &PublicMint, supply: u128, amount: u128) -> StdResult<Uint128> {
+    let base = Decimal256::from(public_mint.price);
+    let units = |n: u128| Decimal256::from_ratio(n, 1u128);
+
+    let cost = match &public_mint.curve {
+        None => base.checked_mul(units(amount))?,
+        // the base price of every unit, plus the slope times the sum of the supplies each unit
+        // is minted at, an arithmetic series from `supply` to `supply + amount - 1`
+        Some(Curve::Linear { slope }) => {
+            let minted_at = if amount == 0 {
+                Uint256::zero()
+            } else {
+                Uint256::from(amount).checked_mul(
+                    Uint256::from(supply) * Uint256::from(2u8) + Uint256::from(amount - 1),
+                )? / Uint256::from(2u8)
+            };
+            base.checked_mul(units(amount))?.checked_add(
+                Decimal256::from(*slope).checked_mul(
+                    Decimal256::checked_from_ratio(minted_at, 1u8)
+                        .map_err(|err| StdError::generic_err(err.to_string()))?,
+                )?,
+            )?
+        }
+        // the rest of the current step, every full step in between and the start of the last step
+        Some(Curve::Exponential { growth, step }) => {
+            let step = step.u128();
+            let end = supply
+                .checked_add(amount)
+                .ok_or(StdError::generic_err("Bonding curve overflow"))?;
+            let (first, last) = (supply / step, end / step);
+
+            if first == last {
+                step_price(base, *growth, first)?.checked_mul(units(amount))?
+            } else {
+                let mut cost = step_price(base, *growth, first)?
+                    .checked_mul(units((first + 1) * step - supply))?
+                    .checked_add(
+                        step_price(base, *growth, last)?.checked_mul(units(end - last * step))?,
+                    )?;
+
+                // geometric series of the full steps
+                if last > first + 1 {
+                    let full_steps = if growth.is_zero() {
+                        base.checked_mul(units(last - first - 1))?
+                    } else {
+                        (step_price(base, *growth, last)? - step_price(base, *growth, first + 1)?)
+                            .checked_div(Decimal256::from(*growth))
+                            .map_err(|err| StdError::generic_err(err.to_string()))?
+                    };
+                    cost = cost.checked_add(full_steps.checked_mul(units(step))?)?;
+                }
+                cost
+            }
+        }
+    };
+
+    Uint128::try_from(cost.to_uint_ceil()).map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+// checks that exactly the required coins were attached
+fn check_mint_funds(funds: &[Coin], required: Vec<Coin>) -> Result<(), ContractError> {
+    let mut expected: Vec<Coin> = vec![];
+    for coin in required.into_iter().filter(|coin| !coin.amount.is_zero()) {
+        match expected.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount += coin.amount,
+            None => expected.push(coin),
+        }
+    }
+    expected.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    let mut funds = funds.to_vec();
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    if funds != expected {
+        let expected: Vec<String> = expected.iter().map(Coin::to_string).collect();
+        return Err(ContractError::MintFunds(expected.join(",")));
+    }
+
+    Ok(())
+}
+
+fn validate_symbol(rules: &SymbolRules, symbol: &str) -> Result<(), ContractError> {
+    let length = symbol.chars().count() as u32;
+    if length < rules.min_length || length > rules.max_length {
+        return Err(ContractError::SymbolLength(
+            rules.min_length,
+            rules.max_length,
+        ));
+    }
+    if let Some(c) = symbol
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !rules.extra_chars.contains(*c))
+    {
+        return Err(ContractError::SymbolCharacter(c));
+    }
+
+    Ok(())
+}
+
+// tokenfactory limits subdenoms to 44 characters, and denoms to alphanumerics and /:._-
+fn validate_subdenom(subdenom: &str) -> Result<(), ContractError> {
+    if subdenom.is_empty()
+        || subdenom.len() > MAX_SUBDENOM_LENGTH
+        || !subdenom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+    {
+        return Err(ContractError::InvalidSubdenom(subdenom.to_string()));
+    }
+
+    Ok(())
+}
+
+// minting is only possible within the mint window, if one is configured
+fn check_mint_window(deps: Deps, now: Timestamp) -> Result<(), ContractError> {
+    if let Some(start) = MINT_START.may_load(deps.storage)? {
+        if now < start {
+            return Err(ContractError::MintNotStarted(start));
+        }
+    }
+    if let Some(end) = MINT_END.may_load(deps.storage)? {
+        if now >= end {
+            return Err(ContractError::MintEnded(end));
+        }
+    }
+
+    Ok(())
+}
+
+fn has_role(deps: Deps, address: &Addr, role: Role) -> StdResult<bool> {
+    Ok(ROLES.has(deps.storage, (Role::Owner.as_str(), address))
+        || ROLES.has(deps.storage, (role.as_str(), address)))
+}
+
+// actions that move the token held by the contract somewhere other than a burn
+fn moves_treasury(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Send(_)
+            | ExecuteMsg::SendAndCall { .. }
+            | ExecuteMsg::Sweep { .. }
+            | ExecuteMsg::IncreaseAllowance { .. }
+            | ExecuteMsg::CreateClPool { .. }
+            | ExecuteMsg::CreateGauge { mint: false, .. }
+            | ExecuteMsg::RenounceContract { sweep_to: Some(_) }
+    )
+}
+
+// mints only take funds for a payment or fee. the other payable actions spend from the balance
+fn is_mint(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Mint(_)
+            | ExecuteMsg::MintWithProof { .. }
+            | ExecuteMsg::MintFromBucket { .. }
+            | ExecuteMsg::MintTo { .. }
+            | ExecuteMsg::MintVested { .. }
+    )
+}
+
+// actions that mint or move the treasury, and so are halted while paused
+fn is_paused(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Mint(_)
+            | ExecuteMsg::MintWithProof { .. }
+            | ExecuteMsg::MintFromBucket { .. }
+            | ExecuteMsg::MintTo { .. }
+            | ExecuteMsg::MintVested { .. }
+            | ExecuteMsg::Send(_)
+            | ExecuteMsg::SendAndCall { .. }
+            | ExecuteMsg::Sweep { .. }
+            | ExecuteMsg::CreateClPool { .. }
+            | ExecuteMsg::CreateGauge { .. }
+            | ExecuteMsg::MintDenom { .. }
+            | ExecuteMsg::Buyback { .. }
+            | ExecuteMsg::Finalize { .. }
+            | ExecuteMsg::Redeem
+    )
+}
+
+// actions that take the attached funds, or spend coins the contract holds and can be funded
+// along with the call
+fn is_payable(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::Mint(_)
+            | ExecuteMsg::MintWithProof { .. }
+            | ExecuteMsg::MintFromBucket { .. }
+            | ExecuteMsg::MintTo { .. }
+            | ExecuteMsg::MintVested { .. }
+            | ExecuteMsg::IbcMint { .. }
+            | ExecuteMsg::Redeem
+            | ExecuteMsg::BurnFunds
+            | ExecuteMsg::TransferFunds { .. }
+            | ExecuteMsg::FundStaking
+            | ExecuteMsg::Stake { .. }
+            | ExecuteMsg::CreateDistribution { .. }
+            | ExecuteMsg::CreateClPool { .. }
+            | ExecuteMsg::CreateGauge { .. }
+            | ExecuteMsg::CreateDenom { .. }
+            | ExecuteMsg::Buyback { .. }
+            | ExecuteMsg::ProposeTakerFee { .. }
+            | ExecuteMsg::Propose { .. }
+            | ExecuteMsg::Finalize { .. }
+            | ExecuteMsg::Multi(_)
+    )
+}
+
+fn execute_multi(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<ExecuteMsg>,
+) -> Result<Response, ContractError> {
+    // funds can't be split between the actions, so a batch can't pay for a mint
+    if !info.funds.is_empty() {
+        return Err(ContractError::InvalidMulti);
+    }
+
+    let mut response = Response::new().add_attribute("action", "factory_multi");
+
+    // each action sees the state left by the ones before it, and any failure reverts them all
+    for msg in msgs {
+        if let ExecuteMsg::Multi(_) = msg {
+            return Err(ContractError::InvalidMulti);
+        }
+        let action_response = execute_action(deps.branch(), env.clone(), info.clone(), msg)?;
+        response = response
+            .add_submessages(action_response.messages)
+            .add_attributes(action_response.attributes)
+            .add_events(action_response.events);
+    }
+
+    Ok(response)
+}
+
+fn execute_queue(deps: DepsMut, env: Env, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    let Some(timelock) = TIMELOCK.may_load(deps.storage)? else {
+        return Err(ContractError::NoTimelock);
+    };
+
+    // only actions that are subject to the timelock can be queued
+    if !is_timelocked(&msg) {
+        return Err(ContractError::InvalidQueuedAction);
+    }
+
+    let id = QUEUE_ID.may_load(deps.storage)?.unwrap_or_default();
+    let executable_after = env.block.time.plus_seconds(timelock);
+
+    QUEUED_ACTIONS.save(
+        deps.storage,
+        id,
+        &QueuedAction {
+            msg,
+            executable_after,
+            committed: false,
+        },
+    )?;
+    QUEUE_ID.save(deps.storage, &(id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_queue")
+        .add_attribute("id", id.to_string())
+        .add_attribute("executable_after", executable_after.to_string()))
+}
+
+fn execute_schedule(
+    deps: DepsMut,
+    env: Env,
+    msg: ExecuteMsg,
+    execute_at: Timestamp,
+) -> Result<Response, ContractError> {
+    // scheduling can't be used to get around the timelock
+    let timelock = TIMELOCK.may_load(deps.storage)?.unwrap_or_default();
+    if !matches!(
+        msg,
+        ExecuteMsg::Mint(_)
+            | ExecuteMsg::Send(_)
+            | ExecuteMsg::UpdateSupply(_)
+            | ExecuteMsg::Revoke
+    ) || execute_at < env.block.time.plus_seconds(timelock)
+    {
+        return Err(ContractError::InvalidScheduledAction);
+    }
+
+    let id = QUEUE_ID.may_load(deps.storage)?.unwrap_or_default();
+    QUEUED_ACTIONS.save(
+        deps.storage,
+        id,
+        &QueuedAction {
+            msg,
+            executable_after: execute_at,
+            committed: true,
+        },
+    )?;
+    SCHEDULE.save(deps.storage, (execute_at.nanos(), id), &Empty {})?;
+    QUEUE_ID.save(deps.storage, &(id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_schedule")
+        .add_attribute("id", id.to_string())
+        .add_attribute("executable_after", execute_at.to_string()))
+}
+
+fn execute_set_crank_reward(
+    deps: DepsMut,
+    reward: Option<Coin>,
+) -> Result<Response, ContractError> {
+    match &reward {
+        Some(reward) => CRANK_REWARD.save(deps.storage, reward)?,
+        None => CRANK_REWARD.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_set_crank_reward")
+        .add_attribute(
+            "reward",
+            reward.map(|reward| reward.to_string()).unwrap_or_default(),
+        ))
+}
+
+// adds the token to an assetlist contract, with the listing fee attached
+fn assetlist_listing(
+    deps: Deps,
+    denom: String,
+    assetlist: AssetlistRegistration,
+) -> StdResult<(WasmMsg, String)> {
+    let contract_addr = deps.api.addr_validate(&assetlist.contract)?.into_string();
+    let listing = ListingMsg::Add(vec![(denom, assetlist.metadata)]);
+    Ok((
+        WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_json_binary(&AssetlistExecuteMsg::Listing(listing))?,
+            funds: assetlist.fee,
+        },
+        contract_addr,
+    ))
+}
+
+fn execute_queued(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let Some(queued) = QUEUED_ACTIONS.may_load(deps.storage, id)? else {
+        return Err(ContractError::QueuedActionNotFound(id));
+    };
+
+    if env.block.time < queued.executable_after {
+        return Err(ContractError::QueuedActionTimelocked(
+            queued.executable_after,
+        ));
+    }
+
+    remove_queued(deps.storage, id, &queued)?;
+    // a committed revoke changes the token admin directly, so no pending revoke can hold it up
+    let response = match queued.msg {
+        ExecuteMsg::Revoke if queued.committed => execute_revoke(deps, env.contract.address, None)?,
+        msg => dispatch(deps, env, msg)?,
+    };
+    Ok(response
+        .add_attribute("action", "factory_execute_queued")
+        .add_attribute("id", id.to_string()))
+}
+
+fn execute_crank(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // only scheduled actions are cranked. queued ones are left to the owners to execute. the
+    // schedule is ordered by due time, so the scan stops at the first action that isn't due
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let end = Bound::inclusive((env.block.time.nanos(), u64::MAX));
+    let due = SCHEDULE
+        .keys(deps.storage, None, Some(end), Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    if due.is_empty() {
+        return Err(ContractError::NothingToCrank);
+    }
+
+    let mut response = Response::new().add_attribute("action", "factory_crank");
+    CRANKER.save(deps.storage, &info.sender)?;
+
+    // each action is executed in order in its own sub-message, so a failing one only reverts itself
+    // and stays scheduled. the reply pays the reward for the ones that succeed
+    for (_, id) in &due {
+        let queued = QUEUED_ACTIONS.load(deps.storage, *id)?;
+        if can_never_execute(deps.as_ref(), &env, &queued.msg)? {
+            remove_queued(deps.storage, *id, &queued)?;
+            response = response.add_attribute("dropped", id.to_string());
+            continue;
+        }
+
+        let msg = WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::ExecuteQueued { id: *id })?,
+            funds: vec![],
+        };
+        response = response
+            .add_submessage(SubMsg::reply_always(msg, CRANK_REPLY_ID_OFFSET + id))
+            .add_attribute("id", id.to_string());
+    }
+
+    Ok(response)
+}
+
+// whether a scheduled action can no longer succeed, so the crank drops it instead of retrying it
+// forever. anything else that fails, like a mint while the contract is paused, is retried
+fn can_never_execute(deps: Deps, env: &Env, msg: &ExecuteMsg) -> StdResult<bool> {
+    let revoked = || -> StdResult<bool> {
+        let admin = TokenfactoryQuerier::new(&deps.querier)
+            .denom_authority_metadata(DENOM.load(deps.storage)?)?
+            .authority_metadata
+            .map(|metadata| metadata.admin);
+        Ok(admin.as_deref() != Some(env.contract.address.as_str()))
+    };
+
+    Ok(match msg {
+        ExecuteMsg::Mint(_) => {
+            MINT_END
+                .may_load(deps.storage)?
+                .is_some_and(|end| env.block.time >= end)
+                || revoked()?
+        }
+        ExecuteMsg::Send(_) => SEND_DISABLED.exists(deps.storage),
+        ExecuteMsg::UpdateSupply(_) => SUPPLY_LOCKED.may_load(deps.storage)?.unwrap_or_default(),
+        ExecuteMsg::Revoke => revoked()?,
+        _ => false,
+    })
+}
+
+fn remove_queued(storage: &mut dyn Storage, id: u64, queued: &QueuedAction) -> StdResult<()> {
+    QUEUED_ACTIONS.remove(storage, id)?;
+    if queued.committed {
+        SCHEDULE.remove(storage, (queued.executable_after.nanos(), id));
+    }
+    Ok(())
+}
+
+fn execute_cancel_queued(deps: DepsMut, id: u64) -> Result<Response, ContractError> {
+    let Some(queued) = QUEUED_ACTIONS.may_load(deps.storage, id)? else {
+        return Err(ContractError::QueuedActionNotFound(id));
+    };
+    if queued.committed {
+        return Err(ContractError::CommittedAction);
+    }
+
+    QUEUED_ACTIONS.remove(deps.storage, id)?;
+    Ok(Response::new()
+        .add_attribute("action", "factory_cancel_queued")
+        .add_attribute("id", id.to_string()))
+}
+
+// who a mint counts towards, for the per address mint limit
+enum Charge<'a> {
+    // each receiver
+    Receivers,
+    // the address the contract mints to itself for, to forward or hold the tokens on its behalf
+    Beneficiary(&'a Addr),
+    // the contract's own scheduled and closing mints, which the limit doesn't apply to
+    Exempt,
+}
+
+// mints from a bucket, or without one from the supply that isn't reserved for the buckets
+fn execute_mint(
+    deps: DepsMut,
+    contract: &Addr,
+    now: Timestamp,
+    receivers: &[Receiver],
+    bucket: Option<&str>,
+    charge: Charge,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+    let mint_limit = MINT_LIMIT.may_load(deps.storage)?;
+
+    let mut msgs: Vec<SubMsg> = vec![];
+    let mut attributes: Vec<cosmwasm_std::Attribute> = vec![];
+    let mut minted: Vec<(String, Uint128)> = vec![];
+    let mut total_to_mint = Uint128::zero();
+    let mut largest = Uint128::zero();
+
+    for (address, amount) in aggregate_receivers(
+        deps.api,
+        contract.as_str(),
+        receivers,
+        ContractError::MintInvalid,
+    )? {
+        let address = &address;
+        total_to_mint = total_to_mint.checked_add(amount)?;
+        largest = largest.max(amount);
+        minted.push((address.clone(), amount));
+        match charge {
+            Charge::Receivers => count_minted_to(deps.storage, address, amount, mint_limit)?,
+            Charge::Beneficiary(beneficiary) => {
+                count_minted_to(deps.storage, beneficiary.as_str(), amount, mint_limit)?
+            }
+            Charge::Exempt => count_minted_to(deps.storage, address, amount, None)?,
+        }
+        msgs.push(SubMsg::new(tokenfactory::mint(
+            contract.as_str(),
+            &denom,
+            amount,
+            address,
+        )));
+        attributes.push(cosmwasm_std::Attribute {
+            key: String::from("recipient"),
+            value: address.to_string(),
+        });
+        attributes.push(cosmwasm_std::Attribute {
+            key: String::from("amount"),
+            value: amount.to_string(),
+        });
+    }
+
+    // the tax is minted on top of the mint, drawn from the same bucket and within the max supply
+    let mut total_tax = Uint128::zero();
+    if let Some(mint_tax) = MINT_TAX.may_load(deps.storage)? {
+        let tax = mint_tax_on(&mint_tax, total_to_mint);
+        if !tax.is_zero() {
+            total_tax = tax;
+            count_minted_to(deps.storage, mint_tax.treasury.as_str(), tax, None)?;
+            msgs.push(SubMsg::new(tokenfactory::mint(
+                contract.as_str(),
+                &denom,
+                tax,
+                mint_tax.treasury.as_str(),
+            )));
+            attributes.push(cosmwasm_std::Attribute {
+                key: String::from("tax"),
+                value: tax.to_string(),
+            });
+            minted.push((mint_tax.treasury.to_string(), tax));
+        }
+    }
+    let total = total_to_mint.checked_add(total_tax)?;
+
+    if let Some(label) = bucket {
+        let Some(mut allocation) = BUCKETS.may_load(deps.storage, label)? else {
+            return Err(ContractError::BucketNotFound(label.to_string()));
+        };
+        allocation.minted = allocation.minted.checked_add(total)?;
+        if allocation.minted > allocation.allocated {
+            return Err(ContractError::BucketExceeded(label.to_string()));
+        }
+        BUCKETS.save(deps.storage, label, &allocation)?;
+        attributes.push(cosmwasm_std::Attribute {
+            key: String::from("bucket"),
+            value: label.to_string(),
+        });
+    }
+
+    // check if attempting to mint more than max supply, unless max supply is 0. the remaining
+    // allocations of the buckets, less what was just drawn from one, stay reserved
+    let reserved = bucket_reserved(deps.as_ref())?;
+    let new_minted = total.checked_add(Uint128::from(total_minted))?;
+    if max_supply != 0 && Uint128::from(max_supply) < new_minted.checked_add(reserved.into())? {
+        return Err(ContractError::SupplyCap);
+    }
+
+    // update the total minted amount
+    TOTAL_MINTED.save(deps.storage, &new_minted.u128())?;
+    count_mint(deps.storage, now, largest)?;
+
+    // the typed events are only added once every check has passed
+    let mut new_total = total_minted;
+    let events = minted.into_iter().map(|(recipient, amount)| {
+        new_total += amount.u128();
+        mint_event(&denom, &recipient, amount, new_total)
+    });
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_events(events)
+        .add_attribute("action", "factory_mint")
+        .add_attributes(attributes)
+        .add_attribute("total_minted", total_minted.to_string()))
+}
+
+// counts a successful mint operation in the stats. a batch counts once, and the tax minted along
+// with it isn't counted
+fn count_mint(storage: &mut dyn Storage, now: Timestamp, largest: Uint128) -> StdResult<()> {
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    stats.mints += 1;
+    stats.largest_mint = stats.largest_mint.max(largest);
+    stats.last_mint = Some(now);
+    STATS.save(storage, &stats)
+}
+
+fn mint_tax_on(mint_tax: &MintTax, amount: Uint128) -> Uint128 {
+    amount.mul_floor(mint_tax.rate)
+}
+
+// the largest mint that fits in `available` along with its tax
+fn mint_before_tax(mint_tax: Option<&MintTax>, available: Uint128) -> Uint128 {
+    let Some(mint_tax) = mint_tax else {
+        return available;
+    };
+    available.multiply_ratio(
+        Decimal::one().atomics(),
+        (Decimal::one() + mint_tax.rate).atomics(),
+    )
+}
+
+// validates an address. addresses from other chains are called out, since they would otherwise
+// just fail validation
+fn validate_address(api: &dyn Api, contract: &str, address: &str) -> Result<Addr, ContractError> {
+    let prefix = decode(contract)
+        .map_err(|_| ContractError::AddressDerivation(contract.to_string()))?
+        .0;
+    if let Ok((hrp, _)) = decode(address) {
+        if hrp != prefix {
+            return Err(ContractError::ForeignAddress(
+                address.to_string(),
+                prefix.to_string(),
+            ));
+        }
+    }
+
+    Ok(api.addr_validate(address)?)
+}
+
+// validates a batch, and merges receivers that appear more than once into a single amount, in the
+// order they first appear. `invalid` is the error for the index of a zero amount or bad address
+fn aggregate_receivers(
+    api: &dyn Api,
+    contract: &str,
+    receivers: &[Receiver],
+    invalid: fn(usize) -> ContractError,
+) -> Result<Vec<(String, Uint128)>, ContractError> {
+    let mut aggregated: Vec<(String, Uint128)> = vec![];
+    let mut indexes: HashMap<&str, usize> = HashMap::new();
+    for (i, receiver) in receivers.iter().enumerate() {
+        match validate_address(api, contract, &receiver.address) {
+            Err(ContractError::ForeignAddress(address, prefix)) => {
+                return Err(ContractError::AddressPrefix(i, address, prefix))
+            }
+            Err(_) => return Err(invalid(i)),
+            Ok(_) if receiver.amount.is_zero() => return Err(invalid(i)),
+            Ok(_) => {}
+        }
+        match indexes.get(receiver.address.as_str()) {
+            Some(&index) => {
+                let total = &mut aggregated[index].1;
+                *total = total.checked_add(receiver.amount).map_err(|_| invalid(i))?;
+            }
+            None => {
+                indexes.insert(&receiver.address, aggregated.len());
+                aggregated.push((receiver.address.clone(), receiver.amount));
+            }
+        }
+    }
+    Ok(aggregated)
+}
+
+// typed events carry the same keys for every action, so indexers don't need the action's layout
+fn mint_event(denom: &str, recipient: &str, amount: Uint128, new_total: u128) -> Event {
+    Event::new("tfa-mint")
+        .add_attribute("denom", denom)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+        .add_attribute("new_total", new_total.to_string())
+}
+
+fn send_event(denom: &str, recipient: &str, amount: Uint128) -> Event {
+    Event::new("tfa-send")
+        .add_attribute("denom", denom)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+}
+
+// keeps the typed mint, burn and send events of the token and the further denoms in a response in
+// the history, pruning the oldest entries past HISTORY_LIMIT, and notes the token's burns in the
+// stats. sends of other denoms, like staking rewards, are left out
+fn record_history(storage: &mut dyn Storage, env: &Env, events: &[Event]) -> StdResult<()> {
+    let denom = DENOM.load(storage)?;
+    let created = format!("factory/{}/", env.contract.address);
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    let mut recorded = false;
+    for event in events {
+        let Some(action) = event.ty.strip_prefix("tfa-") else {
+            continue;
+        };
+        if !["mint", "burn", "send"].contains(&action) {
+            continue;
+        }
+        let attribute = |key: &str| {
+            event
+                .attributes
+                .iter()
+                .find(|attribute| attribute.key == key)
+                .map(|attribute| attribute.value.clone())
+        };
+        let Some(event_denom) =
+            attribute("denom").filter(|event_denom| event_denom.starts_with(&created))
+        else {
+            continue;
+        };
+
+        let amount: Uint128 = attribute("amount").unwrap_or_default().parse()?;
+        let entry_denom = if event_denom == denom {
+            if action == "burn" {
+                recorded = true;
+                stats.last_burn = Some(env.block.time);
+            }
+            None
+        } else {
+            Some(event_denom)
+        };
+
+        let id = HISTORY_ID.may_load(storage)?.unwrap_or_default() + 1;
+        HISTORY_ID.save(storage, &id)?;
+        HISTORY.save(
+            storage,
+            id,
+            &HistoryEntry {
+                action: action.replace('-', "_"),
+                address: attribute("recipient"),
+                amount,
+                height: env.block.height,
+                time: env.block.time,
+                denom: entry_denom,
+            },
+        )?;
+        if id > HISTORY_LIMIT {
+            HISTORY.remove(storage, id - HISTORY_LIMIT)?;
+        }
+    }
+    if recorded {
+        STATS.save(storage, &stats)?;
+    }
+    Ok(())
+}
+
+// adds a mint to the address's total, which the mint limit applies to
+fn count_minted_to(
+    storage: &mut dyn Storage,
+    address: &str,
+    amount: Uint128,
+    mint_limit: Option<Uint128>,
+) -> Result<(), ContractError> {
+    let address = Addr::unchecked(address);
+    let total = MINTED_TO
+        .may_load(storage, &address)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    if mint_limit.is_some_and(|limit| total > limit) {
+        return Err(ContractError::MintLimit(address.to_string()));
+    }
+    MINTED_TO.save(storage, &address, &total)?;
+    Ok(())
+}
+
+fn execute_mint_to(
+    mut deps: DepsMut,
+    contract: &Addr,
+    now: Timestamp,
+    target: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let target = validate_address(deps.api, contract.as_str(), &target)?;
+
+    // the tokens are minted to this contract first, so they can be attached to the call
+    let mint_response = execute_mint(
+        deps.branch(),
+        contract,
+        now,
+        &[Receiver {
+            address: contract.to_string(),
+            amount,
+            display_units: None,
+        }],
+        None,
+        Charge::Beneficiary(&target),
+    )?;
+
+    Ok(mint_response
+        .add_message(WasmMsg::Execute {
+            contract_addr: target.to_string(),
+            msg,
+            funds: vec![Coin {
+                denom: DENOM.load(deps.storage)?,
+                amount,
+            }],
+        })
+        .add_attribute("contract", target))
+}
+
+fn execute_clawback(
+    deps: DepsMut,
+    contract: Addr,
+    from: String,
+    to: String,
+    amount: Uint128,
+    reason: String,
+) -> Result<Response, ContractError> {
+    if !CLAWBACK.exists(deps.storage) {
+        return Err(ContractError::ClawbackDisabled);
+    }
+    let from = validate_address(deps.api, contract.as_str(), &from)?;
+    let to = validate_address(deps.api, contract.as_str(), &to)?;
+    // the contract's own holdings move through Send, under the send policy and pause
+    if amount.is_zero() || from == to || from == contract {
+        return Err(ContractError::InvalidClawback);
+    }
+
+    let denom = DENOM.load(deps.storage)?;
+    let msg: CosmosMsg = MsgForceTransfer {
+        sender: contract.into_string(),
+        amount: Some(ProtoCoin {
+            denom: denom.clone(),
+            amount: amount.to_string(),
+        }),
+        transfer_from_address: from.to_string(),
+        transfer_to_address: to.to_string(),
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_event(send_event(&denom, to.as_str(), amount))
+        .add_attribute("action", "factory_clawback")
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount)
+        .add_attribute("reason", reason))
+}
+
+fn execute_burn(
+    deps: DepsMut,
+    contract: Addr,
+    burn_amount: &Uint128,
+) -> Result<Response, ContractError> {
+    let from = contract.to_string();
+    execute_burn_from(deps, contract, &from, burn_amount)
+}
+
+fn execute_burn_from(
+    deps: DepsMut,
+    contract: Addr,
+    from: &str,
+    burn_amount: &Uint128,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let msg = tokenfactory::burn(contract.as_str(), &denom, *burn_amount, from);
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_event(
+            Event::new("tfa-burn")
+                .add_attribute("denom", denom)
+                .add_attribute("amount", burn_amount.to_string()),
+        )
+        .add_attribute("action", "factory_burn")
+        .add_attribute("amount", burn_amount.to_string()))
+}
+
+fn execute_transfer(
+    deps: DepsMut,
+    contract: &Addr,
+    messages: &[Receiver],
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<cosmwasm_std::Attribute> = vec![];
+    let mut events: Vec<Event> = vec![];
+
+    let mut total_to_transfer = Uint128::zero();
+
+    for (address, amount) in aggregate_receivers(
+        deps.api,
+        contract.as_str(),
+        messages,
+        ContractError::TransferInvalid,
+    )? {
+        let address = &address;
+        check_send_destination(deps.storage, address)?;
+        msgs.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: address.clone(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }));
+        events.push(send_event(&denom, address, amount));
+        attributes.push(cosmwasm_std::Attribute {
+            key: String::from("recipient"),
+            value: address.to_string(),
+        });
+        attributes.push(cosmwasm_std::Attribute {
+            key: String::from("amount"),
+            value: amount.to_string(),
+        });
+        total_to_transfer += amount;
+    }
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_events(events)
+        .add_attribute("action", "factory_transfer")
+        .add_attributes(attributes)
+        .add_attribute("total_transferred", total_to_transfer.to_string()))
+}
+
+fn execute_increase_allowance(
+    deps: DepsMut,
+    contract: &Addr,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let spender = validate_address(deps.api, contract.as_str(), &spender)?;
+    let mut allowance = ALLOWANCES
+        .may_load(deps.storage, &spender)?
+        .unwrap_or_default();
+    allowance.amount = allowance.amount.checked_add(amount)?;
+    if expires.is_some() {
+        allowance.expires = expires;
+    }
+    ALLOWANCES.save(deps.storage, &spender, &allowance)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_increase_allowance")
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount)
+        .add_attribute("allowance", allowance.amount))
+}
+
+fn execute_decrease_allowance(
+    deps: DepsMut,
+    contract: &Addr,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let spender = validate_address(deps.api, contract.as_str(), &spender)?;
+    let Some(mut allowance) = ALLOWANCES.may_load(deps.storage, &spender)? else {
+        return Err(ContractError::NoAllowance);
+    };
+    allowance.amount = allowance.amount.saturating_sub(amount);
+    if expires.is_some() {
+        allowance.expires = expires;
+    }
+    if allowance.amount.is_zero() {
+        ALLOWANCES.remove(deps.storage, &spender)?;
+    } else {
+        ALLOWANCES.save(deps.storage, &spender, &allowance)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_decrease_allowance")
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount)
+        .add_attribute("allowance", allowance.amount))
+}
+
+fn execute_transfer_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused);
+    }
+    if SEND_DISABLED.exists(deps.storage) {
+        return Err(ContractError::SendDisabled);
+    }
+    let recipient = validate_address(deps.api, env.contract.address.as_str(), &recipient)?;
+    if amount.is_zero() {
+        return Err(ContractError::TransferInvalid(0));
+    }
+    check_send_destination(deps.storage, recipient.as_str())?;
+
+    let Some(mut allowance) = ALLOWANCES.may_load(deps.storage, &info.sender)? else {
+        return Err(ContractError::NoAllowance);
+    };
+    if let Some(expires) = allowance.expires {
+        if env.block.time >= expires {
+            return Err(ContractError::AllowanceExpired(expires));
+        }
+    }
+    allowance.amount = allowance.amount.checked_sub(amount)?;
+    if allowance.amount.is_zero() {
+        ALLOWANCES.remove(deps.storage, &info.sender)?;
+    } else {
+        ALLOWANCES.save(deps.storage, &info.sender, &allowance)?;
+    }
+
+    // allowances can't dip into the reserve, or anything else the contract has to keep
+    check_spendable(deps.as_ref(), &env.contract.address, amount)?;
+
+    let denom = DENOM.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        })
+        .add_event(send_event(&denom, recipient.as_str(), amount))
+        .add_attribute("action", "factory_transfer_from")
+        .add_attribute("spender", info.sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+        .add_attribute("allowance", allowance.amount))
+}
+
+fn execute_send_and_call(
+    deps: DepsMut,
+    contract: &Addr,
+    target: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let target = validate_address(deps.api, contract.as_str(), &target)?;
+    if amount.is_zero() {
+        return Err(ContractError::TransferInvalid(0));
+    }
+    check_send_destination(deps.storage, target.as_str())?;
+
+    let denom = DENOM.load(deps.storage)?;
+    Ok(Response::new()
+        .add_event(send_event(&denom, target.as_str(), amount))
+        .add_message(WasmMsg::Execute {
+            contract_addr: target.to_string(),
+            msg,
+            funds: vec![Coin { denom, amount }],
+        })
+        .add_attribute("action", "factory_send_and_call")
+        .add_attribute("contract", target)
+        .add_attribute("amount", amount))
+}
+
+// ibc-hooks executes from an address derived from the channel and remote sender, so a sender
+// claiming them is authenticated by deriving it again
+fn check_ibc_hook(
+    deps: Deps,
+    contract: &Addr,
+    sender: &Addr,
+    channel: &str,
+    original_sender: &str,
+) -> Result<(), ContractError> {
+    let source = format!("{}/{}", channel, original_sender);
+    if helpers::ibc_hook_sender(contract.as_str(), channel, original_sender)? != sender.as_str() {
+        return Err(ContractError::IbcHookSender(source));
+    }
+
+    match IBC_HOOKS.may_load(deps.storage, channel)? {
+        Some(senders) if senders.is_empty() || senders.iter().any(|s| s == original_sender) => {
+            Ok(())
+        }
+        _ => Err(ContractError::IbcHookNotAllowed(source)),
+    }
+}
+
+fn execute_update_ibc_hooks(
+    deps: DepsMut,
+    channel: String,
+    senders: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let response = Response::new()
+        .add_attribute("action", "factory_update_ibc_hooks")
+        .add_attribute("channel", channel.clone());
+
+    // remote senders are addresses on another chain, so they can't be validated here
+    match senders {
+        Some(senders) => {
+            IBC_HOOKS.save(deps.storage, &channel, &senders)?;
+            Ok(response.add_attribute("senders", senders.join(",")))
+        }
+        None => {
+            IBC_HOOKS.remove(deps.storage, &channel)?;
+            Ok(response)
+        }
+    }
+}
+
+// the balance is read when the sweep executes, so transfers arriving before then are included
+fn execute_sweep(deps: DepsMut, contract: Addr, to: String) -> Result<Response, ContractError> {
+    let to = validate_address(deps.api, contract.as_str(), &to)?;
+    check_send_destination(deps.storage, to.as_str())?;
+    let denom = DENOM.load(deps.storage)?;
+    let amount = free_balance(deps.as_ref(), &contract, &denom)?;
+
+    if amount.is_zero() {
+        return Err(ContractError::EmptyBalance);
+    }
+
+    Ok(Response::new()
+        .add_event(send_event(&denom, to.as_str(), amount))
+        .add_attribute("action", "factory_sweep")
+        .add_attribute("recipient", to.clone())
+        .add_attribute("amount", amount)
+        .add_message(BankMsg::Send {
+            to_address: to.into_string(),
+            amount: vec![Coin { denom, amount }],
+        }))
+}
+
+fn execute_rescue(
+    deps: DepsMut,
+    contract: Addr,
+    denom: String,
+    to: String,
+) -> Result<Response, ContractError> {
+    if denom == DENOM.load(deps.storage)? {
+        return Err(ContractError::RescueToken);
+    }
+    let to = validate_address(deps.api, contract.as_str(), &to)?;
+
+    let amount = free_balance(deps.as_ref(), &contract, &denom)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRescue(denom));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_rescue")
+        .add_attribute("recipient", to.clone())
+        .add_attribute("amount", Coin::new(amount.u128(), &denom).to_string())
+        .add_message(BankMsg::Send {
+            to_address: to.into_string(),
+            amount: vec![Coin { denom, amount }],
+        }))
+}
+
+// the contract's balance of a denom, less what it must keep
+fn free_balance(deps: Deps, contract: &Addr, denom: &str) -> StdResult<Uint128> {
+    let balance = deps.querier.query_balance(contract, denom)?;
+
+    // rewards that holders can still claim stay in the contract
+    let mut held = DISTRIBUTION_HELD
+        .may_load(deps.storage, denom)?
+        .unwrap_or_default();
+
+    // stakes and the rewards funded for them stay in the contract
+    if let Some(staking) = STAKING.may_load(deps.storage)? {
+        if denom == staking.reward_denom {
+            held += staking.rewards_left + staking.unclaimed;
+        }
+        if denom == DENOM.load(deps.storage)? {
+            held += staking.total_staked;
+        }
+    }
+
+    // unclaimed grants and proposal deposits of the token stay in the contract
+    if denom == DENOM.load(deps.storage)? {
+        held += VESTING_LOCKED.may_load(deps.storage)?.unwrap_or_default();
+        held += PROPOSAL_DEPOSITS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        return Ok(balance.amount.saturating_sub(held));
+    }
+
+    // public mint payments backing redemptions or awaiting vesting stay in the contract
+    if PUBLIC_MINT
+        .may_load(deps.storage)?
+        .is_some_and(|public_mint| public_mint.payment_denom == denom)
+    {
+        held += RESERVE.may_load(deps.storage)?.unwrap_or_default();
+        if PROCEEDS_VESTING.exists(deps.storage) {
+            held += VESTING_DEPOSITED.load(deps.storage)? - VESTING_CLAIMED.load(deps.storage)?;
+        }
+    }
+
+    Ok(balance.amount.saturating_sub(held))
+}
+
+fn coin_amount(coins: &[Coin], denom: &str) -> Uint128 {
+    coins
+        .iter()
+        .find(|coin| coin.denom == denom)
+        .map_or(Uint128::zero(), |coin| coin.amount)
+}
+
+// the treasury can't spend tokens that vest to grant receivers
+fn check_spendable(deps: Deps, contract: &Addr, amount: Uint128) -> Result<(), ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    if amount > free_balance(deps, contract, &denom)? {
+        return Err(ContractError::InsufficientBalance(
+            Coin { denom, amount }.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn execute_revoke(
+    deps: DepsMut,
+    contract: Addr,
+    new_admin: Option<String>,
+) -> Result<Response, ContractError> {
+    let sender = contract.into_string();
+    let denom = DENOM.load(deps.storage)?;
+
+    let new_admin = match new_admin {
+        Some(new_admin) => validate_address(deps.api, &sender, &new_admin)?.into_string(),
+        None => null_address(deps.as_ref(), &sender)?,
+    };
+
+    // handing the admin back to this contract would not revoke anything
+    if new_admin == sender {
+        return Err(ContractError::InvalidRevokeTarget);
+    }
+
+    // a scheduled revoke has nothing left to do
+    PENDING_REVOKE.remove(deps.storage);
+
+    let msg = tokenfactory::change_admin(&sender, &denom, &new_admin);
+    Ok(Response::new()
+        .add_message(msg)
+        .add_event(
+            Event::new("tfa-revoke")
+                .add_attribute("denom", denom)
+                .add_attribute("new_admin", new_admin.clone()),
+        )
+        .add_attribute("action", "factory_revoke")
+        .add_attribute("new_admin", new_admin))
+}
+
+// the configured null address, or the zero address for whatever chain this contract is on
+fn null_address(deps: Deps, contract: &str) -> Result<String, ContractError> {
+    match NULL_ADDRESS.may_load(deps.storage)? {
+        Some(null_address) => Ok(null_address.into_string()),
+        None => derive_address(contract, &[0u8; 20]),
+    }
+}
+
+// encodes `data` with the bech32 prefix of the contract address
+fn derive_address(contract: &str, data: &[u8]) -> Result<String, ContractError> {
+    decode(contract)
+        .ok()
+        .and_then(|(hrp, _)| encode::<bech32::Bech32>(hrp, data).ok())
+        .ok_or_else(|| ContractError::AddressDerivation(contract.to_string()))
+}
+
+fn execute_renounce_contract(
+    mut deps: DepsMut,
+    contract: Addr,
+    sweep_to: Option<String>,
+) -> Result<Response, ContractError> {
+    // commitments made with Schedule outlive the owners, so they have to be executed first
+    for item in QUEUED_ACTIONS.range(deps.storage, None, None, Order::Ascending) {
+        if item?.1.committed {
+            return Err(ContractError::ScheduledActionsPending);
+        }
+    }
+    // only an owner can sweep what holders leave unclaimed, so it would be stuck for good
+    if !DISTRIBUTIONS.is_empty(deps.storage) {
+        return Err(ContractError::DistributionsOpen);
+    }
+
+    let denom = DENOM.load(deps.storage)?;
+    let mut response = Response::new().add_attribute("action", "factory_renounce_contract");
+
+    // nothing could revoke the token admin once the owners are gone
+    let admin = TokenfactoryQuerier::new(&deps.querier)
+        .denom_authority_metadata(denom.clone())?
+        .authority_metadata
+        .map(|metadata| metadata.admin);
+    if admin.as_deref() == Some(contract.as_str()) {
+        let revoke_response = execute_revoke(deps.branch(), contract.clone(), None)?;
+        response = response
+            .add_submessages(revoke_response.messages)
+            .add_events(revoke_response.events)
+            .add_attributes(revoke_response.attributes);
+    }
+
+    // the further denoms are always administered by the contract, and go with the token
+    let null_address = null_address(deps.as_ref(), contract.as_str())?;
+    for item in DENOMS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, managed) = item?;
+        response = response
+            .add_message(tokenfactory::change_admin(
+                contract.as_str(),
+                &managed.denom,
+                &null_address,
+            ))
+            .add_event(
+                Event::new("tfa-revoke")
+                    .add_attribute("denom", managed.denom)
+                    .add_attribute("new_admin", &null_address),
+            );
+    }
+
+    if let Some(sweep_to) = sweep_to {
+        let sweep_to = validate_address(deps.api, contract.as_str(), &sweep_to)?;
+        check_send_destination(deps.storage, sweep_to.as_str())?;
+        let amount = free_balance(deps.as_ref(), &contract, &denom)?;
+        if !amount.is_zero() {
+            response = response
+                .add_event(send_event(&denom, sweep_to.as_str(), amount))
+                .add_message(BankMsg::Send {
+                    to_address: sweep_to.to_string(),
+                    amount: vec![Coin { denom, amount }],
+                });
+        }
+        response = response
+            .add_attribute("sweep_to", sweep_to)
+            .add_attribute("amount", amount);
+    }
+
+    // everything that could still act for the owners goes with them
+    ROLES.clear(deps.storage)?;
+    DENOM_MINTERS.clear(deps.storage)?;
+    ALLOWANCES.clear(deps.storage)?;
+    QUEUED_ACTIONS.clear(deps.storage)?;
+    PENDING_REVOKE.remove(deps.storage);
+    DEAD_MAN_SWITCH.remove(deps.storage);
+    GOVERNANCE.remove(deps.storage);
+
+    Ok(response)
+}
+
+fn execute_handover_to_gov(
+    deps: DepsMut,
+    contract: Addr,
+    gov_module: Option<String>,
+) -> Result<Response, ContractError> {
+    // module accounts are the first 20 bytes of the sha256 hash of the module name
+    let gov_module = match gov_module {
+        Some(gov_module) => gov_module,
+        None => derive_address(contract.as_str(), &Sha256::digest(GOV_MODULE_NAME)[..20])?,
+    };
+
+    let revoke_response = execute_revoke(deps, contract, Some(gov_module.clone()))?;
+    Ok(Response::new()
+        .add_submessages(revoke_response.messages)
+        .add_events(revoke_response.events)
+        .add_attribute("action", "factory_handover_to_gov")
+        .add_attribute("new_admin", gov_module))
+}
+
+fn execute_create_cl_pool(
+    deps: DepsMut,
+    contract: Addr,
+    amount: Uint128,
+    quote: Coin,
+    tick_spacing: u64,
+    spread_factor: Decimal,
+    locked_until: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    if CL_POOL.exists(deps.storage) {
+        return Err(ContractError::PoolExists);
+    }
+    let denom = DENOM.load(deps.storage)?;
+    if amount.is_zero() || quote.amount.is_zero() || quote.denom == denom {
+        return Err(ContractError::InvalidPool);
+    }
+
+    // the pool can only be seeded with what the contract doesn't have to keep
+    check_spendable(deps.as_ref(), &contract, amount)?;
+    if quote.amount > free_balance(deps.as_ref(), &contract, &quote.denom)? {
+        return Err(ContractError::InsufficientBalance(quote.to_string()));
+    }
+
+    // the position is opened once the pool id is known. coins have to be sorted by denom
+    let mut tokens = vec![
+        Coin {
+            denom: denom.clone(),
+            amount,
+        },
+        quote.clone(),
+    ];
+    tokens.sort_by(|a, b| a.denom.cmp(&b.denom));
+    PENDING_POSITION.save(
+        deps.storage,
+        &PendingPosition {
+            tokens,
+            locked_until,
+        },
+    )?;
+
+    // sdk decimals are encoded by their atomics, with the same 18 decimal places as Decimal
+    let msg: CosmosMsg = MsgCreateConcentratedPool {
+        sender: contract.into_string(),
+        denom0: denom,
+        denom1: quote.denom.clone(),
+        tick_spacing,
+        spread_factor: spread_factor.atomics().to_string(),
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, CREATE_POOL_REPLY_ID))
+        .add_attribute("action", "factory_create_cl_pool")
+        .add_attribute("amount", amount)
+        .add_attribute("quote", quote.to_string())
+        .add_attribute(
+            "locked_until",
+            locked_until.map_or("permanent".to_string(), |time| time.to_string()),
+        ))
+}
+
+fn execute_create_denom(
+    deps: DepsMut,
+    env: Env,
+    symbol: String,
+    subdenom: Option<String>,
+    max_supply: Uint128,
+) -> Result<Response, ContractError> {
+    validate_symbol(&SYMBOL_RULES.load(deps.storage)?, &symbol)?;
+    if symbol == SYMBOL.load(deps.storage)? || DENOMS.has(deps.storage, &symbol) {
+        return Err(ContractError::DenomExists(symbol));
+    }
+    let contract = env.contract.address.as_str();
+    let subdenom = helpers::subdenom(
+        &symbol,
+        subdenom.as_deref(),
+        TFA_PREFIX.exists(deps.storage),
+    );
+    validate_subdenom(&subdenom)?;
+
+    // the denom is known up front, so unlike the token itself it doesn't wait for a reply
+    let denom = format!("factory/{contract}/{subdenom}");
+    DENOMS.save(
+        deps.storage,
+        &symbol,
+        &ManagedDenom {
+            denom: denom.clone(),
+            max_supply,
+            minted: Uint128::zero(),
+            burned: Uint128::zero(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_message(tokenfactory::create_denom(contract, &subdenom))
+        .add_attribute("action", "factory_create_denom")
+        .add_attribute("symbol", symbol)
+        .add_attribute("denom", denom)
+        .add_attribute("max_supply", max_supply))
+}
+
+fn execute_mint_denom(
+    deps: DepsMut,
+    env: Env,
+    symbol: String,
+    receivers: Vec<Receiver>,
+) -> Result<Response, ContractError> {
+    let mut managed = DENOMS
+        .may_load(deps.storage, &symbol)?
+        .ok_or_else(|| ContractError::UnknownDenom(symbol.clone()))?;
+    let contract = env.contract.address.as_str();
+
+    // amounts are always in base units, the exponent only applies to the token itself
+    if let Some(i) = receivers
+        .iter()
+        .position(|receiver| receiver.display_units.unwrap_or_default())
+    {
+        return Err(ContractError::MintInvalid(i));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_mint_denom")
+        .add_attribute("symbol", &symbol);
+    for (address, amount) in
+        aggregate_receivers(deps.api, contract, &receivers, ContractError::MintInvalid)?
+    {
+        managed.minted = managed.minted.checked_add(amount)?;
+        response = response
+            .add_message(tokenfactory::mint(
+                contract,
+                &managed.denom,
+                amount,
+                &address,
+            ))
+            .add_event(mint_event(
+                &managed.denom,
+                &address,
+                amount,
+                managed.minted.u128(),
+            ))
+            .add_attribute("recipient", address)
+            .add_attribute("amount", amount);
+    }
+    // a max supply of 0 leaves the denom uncapped, like the token itself
+    if !managed.max_supply.is_zero() && managed.minted > managed.max_supply {
+        return Err(ContractError::DenomSupplyExceeded(symbol));
+    }
+    DENOMS.save(deps.storage, &symbol, &managed)?;
+
+    Ok(response)
+}
+
+fn execute_burn_denom(
+    deps: DepsMut,
+    env: Env,
+    symbol: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut managed = DENOMS
+        .may_load(deps.storage, &symbol)?
+        .ok_or_else(|| ContractError::UnknownDenom(symbol.clone()))?;
+    let contract = env.contract.address.as_str();
+
+    let balance = deps.querier.query_balance(contract, &managed.denom)?.amount;
+    if amount.is_zero() || amount > balance {
+        return Err(ContractError::InsufficientBalance(format!(
+            "{amount}{}",
+            managed.denom
+        )));
+    }
+    managed.burned = managed.burned.checked_add(amount)?;
+    DENOMS.save(deps.storage, &symbol, &managed)?;
+
+    // the typed event keeps the burn in the history, like the burns of the token
+    Ok(Response::new()
+        .add_message(tokenfactory::burn(
+            contract,
+            &managed.denom,
+            amount,
+            contract,
+        ))
+        .add_event(
+            Event::new("tfa-burn")
+                .add_attribute("denom", managed.denom)
+                .add_attribute("amount", amount),
+        )
+        .add_attribute("action", "factory_burn_denom")
+        .add_attribute("symbol", symbol)
+        .add_attribute("amount", amount))
+}
+
+fn execute_update_denom_minters(
+    deps: DepsMut,
+    contract: &Addr,
+    symbol: String,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !DENOMS.has(deps.storage, &symbol) {
+        return Err(ContractError::UnknownDenom(symbol));
+    }
+
+    for address in &add {
+        let address = validate_address(deps.api, contract.as_str(), address)?;
+        DENOM_MINTERS.save(deps.storage, (&symbol, &address), &Empty {})?;
+    }
+    for address in &remove {
+        DENOM_MINTERS.remove(deps.storage, (&symbol, &Addr::unchecked(address)))?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_update_denom_minters")
+        .add_attribute("symbol", symbol)
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_create_gauge(
+    mut deps: DepsMut,
+    env: Env,
+    pool_id: u64,
+    amount: Uint128,
+    epochs: u64,
+    lock_duration: Option<u64>,
+    start_time: Option<Timestamp>,
+    mint: bool,
+) -> Result<Response, ContractError> {
+    if pool_id == 0 || amount.is_zero() || epochs == 0 {
+        return Err(ContractError::InvalidGauge);
+    }
+    let contract = env.contract.address.clone();
+    let denom = DENOM.load(deps.storage)?;
+
+    // minted tokens reach the contract before the gauge is created
+    let mut response = Response::new();
+    if mint {
+        check_mint_window(deps.as_ref(), env.block.time)?;
+        let mint_response = execute_mint(
+            deps.branch(),
+            &contract,
+            env.block.time,
+            &[Receiver {
+                address: contract.to_string(),
+                amount,
+                display_units: None,
+            }],
+            None,
+            Charge::Exempt,
+        )?;
+        response = response
+            .add_submessages(mint_response.messages)
+            .add_events(mint_response.events);
+    } else if amount > free_balance(deps.as_ref(), &contract, &denom)? {
+        return Err(ContractError::InsufficientBalance(format!(
+            "{amount}{denom}"
+        )));
+    }
+
+    // pool gauges can't have a lock condition, the module fills in the pool's gauge denom
+    let distribute_to = match lock_duration {
+        Some(seconds) => QueryCondition {
+            lock_query_type: LockQueryType::ByDuration.into(),
+            denom: format!("gamm/pool/{pool_id}"),
+            duration: Some(ProtoDuration {
+                seconds: seconds as i64,
+                nanos: 0,
+            }),
+            timestamp: None,
+        },
+        None => QueryCondition {
+            lock_query_type: LockQueryType::NoLock.into(),
+            denom: String::new(),
+            duration: None,
+            timestamp: None,
+        },
+    };
+    let msg: CosmosMsg = MsgCreateGauge {
+        is_perpetual: false,
+        owner: contract.into_string(),
+        distribute_to: Some(distribute_to),
+        coins: vec![ProtoCoin {
+            denom,
+            amount: amount.to_string(),
+        }],
+        start_time: start_time.map(|time| ProtoTimestamp {
+            seconds: time.seconds() as i64,
+            nanos: time.subsec_nanos() as i32,
+        }),
+        num_epochs_paid_over: epochs,
+        pool_id: if lock_duration.is_some() { 0 } else { pool_id },
+    }
+    .into();
+
+    Ok(response
+        .add_message(msg)
+        .add_attribute("action", "factory_create_gauge")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("epochs", epochs.to_string()))
+}
+
+fn execute_buyback(
+    deps: DepsMut,
+    contract: Addr,
+    offer: Coin,
+    pool_id: Option<u64>,
+    min_out: Uint128,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    if offer.denom == denom || offer.amount.is_zero() || min_out.is_zero() {
+        return Err(ContractError::InvalidBuyback);
+    }
+    let pool_id = match pool_id {
+        Some(pool_id) => pool_id,
+        None => {
+            CL_POOL
+                .may_load(deps.storage)?
+                .ok_or(ContractError::InvalidBuyback)?
+                .pool_id
+        }
+    };
+
+    let available = free_balance(deps.as_ref(), &contract, &offer.denom)?;
+    if offer.amount > available {
+        return Err(ContractError::InsufficientBalance(offer.to_string()));
+    }
+
+    let msg: CosmosMsg = MsgSwapExactAmountIn {
+        sender: contract.into_string(),
+        routes: vec![SwapAmountInRoute {
+            pool_id,
+            token_out_denom: denom,
+        }],
+        token_in: Some(ProtoCoin {
+            denom: offer.denom.clone(),
+            amount: offer.amount.to_string(),
+        }),
+        token_out_min_amount: min_out.to_string(),
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, BUYBACK_REPLY_ID))
+        .add_attribute("action", "factory_buyback")
+        .add_attribute("offer", offer.to_string())
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute("min_out", min_out))
+}
+
+// the position is owned by this contract, so this is the only way its liquidity can be removed
+fn execute_withdraw_position(
+    deps: DepsMut,
+    env: Env,
+    contract: Addr,
+) -> Result<Response, ContractError> {
+    let Some(mut pool) = CL_POOL.may_load(deps.storage)? else {
+        return Err(ContractError::NoPosition);
+    };
+    let Some(position_id) = pool.position_id else {
+        return Err(ContractError::NoPosition);
+    };
+    match pool.locked_until {
+        None => return Err(ContractError::PositionLockedForever),
+        Some(locked_until) if env.block.time < locked_until => {
+            return Err(ContractError::PositionLocked(locked_until))
+        }
+        Some(_) => {}
+    }
+
+    // the liquidity is returned encoded like the message expects it, so it is passed on as is
+    let liquidity = ConcentratedliquidityQuerier::new(&deps.querier)
+        .position_by_id(position_id)?
+        .position
+        .and_then(|breakdown| breakdown.position)
+        .ok_or(ContractError::NoPosition)?
+        .liquidity;
+
+    pool.position_id = None;
+    CL_POOL.save(deps.storage, &pool)?;
+
+    let msg: CosmosMsg = MsgWithdrawPosition {
+        position_id,
+        sender: contract.into_string(),
+        liquidity_amount: liquidity,
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "factory_withdraw_position")
+        .add_attribute("position_id", position_id.to_string()))
+}
+
+fn execute_finalize(
+    mut deps: DepsMut,
+    env: Env,
+    recipient: String,
+    assetlist: Option<AssetlistRegistration>,
+) -> Result<Response, ContractError> {
+    let contract = env.contract.address;
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+
+    // there is no remaining supply to mint for an uncapped token
+    if max_supply == 0 {
+        return Err(ContractError::UncappedSupply);
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_finalize")
+        .add_attribute("recipient", recipient.clone());
+
+    // mint whatever is left up to the cap outside of the buckets, unless the mint window has
+    // closed. the tax is minted on top, so it has to fit in the remaining supply as well
+    let reserved = bucket_reserved(deps.as_ref())?;
+    let amount = mint_before_tax(
+        MINT_TAX.may_load(deps.storage)?.as_ref(),
+        Uint128::from(max_supply.saturating_sub(total_minted + reserved)),
+    );
+    if !amount.is_zero() && check_mint_window(deps.as_ref(), env.block.time).is_ok() {
+        let mint_response = execute_mint(
+            deps.branch(),
+            &contract,
+            env.block.time,
+            &[Receiver {
+                address: recipient,
+                amount,
+                display_units: None,
+            }],
+            None,
+            Charge::Exempt,
+        )?;
+        response = response
+            .add_submessages(mint_response.messages)
+            .add_events(mint_response.events)
+            .add_attributes(mint_response.attributes);
+    }
+
+    // list the token before revoking, so the listing is published by this contract
+    if let Some(assetlist) = assetlist {
+        let (listing_msg, contract_addr) =
+            assetlist_listing(deps.as_ref(), DENOM.load(deps.storage)?, assetlist)?;
+        response = response
+            .add_message(listing_msg)
+            .add_attribute("assetlist", contract_addr);
+    }
+
+    let revoke_response = execute_revoke(deps, contract, None)?;
+    Ok(response
+        .add_submessages(revoke_response.messages)
+        .add_events(revoke_response.events)
+        .add_attributes(revoke_response.attributes))
+}
+
+fn execute_schedule_revoke(
+    deps: DepsMut,
+    env: Env,
+    delay: u64,
+    new_admin: Option<String>,
+) -> Result<Response, ContractError> {
+    if PENDING_REVOKE.exists(deps.storage) {
+        return Err(ContractError::RevokeScheduled);
+    }
+
+    // validate the target now so a bad address can't block confirmation later
+    let new_admin = new_admin
+        .map(|new_admin| validate_address(deps.api, env.contract.address.as_str(), &new_admin))
+        .transpose()?
+        .map(Addr::into_string);
+    if new_admin.as_deref() == Some(env.contract.address.as_str()) {
+        return Err(ContractError::InvalidRevokeTarget);
+    }
+
+    let executable_after = env.block.time.plus_seconds(delay);
+    PENDING_REVOKE.save(
+        deps.storage,
+        &PendingRevoke {
+            executable_after,
+            new_admin: new_admin.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_schedule_revoke")
+        .add_attribute("executable_after", executable_after.to_string())
+        .add_attribute("new_admin", new_admin.unwrap_or_default()))
+}
+
+fn execute_confirm_revoke(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let Some(pending) = PENDING_REVOKE.may_load(deps.storage)? else {
+        return Err(ContractError::RevokeNotScheduled);
+    };
+
+    if env.block.time < pending.executable_after {
+        return Err(ContractError::RevokeTimelocked(pending.executable_after));
+    }
+
+    execute_revoke(deps, env.contract.address, pending.new_admin)
+}
+
+fn execute_cancel_revoke(deps: DepsMut) -> Result<Response, ContractError> {
+    if !PENDING_REVOKE.exists(deps.storage) {
+        return Err(ContractError::RevokeNotScheduled);
+    }
+
+    PENDING_REVOKE.remove(deps.storage);
+    Ok(Response::new().add_attribute("action", "factory_cancel_revoke"))
+}
+
+fn execute_update_supply(deps: DepsMut, new_max: &Uint128) -> Result<Response, ContractError> {
+    if SUPPLY_LOCKED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::SupplyLocked);
+    }
+
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+
+    // make sure that the max supply is not reduced below the total minted amount plus what is still
+    // reserved in allocation buckets, unless the new max is 0 (uncapped)
+    if new_max.u128() < total_minted + bucket_reserved(deps.as_ref())? && !new_max.is_zero() {
+        return Err(ContractError::CurrentSupply);
+    }
+
+    MAX_SUPPLY.save(deps.storage, &new_max.u128())?;
+    Ok(Response::new().add_attribute("action", "factory_update_supply"))
+}
+
+fn execute_set_mint_tax(
+    deps: DepsMut,
+    contract: &Addr,
+    mint_tax: Option<MintTax>,
+) -> Result<Response, ContractError> {
+    let Some(mint_tax) = mint_tax else {
+        MINT_TAX.remove(deps.storage);
+        return Ok(Response::new()
+            .add_attribute("action", "factory_set_mint_tax")
+            .add_attribute("rate", "0"));
+    };
+    if mint_tax.rate.is_zero() || mint_tax.rate >= Decimal::one() {
+        return Err(ContractError::InvalidMintTax);
+    }
+    validate_address(deps.api, contract.as_str(), mint_tax.treasury.as_str())?;
+
+    MINT_TAX.save(deps.storage, &mint_tax)?;
+    Ok(Response::new()
+        .add_attribute("action", "factory_set_mint_tax")
+        .add_attribute("rate", mint_tax.rate.to_string())
+        .add_attribute("treasury", mint_tax.treasury))
+}
+
+fn execute_set_mint_limit(
+    deps: DepsMut,
+    limit: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    match limit {
+        Some(limit) => MINT_LIMIT.save(deps.storage, &limit)?,
+        None => MINT_LIMIT.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_set_mint_limit")
+        .add_attribute(
+            "limit",
+            limit.map_or(String::new(), |limit| limit.to_string()),
+        ))
+}
+
+fn execute_set_emission(
+    deps: DepsMut,
+    contract: &Addr,
+    emission: Option<Emission>,
+) -> Result<Response, ContractError> {
+    let Some(emission) = emission else {
+        EMISSION.remove(deps.storage);
+        EMISSION_MINTED.remove(deps.storage);
+        return Ok(Response::new()
+            .add_attribute("action", "factory_set_emission")
+            .add_attribute("recipient", ""));
+    };
+
+    validate_address(deps.api, contract.as_str(), emission.recipient.as_str())?;
+    if emission.initial_reward.is_zero() || emission.halving_interval == 0 || emission.epochs == 0 {
+        return Err(ContractError::InvalidEmission);
+    }
+    // a new schedule starts over
+    EMISSION.save(deps.storage, &emission)?;
+    EMISSION_MINTED.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_set_emission")
+        .add_attribute("total", emission_released(&emission, None))
+        .add_attribute("recipient", emission.recipient))
+}
+
+// how much the emission has released by `now`, or over all of its epochs when None. each epoch
+// releases its reward linearly, and the reward halves every epoch
+fn emission_released(emission: &Emission, now: Option<Timestamp>) -> Uint128 {
+    let elapsed = match now {
+        Some(now) => now.seconds().saturating_sub(emission.start.seconds()),
+        None => u64::MAX,
+    };
+    let epoch = elapsed / emission.halving_interval;
+
+    let mut released = Uint128::zero();
+    // the reward is zero once it has been halved 128 times
+    for k in 0..emission.epochs.min(128) {
+        let reward = Uint128::from(emission.initial_reward.u128() >> k);
+        match u64::from(k).cmp(&epoch) {
+            Ordering::Less => released += reward,
+            Ordering::Equal => {
+                released += reward.multiply_ratio(
+                    elapsed % emission.halving_interval,
+                    emission.halving_interval,
+                )
+            }
+            Ordering::Greater => break,
+        }
+    }
+    released
+}
+
+fn execute_mint_emission(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let Some(emission) = EMISSION.may_load(deps.storage)? else {
+        return Err(ContractError::NoEmission);
+    };
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused);
+    }
+    check_mint_window(deps.as_ref(), env.block.time)?;
+
+    let minted = EMISSION_MINTED.load(deps.storage)?;
+    let amount = emission_released(&emission, Some(env.block.time)) - minted;
+    if amount.is_zero() {
+        return Err(ContractError::NothingReleased);
+    }
+    EMISSION_MINTED.save(deps.storage, &(minted + amount))?;
+
+    let mint_response = execute_mint(
+        deps.branch(),
+        &env.contract.address,
+        env.block.time,
+        &[Receiver {
+            address: emission.recipient.to_string(),
+            amount,
+            display_units: None,
+        }],
+        None,
+        Charge::Exempt,
+    )?;
+    Ok(Response::new()
+        .add_submessages(mint_response.messages)
+        .add_events(mint_response.events)
+        .add_attribute("action", "factory_mint_emission")
+        .add_attribute("recipient", emission.recipient)
+        .add_attribute("amount", amount))
+}
+
+fn execute_set_bucket(
+    deps: DepsMut,
+    label: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+
+    // buckets partition the un-minted cap, so the supply must be capped
+    if max_supply == 0 {
+        return Err(ContractError::UncappedSupply);
+    }
+    if label.is_empty() {
+        return Err(ContractError::BucketNotFound(label));
+    }
+
+    let mut bucket = BUCKETS.may_load(deps.storage, &label)?.unwrap_or(Bucket {
+        allocated: Uint128::zero(),
+        minted: Uint128::zero(),
+        minters: vec![],
+    });
+
+    // a bucket can't be shrunk below what has already been minted from it
+    if amount < bucket.minted {
+        return Err(ContractError::BucketExceeded(label));
+    }
+
+    // the remaining allocations of all buckets must fit in the un-minted cap
+    let reserved = bucket_reserved(deps.as_ref())? - (bucket.allocated - bucket.minted).u128()
+        + (amount - bucket.minted).u128();
+    if total_minted + reserved > max_supply {
+        return Err(ContractError::SupplyCap);
+    }
+
+    bucket.allocated = amount;
+    BUCKETS.save(deps.storage, &label, &bucket)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_set_bucket")
+        .add_attribute("bucket", label)
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_set_bucket_minters(
+    deps: DepsMut,
+    contract: &Addr,
+    label: String,
+    minters: Vec<String>,
+) -> Result<Response, ContractError> {
+    let Some(mut bucket) = BUCKETS.may_load(deps.storage, &label)? else {
+        return Err(ContractError::BucketNotFound(label));
+    };
+
+    bucket.minters = minters
+        .iter()
+        .map(|minter| validate_address(deps.api, contract.as_str(), minter))
+        .collect::<Result<Vec<Addr>, _>>()?;
+    BUCKETS.save(deps.storage, &label, &bucket)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_set_bucket_minters")
+        .add_attribute("bucket", label)
+        .add_attribute("minters", minters.join(",")))
+}
+
+fn is_bucket_minter(deps: Deps, label: &str, sender: &Addr) -> StdResult<bool> {
+    Ok(BUCKETS
+        .may_load(deps.storage, label)?
+        .is_some_and(|bucket| bucket.minters.contains(sender)))
+}
+
+// the amount allocated to buckets that has not been minted yet
+fn bucket_reserved(deps: Deps) -> StdResult<u128> {
+    BUCKETS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, bucket)| (bucket.allocated - bucket.minted).u128()))
+        .sum()
+}
+
+fn execute_lock_supply(deps: DepsMut) -> Result<Response, ContractError> {
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+
+    // a locked uncapped supply would make no max supply guarantee at all
+    if max_supply == 0 {
+        return Err(ContractError::UncappedSupply);
+    }
+
+    SUPPLY_LOCKED.save(deps.storage, &true)?;
+    Ok(Response::new()
+        .add_attribute("action", "factory_lock_supply")
+        .add_attribute("max_supply", max_supply.to_string()))
+}
+
+fn execute_update_allowlist(
+    deps: DepsMut,
+    contract: &Addr,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    // the allowlist is only enforced when the contract was registered as the before-send hook
+    if !TRADING_START.exists(deps.storage) {
+        return Err(ContractError::NoPreLaunch);
+    }
+
+    for address in &add {
+        let address = validate_address(deps.api, contract.as_str(), address)?;
+        ALLOWLIST.save(deps.storage, &address, &Empty {})?;
+    }
+    for address in &remove {
+        ALLOWLIST.remove(deps.storage, &Addr::unchecked(address))?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_update_allowlist")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+fn execute_reconcile(deps: DepsMut) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let supply = query_bank_supply(deps.as_ref(), denom.clone());
+    let minted = TOTAL_MINTED.load(deps.storage)?;
+
+    // whatever is in circulation was minted at some point
+    let delta = supply.saturating_sub(minted);
+    if delta > 0 {
+        TOTAL_MINTED.save(deps.storage, &supply)?;
+    }
+    let minted = minted + delta;
+
+    // out of band mints can take the supply past the cap. the counters still follow the supply,
+    // and the overrun is flagged so it doesn't go unnoticed
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let cap_exceeded = max_supply != 0 && supply > max_supply;
+
+    Ok(Response::new()
+        .add_event(
+            Event::new("tfa-reconcile")
+                .add_attribute("denom", denom)
+                .add_attribute("supply", supply.to_string())
+                .add_attribute("minted", minted.to_string())
+                .add_attribute("burned", (minted - supply).to_string())
+                .add_attribute("minted_delta", delta.to_string())
+                .add_attribute("cap_exceeded", cap_exceeded.to_string()),
+        )
+        .add_attribute("action", "factory_reconcile")
+        .add_attribute("minted_delta", delta.to_string()))
+}
+
+fn execute_update_send_policy(
+    deps: DepsMut,
+    contract: &Addr,
+    enabled: Option<bool>,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    for address in &add {
+        let address = validate_address(deps.api, contract.as_str(), address)?;
+        SEND_DESTINATIONS.save(deps.storage, &address, &Empty {})?;
+    }
+    for address in &remove {
+        SEND_DESTINATIONS.remove(deps.storage, &Addr::unchecked(address))?;
+    }
+    match enabled {
+        Some(true) => SEND_POLICY.save(deps.storage, &true)?,
+        Some(false) => SEND_POLICY.remove(deps.storage),
+        None => {}
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_update_send_policy")
+        .add_attribute("enabled", SEND_POLICY.exists(deps.storage).to_string())
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+// with the send policy enabled, the token held by the contract can only go to allowed destinations
+fn check_send_destination(storage: &dyn Storage, to: &str) -> Result<(), ContractError> {
+    if SEND_POLICY.exists(storage) && !SEND_DESTINATIONS.has(storage, &Addr::unchecked(to)) {
+        return Err(ContractError::SendDestination(to.to_string()));
+    }
+    Ok(())
+}
+
+fn execute_disable_send(deps: DepsMut) -> Result<Response, ContractError> {
+    SEND_DISABLED.save(deps.storage, &true)?;
+    // allowances could only be spent by sending
+    ALLOWANCES.clear(deps.storage)?;
+
+    Ok(Response::new().add_attribute("action", "factory_disable_send"))
+}
+
+fn execute_disable_transfer_burn(deps: DepsMut) -> Result<Response, ContractError> {
+    if !TRANSFER_BURN.exists(deps.storage) {
+        return Err(ContractError::NoTransferBurn);
+    }
+    // the burned total stays, so it is still reported
+    TRANSFER_BURN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "factory_disable_transfer_burn"))
+}
+
+fn execute_set_transfer_tax(
+    deps: DepsMut,
+    env: Env,
+    transfer_tax: Option<TransferTaxMsg>,
+) -> Result<Response, ContractError> {
+    if TRANSFER_TAX_RENOUNCED.exists(deps.storage) {
+        return Err(ContractError::TransferTaxRenounced);
+    }
+    let Some(TransferTaxMsg {
+        transfer_tax,
+        exempt,
+    }) = transfer_tax
+    else {
+        TRANSFER_TAX.remove(deps.storage);
+        return Ok(Response::new()
+            .add_attribute("action", "factory_set_transfer_tax")
+            .add_attribute("rate", "0"));
+    };
+    // both are taken out of what the recipient receives
+    let transfer_burn = TRANSFER_BURN.may_load(deps.storage)?.unwrap_or_default();
+    if transfer_tax.rate.is_zero() || transfer_tax.rate + transfer_burn >= Decimal::one() {
+        return Err(ContractError::InvalidTransferTax);
+    }
+    if let Some(treasury) = &transfer_tax.treasury {
+        validate_address(deps.api, env.contract.address.as_str(), treasury.as_str())?;
+    }
+    // the pools, routers and ibc escrows the token moves through are exempted along with the tax,
+    // or the before-send hook rejects every transfer through them
+    for address in &exempt {
+        let address = validate_address(deps.api, env.contract.address.as_str(), address)?;
+        TRANSFER_FEE_EXEMPT.save(deps.storage, &address, &Empty {})?;
+    }
+
+    let mut response = Response::new();
+    // the before-send hook sends transfers through TransferFunds, set here if nothing else needed it
+    if !send_hook_enabled(deps.storage) {
+        let hook_msg: CosmosMsg = MsgSetBeforeSendHook {
+            sender: env.contract.address.to_string(),
+            denom: DENOM.load(deps.storage)?,
+            cosmwasm_address: env.contract.address.to_string(),
+        }
+        .into();
+        response = response.add_message(hook_msg);
+    }
+
+    TRANSFER_TAX.save(deps.storage, &transfer_tax)?;
+    Ok(response
+        .add_attribute("action", "factory_set_transfer_tax")
+        .add_attribute("rate", transfer_tax.rate.to_string())
+        .add_attribute(
+            "treasury",
+            transfer_tax
+                .treasury
+                .map_or(String::new(), |treasury| treasury.into_string()),
+        )
+        .add_attribute("exempt", exempt.len().to_string()))
+}
+
+fn execute_renounce_transfer_tax(deps: DepsMut) -> Result<Response, ContractError> {
+    TRANSFER_TAX.remove(deps.storage);
+    TRANSFER_TAX_RENOUNCED.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_attribute("action", "factory_renounce_transfer_tax"))
+}
+
+fn execute_update_transfer_fee_exempt(
+    deps: DepsMut,
+    contract: &Addr,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    for address in &add {
+        let address = validate_address(deps.api, contract.as_str(), address)?;
+        TRANSFER_FEE_EXEMPT.save(deps.storage, &address, &Empty {})?;
+    }
+    for address in &remove {
+        TRANSFER_FEE_EXEMPT.remove(deps.storage, &Addr::unchecked(address))?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_update_transfer_fee_exempt")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+fn execute_update_max_wallet_exempt(
+    deps: DepsMut,
+    contract: &Addr,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !MAX_WALLET.exists(deps.storage) {
+        return Err(ContractError::NoMaxWallet);
+    }
+
+    for address in &add {
+        let address = validate_address(deps.api, contract.as_str(), address)?;
+        MAX_WALLET_EXEMPT.save(deps.storage, &address, &Empty {})?;
+    }
+    for address in &remove {
+        MAX_WALLET_EXEMPT.remove(deps.storage, &Addr::unchecked(address))?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_update_max_wallet_exempt")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+fn execute_add_role(
+    deps: DepsMut,
+    contract: &Addr,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = validate_address(deps.api, contract.as_str(), &address)?;
+    ROLES.save(deps.storage, (role.as_str(), &address), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_add_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+fn execute_remove_role(
+    deps: DepsMut,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = Addr::unchecked(address);
+
+    // removing the last owner would leave the contract without anyone able to manage it
+    if role == Role::Owner
+        && ROLES.has(deps.storage, (role.as_str(), &address))
+        && role_members(deps.as_ref(), Role::Owner)?.len() == 1
+    {
+        return Err(ContractError::LastOwner);
+    }
+
+    ROLES.remove(deps.storage, (role.as_str(), &address))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_remove_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+fn role_members(deps: Deps, role: Role) -> StdResult<Vec<Addr>> {
+    ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+fn execute_claim_vested(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let Some(vesting) = PROCEEDS_VESTING.may_load(deps.storage)? else {
+        return Err(ContractError::Unauthorized);
+    };
+    if info.sender != vesting.beneficiary {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let claimed = VESTING_CLAIMED.load(deps.storage)?;
+    let amount = vested_proceeds(deps.as_ref(), &vesting, env.block.time)? - claimed;
+    VESTING_CLAIMED.save(deps.storage, &(claimed + amount))?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_claim_vested")
+        .add_attribute("beneficiary", &vesting.beneficiary)
+        .add_attribute("amount", amount);
+
+    if !amount.is_zero() {
+        let denom = PUBLIC_MINT.load(deps.storage)?.payment_denom;
+        response = response
+            .add_event(send_event(&denom, vesting.beneficiary.as_str(), amount))
+            .add_message(BankMsg::Send {
+                to_address: vesting.beneficiary.into_string(),
+                amount: vec![Coin { denom, amount }],
+            });
+    }
+
+    Ok(response)
+}
+
+// the deposited proceeds that have vested by `now`, including any already claimed. nothing
+// vests before the cliff, then deposits vest linearly from the start until the end of the duration
+fn vested_proceeds(deps: Deps, vesting: &ProceedsVesting, now: Timestamp) -> StdResult<Uint128> {
+    let deposited = VESTING_DEPOSITED.load(deps.storage)?;
+    Ok(vested_amount(
+        deposited,
+        vesting.start,
+        vesting.cliff,
+        vesting.duration,
+        now,
+    ))
+}
+
+fn vested_amount(
+    total: Uint128,
+    start: Timestamp,
+    cliff: u64,
+    duration: u64,
+    now: Timestamp,
+) -> Uint128 {
+    let elapsed = now.seconds().saturating_sub(start.seconds());
+
+    if elapsed < cliff {
+        Uint128::zero()
+    } else if elapsed >= duration {
+        total
+    } else {
+        total.multiply_ratio(elapsed, duration)
+    }
+}
+
+fn execute_mint_vested(
+    mut deps: DepsMut,
+    contract: &Addr,
+    now: Timestamp,
+    receiver: String,
+    amount: Uint128,
+    schedule: VestingSchedule,
+) -> Result<Response, ContractError> {
+    let receiver = validate_address(deps.api, contract.as_str(), &receiver)?;
+    if schedule.cliff > schedule.duration {
+        return Err(ContractError::InvalidSchedule);
+    }
+
+    // the tokens are held by the contract until claimed, and count towards the receiver
+    let mint_response = execute_mint(
+        deps.branch(),
+        contract,
+        now,
+        &[Receiver {
+            address: contract.to_string(),
+            amount,
+            display_units: None,
+        }],
+        None,
+        Charge::Beneficiary(&receiver),
+    )?;
+
+    let mut grants = VESTING_GRANTS
+        .may_load(deps.storage, &receiver)?
+        .unwrap_or_default();
+    grants.push(VestingGrant {
+        amount,
+        claimed: Uint128::zero(),
+        schedule,
+    });
+    VESTING_GRANTS.save(deps.storage, &receiver, &grants)?;
+    let locked = VESTING_LOCKED.may_load(deps.storage)?.unwrap_or_default();
+    VESTING_LOCKED.save(deps.storage, &(locked + amount))?;
+
+    Ok(mint_response.add_attribute("vesting_receiver", receiver))
+}
+
+fn execute_claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let Some(mut grants) = VESTING_GRANTS.may_load(deps.storage, &info.sender)? else {
+        return Err(ContractError::Unauthorized);
+    };
+
+    let mut amount = Uint128::zero();
+    for grant in grants.iter_mut() {
+        let vested = vested_amount(
+            grant.amount,
+            grant.schedule.start,
+            grant.schedule.cliff,
+            grant.schedule.duration,
+            env.block.time,
+        );
+        amount += vested - grant.claimed;
+        grant.claimed = vested;
+    }
+
+    // fully claimed grants are dropped, so the list only holds what is still vesting
+    grants.retain(|grant| grant.claimed < grant.amount);
+    if grants.is_empty() {
+        VESTING_GRANTS.remove(deps.storage, &info.sender)?;
+    } else {
+        VESTING_GRANTS.save(deps.storage, &info.sender, &grants)?;
+    }
+    let locked = VESTING_LOCKED.may_load(deps.storage)?.unwrap_or_default();
+    VESTING_LOCKED.save(deps.storage, &(locked - amount))?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_claim")
+        .add_attribute("receiver", &info.sender)
+        .add_attribute("amount", amount);
+
+    if !amount.is_zero() {
+        let denom = DENOM.load(deps.storage)?;
+        response = response
+            .add_event(send_event(&denom, info.sender.as_str(), amount))
+            .add_message(BankMsg::Send {
+                to_address: info.sender.into_string(),
+                amount: vec![Coin { denom, amount }],
+            });
+    }
+
+    Ok(response)
+}
+
+fn execute_create_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    snapshot_height: u64,
+    total: Uint128,
+    expiry: Timestamp,
+) -> Result<Response, ContractError> {
+    if info.funds.is_empty()
+        || total.is_zero()
+        || expiry <= env.block.time
+        || !matches!(hex::decode(&merkle_root), Ok(root) if root.len() == 32)
+    {
+        return Err(ContractError::InvalidDistribution);
+    }
+
+    let id = DISTRIBUTION_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+    DISTRIBUTION_ID.save(deps.storage, &id)?;
+    update_distribution_held(deps.storage, &info.funds, false)?;
+    DISTRIBUTIONS.save(
+        deps.storage,
+        id,
+        &Distribution {
+            merkle_root,
+            snapshot_height,
+            total,
+            rewards: info.funds.clone(),
+            claimed: vec![],
+            expiry,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_create_distribution")
+        .add_attribute("id", id.to_string())
+        .add_attribute("snapshot_height", snapshot_height.to_string())
+        .add_attribute(
+            "rewards",
+            info.funds
+                .iter()
+                .map(Coin::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .add_attribute("expiry", expiry.to_string()))
+}
+
+fn execute_claim_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    balance: Uint128,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let Some(mut distribution) = DISTRIBUTIONS.may_load(deps.storage, id)? else {
+        return Err(ContractError::DistributionNotFound(id));
+    };
+    if env.block.time >= distribution.expiry {
+        return Err(ContractError::DistributionExpired(distribution.expiry));
+    }
+    if DISTRIBUTION_CLAIMS.has(deps.storage, (id, &info.sender)) {
+        return Err(ContractError::DistributionClaimed);
+    }
+    verify_proof(&distribution.merkle_root, &info.sender, balance, &proof)?;
+    if balance > distribution.total {
+        return Err(ContractError::InvalidMerkleProof);
+    }
+
+    // the balances in a bad merkle tree can add up to more than the total, so no claim can take
+    // more than is left
+    let share: Vec<Coin> = distribution
+        .rewards
+        .iter()
+        .map(|reward| Coin {
+            denom: reward.denom.clone(),
+            amount: reward
+                .amount
+                .multiply_ratio(balance, distribution.total)
+                .min(reward.amount - coin_amount(&distribution.claimed, &reward.denom)),
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+    for coin in &share {
+        match distribution
+            .claimed
+            .iter_mut()
+            .find(|claimed| claimed.denom == coin.denom)
+        {
+            Some(claimed) => claimed.amount += coin.amount,
+            None => distribution.claimed.push(coin.clone()),
+        }
+    }
+    DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
+    DISTRIBUTION_CLAIMS.save(deps.storage, (id, &info.sender), &Empty {})?;
+    update_distribution_held(deps.storage, &share, true)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_claim_distribution")
+        .add_attribute("id", id.to_string())
+        .add_attribute("holder", &info.sender)
+        .add_attribute("balance", balance);
+
+    if !share.is_empty() {
+        response = response
+            .add_events(
+                share
+                    .iter()
+                    .map(|coin| send_event(&coin.denom, info.sender.as_str(), coin.amount)),
+            )
+            .add_message(BankMsg::Send {
+                to_address: info.sender.into_string(),
+                amount: share,
+            });
+    }
+
+    Ok(response)
+}
+
+// adds deposited distribution rewards to the running total the contract holds, or takes paid out
+// ones off it
+fn update_distribution_held(
+    storage: &mut dyn Storage,
+    coins: &[Coin],
+    released: bool,
+) -> StdResult<()> {
+    for coin in coins {
+        DISTRIBUTION_HELD.update(storage, &coin.denom, |held| -> StdResult<_> {
+            let held = held.unwrap_or_default();
+            Ok(if released {
+                held - coin.amount
+            } else {
+                held + coin.amount
+            })
+        })?;
+    }
+    Ok(())
+}
+
+fn execute_sweep_distribution(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+    to: String,
+) -> Result<Response, ContractError> {
+    let to = validate_address(deps.api, env.contract.address.as_str(), &to)?;
+    let Some(distribution) = DISTRIBUTIONS.may_load(deps.storage, id)? else {
+        return Err(ContractError::DistributionNotFound(id));
+    };
+    if env.block.time < distribution.expiry {
+        return Err(ContractError::DistributionActive(distribution.expiry));
+    }
+    DISTRIBUTIONS.remove(deps.storage, id)?;
+
+    let unclaimed: Vec<Coin> = distribution
+        .rewards
+        .iter()
+        .map(|reward| Coin {
+            denom: reward.denom.clone(),
+            amount: reward.amount - coin_amount(&distribution.claimed, &reward.denom),
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+    update_distribution_held(deps.storage, &unclaimed, true)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_sweep_distribution")
+        .add_attribute("id", id.to_string())
+        .add_attribute("recipient", to.clone());
+
+    if !unclaimed.is_empty() {
+        response = response
+            .add_events(
+                unclaimed
+                    .iter()
+                    .map(|coin| send_event(&coin.denom, to.as_str(), coin.amount)),
+            )
+            .add_message(BankMsg::Send {
+                to_address: to.into_string(),
+                amount: unclaimed,
+            });
+    }
+
+    Ok(response)
+}
+
+// emits the rewards accrued since the last update, as far as the funded rewards last
+fn accrue_rewards(staking: &mut Staking, now: Timestamp) {
+    if !staking.total_weight.is_zero() {
+        let elapsed = now.seconds().saturating_sub(staking.last_update.seconds());
+        let emitted = staking
+            .reward_rate
+            .saturating_mul(Uint128::from(elapsed))
+            .min(staking.rewards_left);
+        staking.reward_per_weight += Decimal256::from_ratio(emitted, staking.total_weight);
+        staking.rewards_left -= emitted;
+        staking.unclaimed += emitted;
+    } else {
+        // with nothing staked every staker is settled, so what the shares were floored by
+        // goes back to the rewards still to be emitted
+        staking.rewards_left += staking.unclaimed - staking.settled;
+        staking.unclaimed = staking.settled;
+    }
+    staking.last_update = now;
+}
+
+// moves the rewards owed to the staker since they were last settled into their pending rewards
+fn settle_rewards(staker: &mut Staker, staking: &mut Staking) -> StdResult<()> {
+    let owed = Uint128::try_from(
+        Uint256::from(staker.weight)
+            .mul_floor(staking.reward_per_weight - staker.reward_per_weight),
+    )?;
+    staker.pending += owed;
+    staking.settled += owed;
+    staker.reward_per_weight = staking.reward_per_weight;
+    Ok(())
+}
+
+fn load_staker(deps: Deps, staking: &Staking, address: &Addr) -> StdResult<Staker> {
+    Ok(STAKERS.may_load(deps.storage, address)?.unwrap_or(Staker {
+        weight: Uint128::zero(),
+        reward_per_weight: staking.reward_per_weight,
+        pending: Uint128::zero(),
+    }))
+}
+
+fn execute_configure_staking(
+    deps: DepsMut,
+    env: Env,
+    reward_denom: String,
+    reward_rate: Uint128,
+    tiers: Vec<LockTier>,
+) -> Result<Response, ContractError> {
+    if tiers.is_empty() || tiers.iter().any(|tier| tier.multiplier.is_zero()) {
+        return Err(ContractError::InvalidStaking);
+    }
+
+    let staking = match STAKING.may_load(deps.storage)? {
+        Some(mut staking) => {
+            accrue_rewards(&mut staking, env.block.time);
+            if staking.reward_denom != reward_denom
+                && !(staking.rewards_left + staking.unclaimed).is_zero()
+            {
+                return Err(ContractError::InvalidStaking);
+            }
+            Staking {
+                reward_denom,
+                reward_rate,
+                tiers,
+                ..staking
+            }
+        }
+        None => Staking {
+            reward_denom,
+            reward_rate,
+            tiers,
+            total_staked: Uint128::zero(),
+            total_weight: Uint128::zero(),
+            reward_per_weight: Decimal256::zero(),
+            last_update: env.block.time,
+            rewards_left: Uint128::zero(),
+            unclaimed: Uint128::zero(),
+            settled: Uint128::zero(),
+        },
+    };
+    STAKING.save(deps.storage, &staking)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_configure_staking")
+        .add_attribute("reward_denom", staking.reward_denom)
+        .add_attribute("reward_rate", staking.reward_rate))
+}
+
+fn execute_fund_staking(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let Some(mut staking) = STAKING.may_load(deps.storage)? else {
+        return Err(ContractError::StakingDisabled);
+    };
+    let [Coin { denom, amount }] = info.funds.as_slice() else {
+        return Err(ContractError::InvalidStakingFunds);
+    };
+    if *denom != staking.reward_denom || amount.is_zero() {
+        return Err(ContractError::InvalidStakingFunds);
+    }
+
+    accrue_rewards(&mut staking, env.block.time);
+    staking.rewards_left += amount;
+    STAKING.save(deps.storage, &staking)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_fund_staking")
+        .add_attribute("funder", info.sender)
+        .add_attribute("amount", *amount))
+}
+
+fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let Some(mut staking) = STAKING.may_load(deps.storage)? else {
+        return Err(ContractError::StakingDisabled);
+    };
+    let [Coin { denom, amount }] = info.funds.as_slice() else {
+        return Err(ContractError::InvalidStake);
+    };
+    let Some(tier) = staking.tiers.iter().find(|tier| tier.duration == duration) else {
+        return Err(ContractError::InvalidStake);
+    };
+    let weight = amount.mul_floor(tier.multiplier);
+    if *denom != DENOM.load(deps.storage)? || weight.is_zero() {
+        return Err(ContractError::InvalidStake);
+    }
+
+    // rewards are settled at the old weights before the new stake starts earning
+    accrue_rewards(&mut staking, env.block.time);
+    let mut staker = load_staker(deps.as_ref(), &staking, &info.sender)?;
+    settle_rewards(&mut staker, &mut staking)?;
+    staker.weight += weight;
+    staking.total_weight += weight;
+    staking.total_staked += amount;
+
+    let id = STAKE_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+    let unlock = env.block.time.plus_seconds(duration);
+    STAKE_ID.save(deps.storage, &id)?;
+    UNLOCK_WEIGHTS.update(deps.storage, unlock.nanos(), |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + weight)
+    })?;
+    STAKE_POSITIONS.save(
+        deps.storage,
+        (&info.sender, id),
+        &StakePosition {
+            amount: *amount,
+            weight,
+            unlock,
+        },
+    )?;
+    STAKERS.save(deps.storage, &info.sender, &staker)?;
+    STAKING.save(deps.storage, &staking)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_stake")
+        .add_attribute("staker", info.sender)
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", *amount)
+        .add_attribute("unlock", unlock.to_string()))
+}
+
+fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let Some(mut staking) = STAKING.may_load(deps.storage)? else {
+        return Err(ContractError::StakingDisabled);
+    };
+    let Some(position) = STAKE_POSITIONS.may_load(deps.storage, (&info.sender, id))? else {
+        return Err(ContractError::StakeNotFound(id));
+    };
+    if env.block.time < position.unlock {
+        return Err(ContractError::StakeLocked(position.unlock));
+    }
+
+    accrue_rewards(&mut staking, env.block.time);
+    let mut staker = load_staker(deps.as_ref(), &staking, &info.sender)?;
+    settle_rewards(&mut staker, &mut staking)?;
+    staker.weight -= position.weight;
+    staking.total_weight -= position.weight;
+    staking.total_staked -= position.amount;
+
+    STAKE_POSITIONS.remove(deps.storage, (&info.sender, id))?;
+    let unlocking = UNLOCK_WEIGHTS.load(deps.storage, position.unlock.nanos())? - position.weight;
+    if unlocking.is_zero() {
+        UNLOCK_WEIGHTS.remove(deps.storage, position.unlock.nanos());
+    } else {
+        UNLOCK_WEIGHTS.save(deps.storage, position.unlock.nanos(), &unlocking)?;
+    }
+    STAKERS.save(deps.storage, &info.sender, &staker)?;
+    STAKING.save(deps.storage, &staking)?;
+
+    let denom = DENOM.load(deps.storage)?;
+    Ok(Response::new()
+        .add_event(send_event(&denom, info.sender.as_str(), position.amount))
+        .add_attribute("action", "factory_unstake")
+        .add_attribute("staker", &info.sender)
+        .add_attribute("id", id.to_string())
+        .add_attribute("amount", position.amount)
+        .add_message(BankMsg::Send {
+            to_address: info.sender.into_string(),
+            amount: vec![Coin {
+                denom,
+                amount: position.amount,
+            }],
+        }))
+}
+
+fn execute_claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let Some(mut staking) = STAKING.may_load(deps.storage)? else {
+        return Err(ContractError::StakingDisabled);
+    };
+
+    accrue_rewards(&mut staking, env.block.time);
+    let mut staker = load_staker(deps.as_ref(), &staking, &info.sender)?;
+    settle_rewards(&mut staker, &mut staking)?;
+    let amount = staker.pending;
+    staker.pending = Uint128::zero();
+    staking.unclaimed -= amount;
+    staking.settled -= amount;
+
+    // stakers without stakes or pending rewards are forgotten
+    if staker.weight.is_zero() {
+        STAKERS.remove(deps.storage, &info.sender)?;
+    } else {
+        STAKERS.save(deps.storage, &info.sender, &staker)?;
+    }
+    STAKING.save(deps.storage, &staking)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "factory_claim_rewards")
+        .add_attribute("staker", &info.sender)
+        .add_attribute("amount", amount);
+
+    if !amount.is_zero() {
+        response = response
+            .add_event(send_event(
+                &staking.reward_denom,
+                info.sender.as_str(),
+                amount,
+            ))
+            .add_message(BankMsg::Send {
+                to_address: info.sender.into_string(),
+                amount: vec![Coin {
+                    denom: staking.reward_denom,
+                    amount,
+                }],
+            });
+    }
+
+    Ok(response)
+}
+
+// the actions a proposal can execute
+fn is_governable(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::UpdateSupply(_)
+            | ExecuteMsg::LockSupply
+            | ExecuteMsg::Revoke
+            | ExecuteMsg::RevokeTo { .. }
+            | ExecuteMsg::HandoverToGov { .. }
+            | ExecuteMsg::Send(_)
+            | ExecuteMsg::SendAndCall { .. }
+            | ExecuteMsg::Burn(_)
+            | ExecuteMsg::Pause
+            | ExecuteMsg::Unpause
+    )
+}
+
+// only stakes locked until voting closes count, so tokens can't be moved to vote twice
+fn voting_power(deps: Deps, voter: &Addr, end: Timestamp) -> StdResult<Uint128> {
+    STAKE_POSITIONS
+        .prefix(voter)
+        .range(deps.storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |power, position| {
+            let (_, position) = position?;
+            Ok(if position.unlock >= end {
+                power + position.weight
+            } else {
+                power
+            })
+        })
+}
+
+fn reached_quorum(proposal: &Proposal) -> bool {
+    let votes = proposal.yes + proposal.no;
+    !votes.is_zero() && votes >= proposal.total_weight.mul_ceil(proposal.quorum)
+}
+
+fn proposal_status(governance: &Governance, proposal: &Proposal, now: Timestamp) -> &'static str {
+    if proposal.executed {
+        return "executed";
+    }
+    if now < proposal.end {
+        return "open";
+    }
+
+    let votes = proposal.yes + proposal.no;
+    if !reached_quorum(proposal) || proposal.yes <= votes.mul_floor(governance.threshold) {
+        "rejected"
+    } else if now >= proposal.expires {
+        "expired"
+    } else {
+        "passed"
+    }
+}
+
+fn execute_configure_governance(
+    deps: DepsMut,
+    governance: Option<Governance>,
+) -> Result<Response, ContractError> {
+    let Some(governance) = governance else {
+        GOVERNANCE.remove(deps.storage);
+        return Ok(Response::new().add_attribute("action", "factory_configure_governance"));
+    };
+    // the voting period stands in for the timelock, so it can't be shorter
+    let timelock = TIMELOCK.may_load(deps.storage)?.unwrap_or_default();
+    if governance.voting_period == 0
+        || governance.voting_period < timelock
+        || governance.execution_period == 0
+        || governance.quorum > Decimal::one()
+        || governance.threshold > Decimal::one()
+    {
+        return Err(ContractError::InvalidGovernance);
+    }
+
+    GOVERNANCE.save(deps.storage, &governance)?;
+    Ok(Response::new()
+        .add_attribute("action", "factory_configure_governance")
+        .add_attribute("voting_period", governance.voting_period.to_string())
+        .add_attribute("execution_period", governance.execution_period.to_string())
+        .add_attribute("quorum", governance.quorum.to_string())
+        .add_attribute("threshold", governance.threshold.to_string()))
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<ExecuteMsg>,
+) -> Result<Response, ContractError> {
+    let Some(governance) = GOVERNANCE.may_load(deps.storage)? else {
+        return Err(ContractError::GovernanceDisabled);
+    };
+    let Some(staking) = STAKING.may_load(deps.storage)? else {
+        return Err(ContractError::StakingDisabled);
+    };
+    let deposit = Coin {
+        denom: DENOM.load(deps.storage)?,
+        amount: governance.deposit,
+    };
+    let paid = match info.funds.as_slice() {
+        [] => deposit.amount.is_zero(),
+        [funds] => *funds == deposit,
+        _ => false,
+    };
+    if !paid {
+        return Err(ContractError::ProposalDeposit(deposit));
+    }
+    if msgs.len() > MAX_PROPOSAL_MSGS
+        || title.chars().count() > MAX_PROPOSAL_TITLE_LENGTH
+        || description.chars().count() > MAX_PROPOSAL_DESCRIPTION_LENGTH
+    {
+        return Err(ContractError::ProposalTooLong(
+            MAX_PROPOSAL_MSGS,
+            MAX_PROPOSAL_TITLE_LENGTH,
+            MAX_PROPOSAL_DESCRIPTION_LENGTH,
+        ));
+    }
+
+    // amounts are converted now, so voters see what will be executed
+    let msgs = msgs
+        .into_iter()
+        .map(|msg| to_base_units(deps.as_ref(), msg))
+        .collect::<Result<Vec<_>, _>>()?;
+    if msgs.is_empty() || !msgs.iter().all(is_governable) {
+        return Err(ContractError::InvalidProposal);
+    }
+
+    let end = env.block.time.plus_seconds(governance.voting_period);
+    let expires = end.plus_seconds(governance.execution_period);
+    if voting_power(deps.as_ref(), &info.sender, end)?.is_zero() {
+        return Err(ContractError::NoVotingPower);
+    }
+
+    // stakes unlocking before voting closes can't vote, so they don't count towards the quorum
+    let unlocking = UNLOCK_WEIGHTS
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::exclusive(end.nanos())),
+            Order::Ascending,
+        )
+        .try_fold(Uint128::zero(), |total, item| -> StdResult<_> {
+            Ok(total + item?.1)
+        })?;
+
+    let id = PROPOSAL_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+    PROPOSAL_ID.save(deps.storage, &id)?;
+    let deposits = PROPOSAL_DEPOSITS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    PROPOSAL_DEPOSITS.save(deps.storage, &(deposits + deposit.amount))?;
+    PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            proposer: info.sender.clone(),
+            title,
+            description,
+            msgs,
+            end,
+            expires,
+            total_weight: staking.total_weight - unlocking,
+            yes: Uint128::zero(),
+            no: Uint128::zero(),
+            executed: false,
+            deposit: deposit.amount,
+            quorum: governance.quorum,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_propose")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposer", info.sender)
+        .add_attribute("end", end.to_string())
+        .add_attribute("expires", expires.to_string()))
+}
+
+fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    approve: bool,
+) -> Result<Response, ContractError> {
+    let Some(mut proposal) = PROPOSALS.may_load(deps.storage, id)? else {
+        return Err(ContractError::ProposalNotFound(id));
+    };
+    if env.block.time >= proposal.end {
+        return Err(ContractError::VotingClosed(proposal.end));
+    }
+    if VOTES.has(deps.storage, (id, &info.sender)) {
+        return Err(ContractError::AlreadyVoted);
+    }
+
+    let power = voting_power(deps.as_ref(), &info.sender, proposal.end)?;
+    if power.is_zero() {
+        return Err(ContractError::NoVotingPower);
+    }
+    if approve {
+        proposal.yes += power;
+    } else {
+        proposal.no += power;
+    }
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+    VOTES.save(deps.storage, (id, &info.sender), &approve)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_vote")
+        .add_attribute("id", id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("approve", approve.to_string())
+        .add_attribute("power", power))
+}
+
+fn execute_proposal(mut deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let Some(governance) = GOVERNANCE.may_load(deps.storage)? else {
+        return Err(ContractError::GovernanceDisabled);
+    };
+    let Some(mut proposal) = PROPOSALS.may_load(deps.storage, id)? else {
+        return Err(ContractError::ProposalNotFound(id));
+    };
+    if env.block.time < proposal.end {
+        return Err(ContractError::VotingOpen(proposal.end));
+    }
+    if proposal_status(&governance, &proposal, env.block.time) != "passed" {
+        return Err(ContractError::ProposalNotPassed);
+    }
+
+    proposal.executed = true;
+    let mut response = settle_deposit(deps.storage, &env, id, &mut proposal)?
+        .add_attribute("action", "factory_execute_proposal")
+        .add_attribute("id", id.to_string());
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    // the voting period stands in for the timelock, and the actions run like queued ones
+    for msg in proposal.msgs {
+        let action_response = dispatch(deps.branch(), env.clone(), msg)?;
+        response = response
+            .add_submessages(action_response.messages)
+            .add_attributes(action_response.attributes)
+            .add_events(action_response.events);
+    }
+
+    Ok(response)
+}
+
+// doesn't need governance to be enabled, so deposits can't be stranded by disabling it
+fn execute_settle_deposit(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let Some(mut proposal) = PROPOSALS.may_load(deps.storage, id)? else {
+        return Err(ContractError::ProposalNotFound(id));
+    };
+    if env.block.time < proposal.end {
+        return Err(ContractError::VotingOpen(proposal.end));
+    }
+    if proposal.deposit.is_zero() {
+        return Err(ContractError::NoProposalDeposit);
+    }
+
+    let response = settle_deposit(deps.storage, &env, id, &mut proposal)?;
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+    Ok(response)
+}
+
+// refunds a closed proposal's deposit to the proposer if it reached the quorum, and burns it
+// otherwise, so proposing spam costs the spammer
+fn settle_deposit(
+    storage: &mut dyn Storage,
+    env: &Env,
+    id: u64,
+    proposal: &mut Proposal,
+) -> Result<Response, ContractError> {
+    let amount = proposal.deposit;
+    if amount.is_zero() {
+        return Ok(Response::new());
+    }
+    proposal.deposit = Uint128::zero();
+    PROPOSAL_DEPOSITS.update(storage, |total| -> StdResult<_> { Ok(total - amount) })?;
+
+    let denom = DENOM.load(storage)?;
+    let contract = env.contract.address.as_str();
+    let response = Response::new()
+        .add_attribute("action", "factory_settle_deposit")
+        .add_attribute("id", id.to_string());
+    if reached_quorum(proposal) {
+        Ok(response
+            .add_attribute("refunded", amount)
+            .add_attribute("burned", "0")
+            .add_event(send_event(&denom, proposal.proposer.as_str(), amount))
+            .add_message(BankMsg::Send {
+                to_address: proposal.proposer.to_string(),
+                amount: vec![Coin::new(amount.u128(), &denom)],
+            }))
+    } else {
+        Ok(response
+            .add_attribute("refunded", "0")
+            .add_attribute("burned", amount)
+            .add_event(
+                Event::new("tfa-burn")
+                    .add_attribute("denom", &denom)
+                    .add_attribute("amount", amount),
+            )
+            .add_message(tokenfactory::burn(contract, &denom, amount, contract)))
+    }
+}
+
+fn query_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ProposalResponse>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+    let governance = GOVERNANCE.may_load(deps.storage)?;
+
+    PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            // proposals that can no longer be executed read as rejected
+            let status = governance.as_ref().map_or("rejected", |governance| {
+                proposal_status(governance, &proposal, env.block.time)
+            });
+            Ok(ProposalResponse {
+                id,
+                status: status.to_string(),
+                proposal,
+            })
+        })
+        .collect()
+}
+
+fn query_price(deps: Deps, env: Env, quote_denom: String) -> StdResult<PriceResponse> {
+    let Some(pool) = CL_POOL.may_load(deps.storage)? else {
+        return Err(StdError::generic_err(
+            "No pool has been created for the token",
+        ));
+    };
+    // a twap can't start before the pool existed, so young pools average over their whole life
+    let start_time = env
+        .block
+        .time
+        .minus_seconds(TWAP_WINDOW)
+        .max(pool.created.unwrap_or_default());
+
+    let twap = TwapQuerier::new(&deps.querier).arithmetic_twap_to_now(
+        pool.pool_id,
+        DENOM.load(deps.storage)?,
+        quote_denom.clone(),
+        Some(ProtoTimestamp {
+            seconds: start_time.seconds() as i64,
+            nanos: start_time.subsec_nanos() as i32,
+        }),
+    )?;
+
+    Ok(PriceResponse {
+        pool_id: pool.pool_id,
+        quote_denom,
+        // sdk decimals have the same 18 decimal places as Decimal
+        price: Decimal::from_str(&twap.arithmetic_twap)?,
+        start_time,
+    })
+}
+
+fn query_staking(deps: Deps, env: Env) -> StdResult<Option<Staking>> {
+    Ok(STAKING.may_load(deps.storage)?.map(|mut staking| {
+        accrue_rewards(&mut staking, env.block.time);
+        staking
+    }))
+}
+
+fn query_stakes(deps: Deps, env: Env, address: String) -> StdResult<StakesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let Some(mut staking) = query_staking(deps, env)? else {
+        return Ok(StakesResponse {
+            positions: vec![],
+            weight: Uint128::zero(),
+            rewards: Uint128::zero(),
+        });
+    };
+
+    let mut staker = load_staker(deps, &staking, &address)?;
+    settle_rewards(&mut staker, &mut staking)?;
+
+    Ok(StakesResponse {
+        positions: STAKE_POSITIONS
+            .prefix(&address)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?,
+        weight: staker.weight,
+        rewards: staker.pending,
+    })
+}
+
+fn query_vesting(deps: Deps, env: Env, address: String) -> StdResult<VestingResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let grants = VESTING_GRANTS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+
+    let mut response = VestingResponse {
+        locked: Uint128::zero(),
+        claimable: Uint128::zero(),
+        claimed: Uint128::zero(),
+    };
+    for grant in grants {
+        let vested = vested_amount(
+            grant.amount,
+            grant.schedule.start,
+            grant.schedule.cliff,
+            grant.schedule.duration,
+            env.block.time,
+        );
+        response.locked += grant.amount - vested;
+        response.claimable += vested - grant.claimed;
+        response.claimed += grant.claimed;
+    }
+
+    Ok(response)
+}
+
+fn execute_redeem(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let Some(public_mint) = PUBLIC_MINT.may_load(deps.storage)? else {
+        return Err(ContractError::NoReserve);
+    };
+    let reserve = RESERVE.may_load(deps.storage)?.unwrap_or_default();
+
+    let [Coin {
+        denom: paid,
+        amount,
+    }] = info.funds.as_slice()
+    else {
+        return Err(ContractError::InvalidRedeem);
+    };
+    if *paid != denom || amount.is_zero() {
+        return Err(ContractError::InvalidRedeem);
+    }
+
+    // the share is taken of the supply the contract doesn't own, including the redeemed tokens.
+    // stakes, grants and deposits held for holders count towards it. the supply reads as 0 if it
+    // can't be queried, which must fail instead of underflowing
+    let supply = query_bank_supply(deps.as_ref(), denom.clone());
+    let free = free_balance(deps.as_ref(), &env.contract.address, &denom)?;
+    let circulating = Uint128::from(supply)
+        .checked_sub(free)?
+        .checked_add(*amount)?;
+    let payout = reserve.multiply_ratio(*amount, circulating);
+    if payout.is_zero() {
+        return Err(ContractError::RedeemTooSmall);
+    }
+
+    // the redeemed tokens move the bonding curve back down. they stay counted as minted, so they
+    // can't be minted again past the max supply
+    RESERVE.save(deps.storage, &(reserve - payout))?;
+    let redeemed = REDEEMED.may_load(deps.storage)?.unwrap_or_default();
+    REDEEMED.save(deps.storage, &(redeemed + amount.u128()))?;
+
+    let burn_response = execute_burn(deps, env.contract.address, amount)?;
+    Ok(Response::new()
+        .add_submessages(burn_response.messages)
+        .add_events(burn_response.events)
+        .add_attribute("action", "factory_redeem")
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("payout", payout.to_string())
+        .add_message(BankMsg::Send {
+            to_address: info.sender.into_string(),
+            amount: vec![Coin {
+                denom: public_mint.payment_denom,
+                amount: payout,
+            }],
+        }))
+}
+
+fn execute_burn_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let [Coin { denom, amount }] = info.funds.as_slice() else {
+        return Err(ContractError::InvalidBurnFunds);
+    };
+    if *denom != DENOM.load(deps.storage)? || amount.is_zero() {
+        return Err(ContractError::InvalidBurnFunds);
+    }
+
+    // the attached tokens are now held by the contract, which burns them from its own balance
+    Ok(execute_burn(deps, env.contract.address, amount)?.add_attribute("burner", info.sender))
+}
+
+// sends the attached tokens on to the recipient, less the transfer burn and tax. the burn and a tax
+// without a treasury are burned from the contract's balance. transfers from or to an exempt
+// address aren't charged
+fn execute_transfer_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let contract = env.contract.address.as_str();
+    let recipient = validate_address(deps.api, contract, &recipient)?;
+    let denom = DENOM.load(deps.storage)?;
+    let [Coin {
+        denom: sent_denom,
+        amount,
+    }] = info.funds.as_slice()
+    else {
+        return Err(ContractError::InvalidTransferFunds);
+    };
+    if *sent_denom != denom || amount.is_zero() {
+        return Err(ContractError::InvalidTransferFunds);
+    }
+
+    let exempt = TRANSFER_FEE_EXEMPT.has(deps.storage, &info.sender)
+        || TRANSFER_FEE_EXEMPT.has(deps.storage, &recipient);
+    let transfer_burn = TRANSFER_BURN.may_load(deps.storage)?.filter(|_| !exempt);
+    let transfer_tax = TRANSFER_TAX.may_load(deps.storage)?.filter(|_| !exempt);
+    let burn = transfer_burn.map_or(Uint128::zero(), |rate| amount.mul_floor(rate));
+    let tax = transfer_tax
+        .as_ref()
+        .map_or(Uint128::zero(), |transfer_tax| {
+            amount.mul_floor(transfer_tax.rate)
+        });
+    // the rates add up to less than one, so the recipient always receives something
+    let received = *amount - burn - tax;
+
+    let mut response = Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(received.u128(), &denom)],
+        })
+        .add_event(send_event(&denom, recipient.as_str(), received))
+        .add_attribute("action", "factory_transfer_funds")
+        .add_attribute("sender", info.sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", received)
+        .add_attribute("burned", burn)
+        .add_attribute("tax", tax);
+
+    let mut burned = burn;
+    if !burn.is_zero() {
+        TRANSFER_BURNED.update(deps.storage, |total| -> StdResult<_> { Ok(total + burn) })?;
+    }
+    match transfer_tax.and_then(|transfer_tax| transfer_tax.treasury) {
+        Some(treasury) if !tax.is_zero() => {
+            response = response
+                .add_message(BankMsg::Send {
+                    to_address: treasury.to_string(),
+                    amount: vec![Coin::new(tax.u128(), &denom)],
+                })
+                .add_event(send_event(&denom, treasury.as_str(), tax));
+        }
+        _ => burned += tax,
+    }
+
+    if !burned.is_zero() {
+        response = response
+            .add_message(tokenfactory::burn(contract, &denom, burned, contract))
+            .add_event(
+                Event::new("tfa-burn")
+                    .add_attribute("denom", denom)
+                    .add_attribute("amount", burned),
+            );
+    }
+    Ok(response)
+}
+
+fn execute_claim_ownership(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let Some(dead_man_switch) = DEAD_MAN_SWITCH.may_load(deps.storage)? else {
+        return Err(ContractError::NoDeadManSwitch);
+    };
+
+    let claimable_after = LAST_ACTIVITY
+        .load(deps.storage)?
+        .plus_seconds(dead_man_switch.inactivity);
+    if env.block.time < claimable_after {
+        return Err(ContractError::OwnerActive(claimable_after));
+    }
+
+    DEAD_MAN_SWITCH.remove(deps.storage);
+
+    let Some(fallback) = dead_man_switch.fallback else {
+        // without a fallback the token is made immutable, as if the owners had revoked it
+        let revoke_response = execute_revoke(deps, env.contract.address, None)?;
+        return Ok(Response::new()
+            .add_attribute("action", "factory_claim_ownership")
+            .add_attribute("fallback", "")
+            .add_submessages(revoke_response.messages)
+            .add_events(revoke_response.events)
+            .add_attributes(revoke_response.attributes));
+    };
+
+    // the fallback replaces all of the inactive owners
+    for owner in role_members(deps.as_ref(), Role::Owner)? {
+        ROLES.remove(deps.storage, (Role::Owner.as_str(), &owner))?;
+    }
+    ROLES.save(deps.storage, (Role::Owner.as_str(), &fallback), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_claim_ownership")
+        .add_attribute("fallback", fallback))
+}
+
+fn execute_set_paused(deps: DepsMut, paused: bool) -> Result<Response, ContractError> {
+    PAUSED.save(deps.storage, &paused)?;
+
+    let action = if paused {
+        "factory_pause"
+    } else {
+        "factory_unpause"
+    };
+    Ok(Response::new().add_attribute("action", action))
+}
+
+fn execute_set_denom_metadata(
+    deps: DepsMut,
+    contract: Addr,
+    metadata: DenomMetadata,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let symbol = SYMBOL.load(deps.storage)?;
+
+    // later display unit amounts are converted with the published exponent, so once published it
+    // would rescale every balance shown to holders
+    let published = BankQuerier::new(&deps.querier)
+        .denom_metadata(denom.clone())
+        .ok()
+        .and_then(|response| response.metadata)
+        .is_some();
+    if published && metadata.exponent != EXPONENT.load(deps.storage)? {
+        return Err(ContractError::ExponentLocked);
+    }
+    EXPONENT.save(deps.storage, &metadata.exponent)?;
+
+    let name = metadata.name.clone();
+    Ok(Response::new()
+        .add_message(denom_metadata_msg(&contract, denom, symbol, metadata))
+        .add_attribute("action", "factory_set_denom_metadata")
+        .add_attribute("name", name))
+}
+
+// the token's bank metadata, with the symbol as its display unit
+fn denom_metadata_msg(
+    contract: &Addr,
+    denom: String,
+    symbol: String,
+    metadata: DenomMetadata,
+) -> CosmosMsg {
+    MsgSetDenomMetadata {
+        sender: contract.to_string(),
+        metadata: Some(BankMetadata {
+            description: metadata.description,
+            denom_units: vec![
+                DenomUnit {
+                    denom: denom.clone(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: symbol.clone(),
+                    exponent: metadata.exponent,
+                    aliases: vec![],
+                },
+            ],
+            base: denom,
+            display: symbol.clone(),
+            name: metadata.name,
+            symbol,
+            uri: metadata.uri.unwrap_or_default(),
+            uri_hash: String::new(),
+        }),
+    }
+    .into()
+}
+
+fn execute_update_symbol(
+    deps: DepsMut,
+    contract: Addr,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    validate_symbol(&SYMBOL_RULES.load(deps.storage)?, &symbol)?;
+    let old_symbol = SYMBOL.load(deps.storage)?;
+    if symbol == old_symbol || DENOMS.has(deps.storage, &symbol) {
+        return Err(ContractError::DenomExists(symbol));
+    }
+    SYMBOL.save(deps.storage, &symbol)?;
+
+    // the published metadata keeps its name, description and uri. a token without any gets the
+    // symbol as its name
+    let denom = DENOM.load(deps.storage)?;
+    let published = BankQuerier::new(&deps.querier)
+        .denom_metadata(denom.clone())
+        .ok()
+        .and_then(|response| response.metadata);
+    let metadata = DenomMetadata {
+        name: published
+            .as_ref()
+            .map_or_else(|| symbol.clone(), |metadata| metadata.name.clone()),
+        description: published
+            .as_ref()
+            .map(|metadata| metadata.description.clone())
+            .unwrap_or_default(),
+        exponent: EXPONENT.load(deps.storage)?,
+        uri: published
+            .map(|metadata| metadata.uri)
+            .filter(|uri| !uri.is_empty()),
+    };
+
+    Ok(Response::new()
+        .add_message(denom_metadata_msg(
+            &contract,
+            denom.clone(),
+            symbol.clone(),
+            metadata,
+        ))
+        .add_event(
+            Event::new("tfa-symbol")
+                .add_attribute("denom", denom)
+                .add_attribute("old_symbol", &old_symbol)
+                .add_attribute("new_symbol", &symbol),
+        )
+        .add_attribute("action", "factory_update_symbol")
+        .add_attribute("old_symbol", old_symbol)
+        .add_attribute("new_symbol", symbol))
+}
+
+fn execute_set_project_info(
+    deps: DepsMut,
+    project_info: ProjectInfo,
+) -> Result<Response, ContractError> {
+    validate_project_info(&project_info)?;
+    PROJECT_INFO.save(deps.storage, &project_info)?;
+
+    Ok(Response::new().add_attribute("action", "factory_set_project_info"))
+}
+
+fn validate_project_info(project_info: &ProjectInfo) -> Result<(), ContractError> {
+    let fields = [
+        ("description", &project_info.description),
+        ("website", &project_info.website),
+        ("x", &project_info.x),
+        ("telegram", &project_info.telegram),
+        ("logo_uri", &project_info.logo_uri),
+    ];
+    for (name, value) in fields {
+        if value
+            .as_ref()
+            .is_some_and(|value| value.len() > MAX_PROJECT_INFO_LENGTH)
+        {
+            return Err(ContractError::InvalidProjectInfo(
+                name.to_string(),
+                MAX_PROJECT_INFO_LENGTH,
+            ));
+        }
+    }
+    Ok(())
+}
+
+// taker fees for a denom pair are set by chain governance, so the contract submits the proposal itself
+// and records its id to let the launch checklist track it through to execution
+fn execute_propose_taker_fee(
+    deps: DepsMut,
+    contract: Addr,
+    quote_denom: String,
+    taker_fee: Decimal,
+    deposit: Vec<Coin>,
+    title: String,
+    description: String,
+) -> Result<Response, ContractError> {
+    // a vetoed proposal burns the deposit, so it can't come out of what the contract must keep
+    for coin in &deposit {
+        let amount: Uint128 = deposit
+            .iter()
+            .filter(|other| other.denom == coin.denom)
+            .map(|other| other.amount)
+            .sum();
+        if amount > free_balance(deps.as_ref(), &contract, &coin.denom)? {
+            return Err(ContractError::InsufficientBalance(format!(
+                "{amount}{}",
+                coin.denom
+            )));
+        }
+    }
+
+    let denom = DENOM.load(deps.storage)?;
+
+    let content = DenomPairTakerFeeProposal {
+        title,
+        description,
+        denom_pair_taker_fee: vec![DenomPairTakerFee {
+            denom0: denom,
+            denom1: quote_denom.clone(),
+            taker_fee: taker_fee.to_string(),
+        }],
+    }
+    .to_any();
+
+    let msg: CosmosMsg = MsgSubmitProposal {
+        content: Some(content),
+        initial_deposit: deposit
+            .iter()
+            .map(|coin| ProtoCoin {
+                denom: coin.denom.clone(),
+                amount: coin.amount.to_string(),
+            })
+            .collect(),
+        proposer: contract.into_string(),
+    }
+    .into();
+
+    PENDING_TAKER_FEE.save(
+        deps.storage,
+        &TakerFeeProposal {
+            quote_denom: quote_denom.clone(),
+            taker_fee,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(msg, TAKER_FEE_PROPOSAL_REPLY_ID))
+        .add_attribute("action", "factory_propose_taker_fee")
+        .add_attribute("quote_denom", quote_denom)
+        .add_attribute("taker_fee", taker_fee.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(mut deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let response = match msg.id {
+        TAKER_FEE_PROPOSAL_REPLY_ID => reply_taker_fee_proposal(deps.branch(), msg),
+        CREATE_DENOM_REPLY_ID => reply_create_denom(deps.branch(), msg),
+        CREATE_POOL_REPLY_ID => reply_create_pool(deps.branch(), env.clone(), msg),
+        CREATE_POSITION_REPLY_ID => reply_create_position(deps.branch(), msg),
+        BUYBACK_REPLY_ID => reply_buyback(deps.branch(), env.clone(), msg),
+        MULTISIG_REPLY_ID => reply_multisig(deps.branch(), msg),
+        id if id >= CRANK_REPLY_ID_OFFSET => {
+            reply_crank(deps.branch(), env.clone(), id - CRANK_REPLY_ID_OFFSET, msg)
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id {id}")).into()),
+    }?;
+    record_history(deps.storage, &env, &response.events)?;
+    Ok(response)
+}
+
+// pays the crank reward for a cranked action that succeeded. a failed one was reverted along with
+// its removal from the schedule, so it is retried on a later crank. a pause can only delay a
+// committed action, never cancel it
+fn reply_crank(deps: DepsMut, env: Env, id: u64, msg: Reply) -> Result<Response, ContractError> {
+    let SubMsgResult::Err(error) = msg.result else {
+        return crank_reward(deps.as_ref(), &env);
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_crank_failed")
+        .add_attribute("id", id.to_string())
+        .add_attribute("error", error))
+}
+
+// the reward is only paid while the contract can spare it, so it never fails the cranked action
+fn crank_reward(deps: Deps, env: &Env) -> Result<Response, ContractError> {
+    let Some(reward) = CRANK_REWARD.may_load(deps.storage)? else {
+        return Ok(Response::new());
+    };
+    if reward.amount.is_zero()
+        || reward.amount > free_balance(deps, &env.contract.address, &reward.denom)?
+    {
+        return Ok(Response::new());
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: CRANKER.load(deps.storage)?.into_string(),
+            amount: vec![reward.clone()],
+        })
+        .add_attribute("action", "factory_crank_reward")
+        .add_attribute("reward", reward.to_string()))
+}
+
+fn reply_taker_fee_proposal(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let proposal_id = MsgSubmitProposalResponse::try_from(msg.result)?.proposal_id;
+
+    let proposal = PENDING_TAKER_FEE.load(deps.storage)?;
+    PENDING_TAKER_FEE.remove(deps.storage);
+    TAKER_FEE_PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_taker_fee_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+fn reply_create_denom(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let denom = tokenfactory::created_denom(msg.result)?;
+    DENOM.save(deps.storage, &denom)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_denom_created")
+        .add_attribute("denom", denom))
+}
+
+fn reply_multisig(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let multisig = parse_reply_instantiate_data(msg)?.contract_address;
+    let multisig = deps.api.addr_validate(&multisig)?;
+    ROLES.save(deps.storage, (Role::Owner.as_str(), &multisig), &Empty {})?;
+
+    // replaces the instantiate response data, which still names the sender as the admin
+    Ok(Response::new()
+        .set_data(to_json_binary(&InstantiateResponse {
+            denom: DENOM.load(deps.storage)?,
+            symbol: SYMBOL.load(deps.storage)?,
+            admin: multisig.clone(),
+        })?)
+        .add_attribute("action", "factory_multisig")
+        .add_attribute("multisig", multisig))
+}
+
+fn reply_create_pool(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let pool_id = MsgCreateConcentratedPoolResponse::try_from(msg.result)?.pool_id;
+
+    let pending = PENDING_POSITION.load(deps.storage)?;
+    PENDING_POSITION.remove(deps.storage);
+
+    // swaps against the pool are not charged transfer fees, and the launch restrictions don't stop
+    // the contract from seeding it
+    let pool_address = pool_address(&deps.querier, pool_id)?;
+    TRANSFER_FEE_EXEMPT.save(deps.storage, &pool_address, &Empty {})?;
+    if TRADING_START.exists(deps.storage) {
+        ALLOWLIST.save(deps.storage, &pool_address, &Empty {})?;
+    }
+    if MAX_WALLET.exists(deps.storage) {
+        MAX_WALLET_EXEMPT.save(deps.storage, &pool_address, &Empty {})?;
+    }
+
+    CL_POOL.save(
+        deps.storage,
+        &ClPool {
+            pool_id,
+            position_id: None,
+            locked_until: pending.locked_until,
+            created: Some(env.block.time),
+        },
+    )?;
+
+    let position_msg: CosmosMsg = MsgCreatePosition {
+        pool_id,
+        sender: env.contract.address.into_string(),
+        lower_tick: MIN_TICK,
+        upper_tick: MAX_TICK,
+        tokens_provided: pending
+            .tokens
+            .into_iter()
+            .map(|coin| ProtoCoin {
+                denom: coin.denom,
+                amount: coin.amount.to_string(),
+            })
+            .collect(),
+        token_min_amount0: "0".to_string(),
+        token_min_amount1: "0".to_string(),
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            position_msg,
+            CREATE_POSITION_REPLY_ID,
+        ))
+        .add_attribute("action", "factory_cl_pool_created")
+        .add_attribute("pool_id", pool_id.to_string()))
+}
+
+// the address holding a concentrated liquidity pool's liquidity
+fn pool_address(querier: &QuerierWrapper, pool_id: u64) -> Result<Addr, ContractError> {
+    let pool = PoolmanagerQuerier::new(querier)
+        .pool(pool_id)?
+        .pool
+        .ok_or_else(|| StdError::not_found("pool"))?;
+    let pool = ClPoolInfo::try_from(Binary::from(pool.value))?;
+    Ok(Addr::unchecked(pool.address))
+}
+
+fn reply_create_position(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let position_id = MsgCreatePositionResponse::try_from(msg.result)?.position_id;
+
+    CL_POOL.update(deps.storage, |pool| -> StdResult<_> {
+        Ok(ClPool {
+            position_id: Some(position_id),
+            ..pool
+        })
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_cl_position_created")
+        .add_attribute("position_id", position_id.to_string()))
+}
+
+fn reply_buyback(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let bought: Uint128 = MsgSwapExactAmountInResponse::try_from(msg.result)?
+        .token_out_amount
+        .parse()?;
+
+    // everything bought is burned, so the swap can only ever reduce the supply
+    let burn_response = execute_burn(deps, env.contract.address, &bought)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "factory_buyback_burn")
+        .add_attribute("amount", bought)
+        .add_submessages(burn_response.messages)
+        .add_events(burn_response.events))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::BlockBeforeSend { from, to, amount } => {
+            sudo_block_before_send(deps, env, &from, &to, amount)
+        }
+        SudoMsg::TrackBeforeSend { .. } => Ok(Response::new()),
+    }
+}
+
+fn sudo_block_before_send(
+    deps: DepsMut,
+    env: Env,
+    from: &str,
+    to: &str,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    // the contract can always receive, and burns go to the tokenfactory module. the contract's own
+    // sends and mints from the module are checked like any other transfer
+    let contract = env.contract.address.as_str();
+    let module = derive_address(contract, &Sha256::digest(b"tokenfactory")[..20])?;
+    if to == contract || to == module {
+        return Ok(Response::new());
+    }
+
+    // while transfers are charged fees, holders transfer through TransferFunds, which takes them.
+    // the contract's own sends and mints aren't charged, and neither are transfers from or to an
+    // exempt address, like a pool or router
+    if (TRANSFER_BURN.exists(deps.storage) || TRANSFER_TAX.exists(deps.storage))
+        && from != contract
+        && from != module
+        && !TRANSFER_FEE_EXEMPT.has(deps.storage, &Addr::unchecked(from))
+        && !TRANSFER_FEE_EXEMPT.has(deps.storage, &Addr::unchecked(to))
+    {
+        return Err(ContractError::TransferFeesRequired(from.to_string()));
+    }
+
+    // transfers are unrestricted once trading has started (or if there was never a pre-launch phase)
+    if let Some(trading_start) = TRADING_START.may_load(deps.storage)? {
+        if env.block.time < trading_start && !ALLOWLIST.has(deps.storage, &Addr::unchecked(to)) {
+            return Err(ContractError::TransferRestricted(to.to_string()));
+        }
+    }
+
+    // during the launch period no address can end up holding more than the limit
+    if let Some(max_wallet) = MAX_WALLET.may_load(deps.storage)? {
+        if env.block.time < max_wallet.until
+            && !MAX_WALLET_EXEMPT.has(deps.storage, &Addr::unchecked(to))
+        {
+            // compared without adding, so a huge transfer is refused instead of overflowing
+            let balance = deps.querier.query_balance(to, &amount.denom)?.amount;
+            if amount.amount > max_wallet.amount.saturating_sub(balance) {
+                return Err(ContractError::MaxWalletExceeded(
+                    to.to_string(),
+                    max_wallet.amount,
+                    max_wallet.until,
+                ));
+            }
+        }
+    }
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::TokenInfo => to_json_binary(&query_info(deps)?),
+        QueryMsg::ContractInfo => to_json_binary(&query_contract_info(deps, env)?),
+        QueryMsg::DenomPreview {
+            creator,
+            checksum,
+            salt,
+            symbol,
+            subdenom,
+            tfa_prefix,
+        } => {
+            let address = helpers::predict_address(deps.api, &checksum, &creator, &salt)?;
+            to_json_binary(&DenomPreviewResponse {
+                denom: helpers::denom(
+                    address.as_str(),
+                    &symbol,
+                    subdenom.as_deref(),
+                    tfa_prefix.unwrap_or_default(),
+                ),
+                address,
+            })
+        }
+        QueryMsg::Mintable => to_json_binary(&query_mintable(deps, env)?),
+        QueryMsg::CanMint { receivers, bucket } => {
+            to_json_binary(&query_can_mint(deps, env, receivers, bucket)?)
+        }
+        QueryMsg::PendingRevoke => to_json_binary(&PENDING_REVOKE.may_load(deps.storage)?),
+        QueryMsg::ProjectInfo => {
+            to_json_binary(&PROJECT_INFO.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Denoms { start_after, limit } => {
+            to_json_binary(&query_denoms(deps, start_after, limit)?)
+        }
+        QueryMsg::SupportedInterfaces => to_json_binary(&query_supported_interfaces(deps)?),
+        QueryMsg::ClPool => to_json_binary(&CL_POOL.may_load(deps.storage)?),
+        QueryMsg::Price { quote_denom } => to_json_binary(&query_price(deps, env, quote_denom)?),
+        QueryMsg::TakerFeeProposals { start_after, limit } => {
+            to_json_binary(&query_taker_fee_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::Buckets => to_json_binary(&query_buckets(deps)?),
+        QueryMsg::MintFee => to_json_binary(&MINT_FEE.may_load(deps.storage)?),
+        QueryMsg::MintTax => to_json_binary(&MINT_TAX.may_load(deps.storage)?),
+        QueryMsg::NullAddress => to_json_binary(
+            &null_address(deps, env.contract.address.as_str())
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::SendPolicy { start_after, limit } => {
+            to_json_binary(&query_send_policy(deps, start_after, limit)?)
+        }
+        QueryMsg::SendDisabled => to_json_binary(&SEND_DISABLED.exists(deps.storage)),
+        QueryMsg::Clawback => to_json_binary(&CLAWBACK.exists(deps.storage)),
+        QueryMsg::Staking => to_json_binary(&query_staking(deps, env)?),
+        QueryMsg::Governance => to_json_binary(&GOVERNANCE.may_load(deps.storage)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_json_binary(&query_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Vote { id, address } => {
+            to_json_binary(&VOTES.may_load(deps.storage, (id, &deps.api.addr_validate(&address)?))?)
+        }
+        QueryMsg::Stakes { address } => to_json_binary(&query_stakes(deps, env, address)?),
+        QueryMsg::Distributions { start_after, limit } => {
+            to_json_binary(&query_distributions(deps, start_after, limit)?)
+        }
+        QueryMsg::Allowance { spender } => to_json_binary(
+            &ALLOWANCES
+                .may_load(deps.storage, &deps.api.addr_validate(&spender)?)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::Allowances { start_after, limit } => {
+            to_json_binary(&query_allowances(deps, start_after, limit)?)
+        }
+        QueryMsg::Emission => to_json_binary(&query_emission(deps, env)?),
+        QueryMsg::CrankReward => to_json_binary(&CRANK_REWARD.may_load(deps.storage)?),
+        QueryMsg::Stats => to_json_binary(&query_stats(deps)?),
+        QueryMsg::TransferTax => to_json_binary(&TRANSFER_TAX.may_load(deps.storage)?),
+        QueryMsg::TransferFeeExempt { start_after, limit } => {
+            to_json_binary(&query_transfer_fee_exempt(deps, start_after, limit)?)
+        }
+        QueryMsg::History {
+            start_before,
+            limit,
+        } => to_json_binary(&query_history(deps, start_before, limit)?),
+        QueryMsg::DistributionClaimed { id, address } => to_json_binary(
+            &DISTRIBUTION_CLAIMS.has(deps.storage, (id, &deps.api.addr_validate(&address)?)),
+        ),
+        QueryMsg::Vesting { address } => to_json_binary(&query_vesting(deps, env, address)?),
+        QueryMsg::MintLimit => to_json_binary(&MINT_LIMIT.may_load(deps.storage)?),
+        QueryMsg::MintedTo { address } => to_json_binary(
+            &MINTED_TO
+                .may_load(deps.storage, &deps.api.addr_validate(&address)?)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::PublicMint => to_json_binary(&PUBLIC_MINT.may_load(deps.storage)?),
+        QueryMsg::MintCost { amount } => to_json_binary(&query_mint_cost(deps, amount)?),
+        QueryMsg::Reserve => to_json_binary(&Coin {
+            denom: PUBLIC_MINT.load(deps.storage)?.payment_denom,
+            amount: RESERVE.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::ProceedsVesting => to_json_binary(&query_proceeds_vesting(deps, env)?),
+        QueryMsg::Phase => to_json_binary(&query_phase(deps, env)?),
+        QueryMsg::PublicMinted { address } => to_json_binary(
+            &PUBLIC_MINTED
+                .may_load(deps.storage, &deps.api.addr_validate(&address)?)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::Purchases { address } => to_json_binary(&query_purchases(deps, address)?),
+        QueryMsg::DeadManSwitch => to_json_binary(&query_dead_man_switch(deps)?),
+        QueryMsg::Roles => to_json_binary(&query_roles(deps)?),
+        QueryMsg::IbcHooks => to_json_binary(&query_ibc_hooks(deps)?),
+        QueryMsg::IbcHookSender {
+            channel,
+            original_sender,
+        } => to_json_binary(&Addr::unchecked(helpers::ibc_hook_sender(
+            env.contract.address.as_str(),
+            &channel,
+            &original_sender,
+        )?)),
+        QueryMsg::StorageStats => to_json_binary(&query_storage_stats(deps)?),
+        QueryMsg::EventsSchema => to_json_binary(&query_events_schema()),
+        QueryMsg::QueuedActions { start_after, limit } => {
+            to_json_binary(&query_queued_actions(deps, start_after, limit)?)
+        }
+        QueryMsg::MaxWallet { start_after, limit } => {
+            to_json_binary(&query_max_wallet(deps, start_after, limit)?)
+        }
+        QueryMsg::Allowlist { start_after, limit } => {
+            to_json_binary(&query_allowlist(deps, start_after, limit)?)
+        }
+    }
+}
+
+fn query_info(deps: Deps) -> StdResult<crate::msg::TokenInfoResponse> {
+    let symbol = SYMBOL.load(deps.storage)?;
+    let denom = DENOM.load(deps.storage)?;
+    let current_supply = query_bank_supply(deps, denom.clone());
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let minted = TOTAL_MINTED.load(deps.storage)?;
+    // this is redundant. remove it?
+    let burned = minted.saturating_sub(current_supply);
+
+    Ok(crate::msg::TokenInfoResponse {
+        symbol,
+        denom,
+        current_supply: current_supply.into(),
+        max_supply: max_supply.into(),
+        minted: minted.into(),
+        burned: burned.into(),
+        supply_locked: SUPPLY_LOCKED.may_load(deps.storage)?.unwrap_or_default(),
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or_default(),
+        exponent: EXPONENT.load(deps.storage)?,
+        transfer_burn: TRANSFER_BURN.may_load(deps.storage)?,
+        transfer_burned: TRANSFER_BURNED.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+fn query_contract_info(deps: Deps, env: Env) -> StdResult<ContractInfoResponse> {
+    let version = get_contract_version(deps.storage)?;
+    let denom = DENOM.load(deps.storage)?;
+    let admin = TokenfactoryQuerier::new(&deps.querier)
+        .denom_authority_metadata(denom.clone())?
+        .authority_metadata
+        .map(|metadata| metadata.admin)
+        .filter(|admin| !admin.is_empty());
+
+    let features = enabled_features(deps.storage);
+
+    Ok(ContractInfoResponse {
+        contract: version.contract,
+        version: version.version,
+        revoked: admin.as_deref() != Some(env.contract.address.as_str()),
+        denom,
+        symbol: SYMBOL.load(deps.storage)?,
+        admin,
+        owners: role_members(deps, Role::Owner)?,
+        features,
+    })
+}
+
+// whether a feature needing the before-send hook is configured
+fn send_hook_enabled(storage: &dyn Storage) -> bool {
+    TRADING_START.exists(storage)
+        || MAX_WALLET.exists(storage)
+        || TRANSFER_BURN.exists(storage)
+        || TRANSFER_TAX.exists(storage)
+}
+
+// the optional capabilities this deployment has enabled
+fn enabled_features(storage: &dyn Storage) -> Vec<String> {
+    [
+        ("timelock", TIMELOCK.exists(storage)),
+        ("clawback", CLAWBACK.exists(storage)),
+        ("pre_launch", TRADING_START.exists(storage)),
+        (
+            "mint_window",
+            MINT_START.exists(storage) || MINT_END.exists(storage),
+        ),
+        ("mint_fee", MINT_FEE.exists(storage)),
+        ("mint_tax", MINT_TAX.exists(storage)),
+        ("mint_limit", MINT_LIMIT.exists(storage)),
+        ("emission", EMISSION.exists(storage)),
+        ("buckets", !BUCKETS.is_empty(storage)),
+        ("max_wallet", MAX_WALLET.exists(storage)),
+        ("transfer_burn", TRANSFER_BURN.exists(storage)),
+        ("transfer_tax", TRANSFER_TAX.exists(storage)),
+        ("multi_denom", !DENOMS.is_empty(storage)),
+        ("send_disabled", SEND_DISABLED.exists(storage)),
+        ("send_policy", SEND_POLICY.exists(storage)),
+        ("public_mint", PUBLIC_MINT.exists(storage)),
+        ("presale", PRESALE.exists(storage)),
+        ("proceeds_vesting", PROCEEDS_VESTING.exists(storage)),
+        ("dead_man_switch", DEAD_MAN_SWITCH.exists(storage)),
+        ("ibc_hooks", !IBC_HOOKS.is_empty(storage)),
+        ("cl_pool", CL_POOL.exists(storage)),
+        ("staking", STAKING.exists(storage)),
+        ("governance", GOVERNANCE.exists(storage)),
+        ("send_hook", send_hook_enabled(storage)),
+        ("vesting_grants", !VESTING_GRANTS.is_empty(storage)),
+        ("tfa_prefix", TFA_PREFIX.exists(storage)),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(feature, _)| feature.to_string())
+    .collect()
+}
+
+fn query_supported_interfaces(deps: Deps) -> StdResult<SupportedInterfacesResponse> {
+    let mut interfaces = SUPPORTED_INTERFACES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (interface, version) = item?;
+            Ok(SupportedInterface { interface, version })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    interfaces.extend(enabled_features(deps.storage).into_iter().map(|feature| {
+        SupportedInterface {
+            interface: format!("{CONTRACT_NAME}:{feature}"),
+            version: CONTRACT_VERSION.to_string(),
+        }
+    }));
+
+    Ok(SupportedInterfacesResponse { interfaces })
+}
+
+fn query_emission(deps: Deps, env: Env) -> StdResult<Option<EmissionResponse>> {
+    let Some(emission) = EMISSION.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    Ok(Some(EmissionResponse {
+        released: emission_released(&emission, Some(env.block.time)),
+        minted: EMISSION_MINTED.load(deps.storage)?,
+        total: emission_released(&emission, None),
+        emission,
+    }))
+}
+
+fn query_can_mint(
+    deps: Deps,
+    env: Env,
+    receivers: Vec<Receiver>,
+    bucket: Option<String>,
+) -> StdResult<CanMintResponse> {
+    let unit = Uint128::from(10u128).checked_pow(EXPONENT.load(deps.storage)?)?;
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+    let mint_limit = MINT_LIMIT.may_load(deps.storage)?;
+
+    let receivers: Vec<ReceiverCheck> = receivers
+        .into_iter()
+        .map(|receiver| {
+            // same checks as execute_mint, after converting display units
+            let amount = match receiver.display_units {
+                Some(true) => receiver.amount.checked_mul(unit).ok(),
+                _ => Some(receiver.amount),
+            };
+            let valid = amount.is_some_and(|amount| !amount.is_zero())
+                && validate_address(deps.api, env.contract.address.as_str(), &receiver.address)
+                    .is_ok()
+                && mint_limit.is_none_or(|limit| {
+                    let minted_to = MINTED_TO
+                        .may_load(deps.storage, &Addr::unchecked(&receiver.address))
+                        .ok()
+                        .flatten()
+                        .unwrap_or_default();
+                    minted_to.saturating_add(amount.unwrap_or_default()) <= limit
+                });
+            ReceiverCheck {
+                address: receiver.address,
+                amount: amount.unwrap_or_default(),
+                valid,
+            }
+        })
+        .collect();
+
+    let total = receivers
+        .iter()
+        .filter(|receiver| receiver.valid)
+        .fold(Uint128::zero(), |total, receiver| {
+            total.saturating_add(receiver.amount)
+        });
+    let tax = match MINT_TAX.may_load(deps.storage)? {
+        Some(mint_tax) => mint_tax_on(&mint_tax, total),
+        None => Uint128::zero(),
+    };
+    let total_minted = total
+        .saturating_add(tax)
+        .saturating_add(Uint128::from(total_minted));
+    let within_cap = max_supply == 0 || total_minted.u128() <= max_supply;
+
+    // the tax is drawn from the bucket along with the mint
+    let within_bucket = match &bucket {
+        Some(label) => BUCKETS
+            .may_load(deps.storage, label)?
+            .is_some_and(|bucket| bucket.minted + total + tax <= bucket.allocated),
+        None => BUCKETS.is_empty(deps.storage),
+    };
+    let open = !PAUSED.may_load(deps.storage)?.unwrap_or_default()
+        && check_mint_window(deps, env.block.time).is_ok();
+
+    Ok(CanMintResponse {
+        valid: within_cap
+            && within_bucket
+            && open
+            && receivers.iter().all(|receiver| receiver.valid),
+        receivers,
+        total,
+        tax,
+        total_minted,
+        within_cap,
+        within_bucket,
+        open,
+    })
+}
+
+fn query_mintable(deps: Deps, env: Env) -> StdResult<crate::msg::MintableResponse> {
+    let denom = DENOM.load(deps.storage)?;
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+
+    let mut cap_reached = false;
+    let mut revoked = false;
+
+    // check if the max supply has been reached
+    if max_supply != 0 && total_minted >= max_supply {
+        cap_reached = true;
+    }
+
+    // check if the admin has been revoked
+    let admin = TokenfactoryQuerier::new(&deps.querier)
+        .denom_authority_metadata(denom)?
+        .authority_metadata
+        .unwrap()
+        .admin;
+    if admin != env.contract.address.as_str() {
+        revoked = true;
+    }
+
+    Ok(crate::msg::MintableResponse {
+        cap_reached,
+        revoked,
+        window_open: check_mint_window(deps, env.block.time).is_ok(),
+        mint_start: MINT_START.may_load(deps.storage)?,
+        mint_end: MINT_END.may_load(deps.storage)?,
+    })
+}
+
+fn query_proceeds_vesting(deps: Deps, env: Env) -> StdResult<Option<ProceedsVestingResponse>> {
+    let Some(vesting) = PROCEEDS_VESTING.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    let denom = PUBLIC_MINT.load(deps.storage)?.payment_denom;
+    let coin = |amount: Uint128| Coin {
+        denom: denom.clone(),
+        amount,
+    };
+
+    Ok(Some(ProceedsVestingResponse {
+        deposited: coin(VESTING_DEPOSITED.load(deps.storage)?),
+        vested: coin(vested_proceeds(deps, &vesting, env.block.time)?),
+        claimed: coin(VESTING_CLAIMED.load(deps.storage)?),
+        schedule: vesting,
+    }))
+}
+
+fn query_phase(deps: Deps, env: Env) -> StdResult<PhaseResponse> {
+    let presale = PRESALE.may_load(deps.storage)?;
+    let phase = match &presale {
+        Some(presale) if env.block.time < presale.start => "closed",
+        Some(presale) if env.block.time < presale.end => "presale",
+        _ => "public",
+    };
+    let minted = |phase: Phase| -> StdResult<Uint128> {
+        Ok(PHASE_MINTED
+            .may_load(deps.storage, phase.as_str())?
+            .unwrap_or_default())
+    };
+
+    Ok(PhaseResponse {
+        phase: phase.to_string(),
+        presale,
+        presale_minted: minted(Phase::Presale(Decimal::zero()))?,
+        public_minted: minted(Phase::Public)?,
+    })
+}
+
+fn query_purchases(deps: Deps, address: String) -> StdResult<PurchasesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(PurchasesResponse {
+        presale: PRESALE_PURCHASED
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+        public: PUBLIC_MINTED
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+    })
+}
+
+fn query_mint_cost(deps: Deps, amount: Uint128) -> StdResult<MintCostResponse> {
+    let public_mint = PUBLIC_MINT.load(deps.storage)?;
+    let supply = curve_supply(deps.storage)?;
+
+    Ok(MintCostResponse {
+        spot_price: spot_price(&public_mint, supply)?,
+        cost: Coin {
+            amount: mint_cost(&public_mint, supply, amount.u128())?,
+            denom: public_mint.payment_denom,
+        },
+    })
+}
+
+fn query_dead_man_switch(deps: Deps) -> StdResult<Option<DeadManSwitchResponse>> {
+    let Some(DeadManSwitch {
+        inactivity,
+        fallback,
+    }) = DEAD_MAN_SWITCH.may_load(deps.storage)?
+    else {
+        return Ok(None);
+    };
+    let last_activity = LAST_ACTIVITY.load(deps.storage)?;
+
+    Ok(Some(DeadManSwitchResponse {
+        inactivity,
+        fallback,
+        last_activity,
+        claimable_after: last_activity.plus_seconds(inactivity),
+    }))
+}
+
+fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    Ok(RolesResponse {
+        owners: role_members(deps, Role::Owner)?,
+        minters: role_members(deps, Role::Minter)?,
+        pausers: role_members(deps, Role::Pauser)?,
+        metadata_managers: role_members(deps, Role::MetadataManager)?,
+    })
+}
+
+fn query_buckets(deps: Deps) -> StdResult<BucketsResponse> {
+    let max_supply = MAX_SUPPLY.load(deps.storage)?;
+    let total_minted = TOTAL_MINTED.load(deps.storage)?;
+
+    let buckets = BUCKETS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (label, bucket) = item?;
+            Ok(BucketInfo {
+                label,
+                allocated: bucket.allocated,
+                minted: bucket.minted,
+                remaining: bucket.allocated - bucket.minted,
+                minters: bucket.minters,
+            })
+        })
+        .collect::<StdResult<Vec<BucketInfo>>>()?;
+
+    let unallocated = if max_supply == 0 {
+        None
+    } else {
+        let reserved = bucket_reserved(deps)?;
+        Some(Uint128::from(
+            max_supply.saturating_sub(total_minted + reserved),
+        ))
+    };
+
+    Ok(BucketsResponse {
+        buckets,
+        unallocated,
+    })
+}
+
+fn query_queued_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, QueuedAction)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    QUEUED_ACTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_distributions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Distribution)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+
+    DISTRIBUTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_allowances(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Allowance)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    ALLOWANCES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_history(
+    deps: Deps,
+    start_before: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, HistoryEntry)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let end = start_before.map(Bound::exclusive);
+
+    HISTORY
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit as usize)
+        .collect()
+}
+
+fn query_taker_fee_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<TakerFeeProposalResponse>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.map(Bound::exclusive);
+    let gov = GovQuerier::new(&deps.querier);
+
+    TAKER_FEE_PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (proposal_id, proposal) = item?;
+            // the proposal may have been pruned, or gov queries may not be available on this chain
+            let status = gov
+                .proposal(proposal_id)
+                .ok()
+                .and_then(|res| res.proposal)
+                .and_then(|proposal| ProposalStatus::try_from(proposal.status).ok())
+                .map_or("unknown".to_string(), |status| {
+                    status.as_str_name().to_string()
+                });
+
+            Ok(TakerFeeProposalResponse {
+                proposal_id,
+                quote_denom: proposal.quote_denom,
+                taker_fee: proposal.taker_fee,
+                status,
+            })
+        })
+        .collect()
+}
+
+fn query_ibc_hooks(deps: Deps) -> StdResult<Vec<(String, Vec<String>)>> {
+    IBC_HOOKS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+fn query_stats(deps: Deps) -> StdResult<Stats> {
+    // the mint aggregates are kept by each successful mint operation, and every address minted to
+    // has an entry in MINTED_TO
+    let recipients = ENTRY_COUNTS
+        .may_load(deps.storage, MINTED_TO.name())?
+        .unwrap_or_default();
+    Ok(Stats {
+        recipients,
+        ..STATS.may_load(deps.storage)?.unwrap_or_default()
+    })
+}
+
+fn query_storage_stats(deps: Deps) -> StdResult<StorageStatsResponse> {
+    let maps = [
+        ROLES.name(),
+        ALLOWLIST.name(),
+        DENOMS.name(),
+        SEND_DESTINATIONS.name(),
+        DENOM_MINTERS.name(),
+        MAX_WALLET_EXEMPT.name(),
+        TRANSFER_FEE_EXEMPT.name(),
+        BUCKETS.name(),
+        PUBLIC_MINTED.name(),
+        MINTED_TO.name(),
+        VESTING_GRANTS.name(),
+        DISTRIBUTIONS.name(),
+        DISTRIBUTION_CLAIMS.name(),
+        ALLOWANCES.name(),
+        HISTORY.name(),
+        STAKERS.name(),
+        STAKE_POSITIONS.name(),
+        PROPOSALS.name(),
+        VOTES.name(),
+        PRESALE_ALLOWLIST.name(),
+        PRESALE_PURCHASED.name(),
+        PHASE_MINTED.name(),
+        QUEUED_ACTIONS.name(),
+        IBC_HOOKS.name(),
+        TAKER_FEE_PROPOSALS.name(),
+    ];
+
+    Ok(StorageStatsResponse {
+        maps: maps
+            .into_iter()
+            .map(|name| {
+                let entries = ENTRY_COUNTS.may_load(deps.storage, name)?;
+                Ok((name.to_string(), entries.unwrap_or_default()))
+            })
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+fn query_events_schema() -> EventsSchemaResponse {
+    EventsSchemaResponse {
+        version: CONTRACT_VERSION.to_string(),
+        events: EVENTS
+            .iter()
+            .map(|(action, attributes)| EventSchema {
+                event_type: "wasm".to_string(),
+                action: action.to_string(),
+                attributes: attributes.iter().map(|key| key.to_string()).collect(),
+            })
+            .chain(
+                TYPED_EVENTS
+                    .iter()
+                    .map(|(event_type, attributes)| EventSchema {
+                        event_type: event_type.to_string(),
+                        action: String::new(),
+                        attributes: attributes.iter().map(|key| key.to_string()).collect(),
+                    }),
+            )
+            .collect(),
+    }
+}
+
+fn query_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllowlistResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let addresses = ALLOWLIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(AllowlistResponse {
+        trading_start: TRADING_START.may_load(deps.storage)?,
+        addresses,
+    })
+}
+
+fn query_denoms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DenomsResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let denoms = DENOMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (symbol, managed) = item?;
+            let minters = DENOM_MINTERS
+                .prefix(&symbol)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<Addr>>>()?;
+            Ok(DenomInfo {
+                symbol,
+                denom: managed.denom,
+                max_supply: managed.max_supply,
+                minted: managed.minted,
+                burned: managed.burned,
+                minters,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(DenomsResponse { denoms })
+}
+
+fn query_send_policy(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SendPolicyResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let destinations = SEND_DESTINATIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(SendPolicyResponse {
+        enabled: SEND_POLICY.exists(deps.storage),
+        destinations,
+    })
+}
+
+fn query_max_wallet(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MaxWalletResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let exempt = MAX_WALLET_EXEMPT
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(MaxWalletResponse {
+        max_wallet: MAX_WALLET.may_load(deps.storage)?,
+        exempt,
+    })
+}
+
+fn query_transfer_fee_exempt(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    TRANSFER_FEE_EXEMPT
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
 }
 
 fn query_bank_supply(deps: Deps, denom: String) -> u128 {
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, OverflowError, StdError, Timestamp, Uint128};
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,18 +7,388 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("Not authorized to perform this action")]
     Unauthorized,
 
+    #[error("The last owner cannot be removed")]
+    LastOwner,
+
+    #[error("Minting and transfers are paused")]
+    Paused,
+
+    #[error("Exactly {} must be attached to mint", .0)]
+    MintFunds(String),
+
+    #[error("Bonding curve payments are kept as a reserve and can't be sent to a treasury")]
+    ReserveTreasury,
+
+    #[error("Invalid bonding curve")]
+    InvalidCurve,
+
+    #[error("Only the token can be attached to redeem")]
+    InvalidRedeem,
+
+    #[error("Only the token can be attached to be burned")]
+    InvalidBurnFunds,
+
+    #[error("There is no reserve to redeem against")]
+    NoReserve,
+
+    #[error("Too few tokens were attached to redeem any of the reserve")]
+    RedeemTooSmall,
+
+    #[error("Invalid merkle root")]
+    InvalidMerkleRoot,
+
+    #[error("A merkle proof is required to mint")]
+    MerkleProofRequired,
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[error("Proceeds vesting requires a public mint treasury, a share of at most 1 and a cliff within the duration")]
+    InvalidVesting,
+
+    #[error("A vesting schedule's cliff must be within its duration")]
+    InvalidSchedule,
+
+    #[error("Symbol must be between {} and {} characters", .0, .1)]
+    SymbolLength(u32, u32),
+
+    #[error("Symbol contains the invalid character {:?}", .0)]
+    SymbolCharacter(char),
+
+    #[error("Invalid symbol rules")]
+    InvalidSymbolRules,
+
+    #[error("A denom creation fee of {} must be attached", .0)]
+    CreationFee(String),
+
+    #[error("A listing fee of {} must be attached", .0)]
+    ListingFee(String),
+
+    #[error("Invalid subdenom {}", .0)]
+    InvalidSubdenom(String),
+
+    #[error("The mint window must end after it starts")]
+    InvalidMintWindow,
+
+    #[error("Minting opens at {}", .0)]
+    MintNotStarted(Timestamp),
+
+    #[error("Minting closed at {}", .0)]
+    MintEnded(Timestamp),
+
+    #[error("A presale requires the public mint and must end after it starts")]
+    InvalidPresale,
+
+    #[error("The public mint opens at {}", .0)]
+    PublicMintClosed(Timestamp),
+
+    #[error("Only allowlisted addresses can buy during the presale")]
+    PresaleRestricted,
+
+    #[error("The {} phase is sold out", .0)]
+    PhaseCap(String),
+
+    #[error("Public mint limit of {} per address exceeded", .0)]
+    PublicMintLimit(Uint128),
+
+    #[error("Purchases are limited to {} until {}", .0, .1)]
+    AntiSnipeLimit(Uint128, Timestamp),
+
+    #[error("The mint tax rate must be above 0 and below 1")]
+    InvalidMintTax,
+
+    #[error("Minting to {} would exceed the per-address mint limit", .0)]
+    MintLimit(String),
+
+    #[error("An emission needs an initial reward, a halving interval and at least one epoch")]
+    InvalidEmission,
+
+    #[error("No emission is configured for this token")]
+    NoEmission,
+
+    #[error("Nothing has been released since the emission was last minted")]
+    NothingReleased,
+
     #[error("Cannot reduce max supply below current supply")]
     CurrentSupply,
 
     #[error("Cannot mint more than max supply")]
     SupplyCap,
 
+    #[error("Max supply is locked and can no longer be changed")]
+    SupplyLocked,
+
+    #[error("Supply must be capped for this action")]
+    UncappedSupply,
+
+    #[error("Allocation bucket {} not found", .0)]
+    BucketNotFound(String),
+
+    #[error("Allocation bucket {} exceeded", .0)]
+    BucketExceeded(String),
+
+    #[error("Mints must draw from an allocation bucket")]
+    BucketRequired,
+
     #[error("Invalid transfer message at index {}", .0)]
     TransferInvalid(usize),
 
     #[error("Invalid mint message at index {}", .0)]
     MintInvalid(usize),
+
+    #[error("Token admin must be transferred away from this contract")]
+    InvalidRevokeTarget,
+
+    #[error("Could not derive an address from {}", .0)]
+    AddressDerivation(String),
+
+    #[error("A revoke is already scheduled")]
+    RevokeScheduled,
+
+    #[error("No revoke is scheduled")]
+    RevokeNotScheduled,
+
+    #[error("Scheduled revoke cannot be confirmed before {}", .0)]
+    RevokeTimelocked(Timestamp),
+
+    #[error("Clawback is not enabled for this token")]
+    ClawbackDisabled,
+
+    #[error("Clawback requires a timelock")]
+    ClawbackTimelock,
+
+    #[error("A clawback must move a non-zero amount between two different addresses, not from the contract")]
+    InvalidClawback,
+
+    #[error("This action is timelocked and must be queued")]
+    TimelockRequired,
+
+    #[error("No timelock is configured for this token")]
+    NoTimelock,
+
+    #[error("This action cannot be queued")]
+    InvalidQueuedAction,
+
+    #[error("The contract holds none of the token")]
+    EmptyBalance,
+
+    #[error("The token itself cannot be rescued")]
+    RescueToken,
+
+    #[error("The contract holds no rescuable {}", .0)]
+    NothingToRescue(String),
+
+    #[error("The contract holds less than {} it can spend", .0)]
+    InsufficientBalance(String),
+
+    #[error("Sender is not the ibc-hooks address for {}", .0)]
+    IbcHookSender(String),
+
+    #[error("IBC hook mints are not accepted from {}", .0)]
+    IbcHookNotAllowed(String),
+
+    #[error("A pool has already been created for this token")]
+    PoolExists,
+
+    #[error("Pool amounts must be non-zero, and the quote must be another denom")]
+    InvalidPool,
+
+    #[error("The contract has no position to withdraw")]
+    NoPosition,
+
+    #[error("The position is locked until {}", .0)]
+    PositionLocked(Timestamp),
+
+    #[error("The position is locked permanently")]
+    PositionLockedForever,
+
+    #[error("A buyback needs a non-zero offer of another denom, a minimum amount out and a pool")]
+    InvalidBuyback,
+
+    #[error("No allowance for this spender")]
+    NoAllowance,
+
+    #[error("The allowance expired at {}", .0)]
+    AllowanceExpired(Timestamp),
+
+    #[error("Batches cannot be nested or have funds attached")]
+    InvalidMulti,
+
+    #[error("A distribution needs attached rewards, a valid merkle root, a non-zero total and a future expiry")]
+    InvalidDistribution,
+
+    #[error("Distribution {} not found", .0)]
+    DistributionNotFound(u64),
+
+    #[error("Already claimed from this distribution")]
+    DistributionClaimed,
+
+    #[error("The distribution expired at {}", .0)]
+    DistributionExpired(Timestamp),
+
+    #[error("The distribution can't be swept before {}", .0)]
+    DistributionActive(Timestamp),
+
+    #[error("Staking is not enabled for this token")]
+    StakingDisabled,
+
+    #[error("Staking needs lock tiers with non-zero multipliers, and its reward denom can't change while rewards are owed")]
+    InvalidStaking,
+
+    #[error("Only the token can be staked, for one of the configured durations")]
+    InvalidStake,
+
+    #[error("Only the reward denom can fund staking")]
+    InvalidStakingFunds,
+
+    #[error("Stake {} not found", .0)]
+    StakeNotFound(u64),
+
+    #[error("The stake is locked until {}", .0)]
+    StakeLocked(Timestamp),
+
+    #[error("Governance is not enabled for this token")]
+    GovernanceDisabled,
+
+    #[error("Governance needs a voting period of at least the timelock, an execution period, and a quorum and threshold of at most 1")]
+    InvalidGovernance,
+
+    #[error("Proposals need at least one action, and can only execute supply, revoke, treasury and pause actions")]
+    InvalidProposal,
+
+    #[error("Proposals can have at most {} actions, a title of {} characters and a description of {}", .0, .1, .2)]
+    ProposalTooLong(usize, usize, usize),
+
+    #[error("No stakes locked until voting closes")]
+    NoVotingPower,
+
+    #[error("Proposal {} not found", .0)]
+    ProposalNotFound(u64),
+
+    #[error("Voting closed at {}", .0)]
+    VotingClosed(Timestamp),
+
+    #[error("Voting is open until {}", .0)]
+    VotingOpen(Timestamp),
+
+    #[error("Already voted on this proposal")]
+    AlreadyVoted,
+
+    #[error("Proposals need a deposit of exactly {}", .0)]
+    ProposalDeposit(Coin),
+
+    #[error("The proposal has no deposit left to settle")]
+    NoProposalDeposit,
+
+    #[error("The proposal did not pass, was already executed or has expired")]
+    ProposalNotPassed,
+
+    #[error(
+        "Only Mint, Send, UpdateSupply and Revoke can be scheduled, at least the timelock from now"
+    )]
+    InvalidScheduledAction,
+
+    #[error("Scheduled actions are commitments and can't be cancelled")]
+    CommittedAction,
+
+    #[error("Scheduled actions must be executed before the contract can be renounced")]
+    ScheduledActionsPending,
+
+    #[error("Distributions must be swept before the contract can be renounced")]
+    DistributionsOpen,
+
+    #[error("No queued actions are due")]
+    NothingToCrank,
+
+    #[error("Queued action {} not found", .0)]
+    QueuedActionNotFound(u64),
+
+    #[error("Queued action cannot be executed before {}", .0)]
+    QueuedActionTimelocked(Timestamp),
+
+    #[error("No dead man switch is configured for this token")]
+    NoDeadManSwitch,
+
+    #[error("Ownership cannot be claimed before {}", .0)]
+    OwnerActive(Timestamp),
+
+    #[error("Pre-launch allowlist is not enabled for this token")]
+    NoPreLaunch,
+
+    #[error("{} cannot receive the token until trading starts", .0)]
+    TransferRestricted(String),
+
+    #[error("Receiver {} has the address {} from another chain, expected the {} prefix", .0, .1, .2)]
+    AddressPrefix(usize, String, String),
+
+    #[error("The address {} is from another chain, expected the {} prefix", .0, .1)]
+    ForeignAddress(String, String),
+
+    #[error(
+        "Invalid multisig, it needs voters with a weight and a reachable threshold, and no admin"
+    )]
+    InvalidMultisig,
+
+    #[error("{} is not an allowed destination under the send policy", .0)]
+    SendDestination(String),
+
+    #[error("Sending the token held by the contract has been disabled")]
+    SendDisabled,
+
+    #[error("A denom with the symbol {} already exists", .0)]
+    DenomExists(String),
+
+    #[error("The exponent can't be changed once the denom metadata is published")]
+    ExponentLocked,
+
+    #[error("No denom with the symbol {} was created by this contract", .0)]
+    UnknownDenom(String),
+
+    #[error("Minting would exceed the max supply of {}", .0)]
+    DenomSupplyExceeded(String),
+
+    #[error("Project {} is longer than {} characters", .0, .1)]
+    InvalidProjectInfo(String, usize),
+
+    #[error("A gauge needs a pool, an amount and at least one epoch")]
+    InvalidGauge,
+
+    #[error("The transfer burn must be a share between 0 and 1")]
+    InvalidTransferBurn,
+
+    #[error("Transfer burn is not enabled for this token")]
+    NoTransferBurn,
+
+    #[error("The transfer tax rate must be above 0, and below 1 together with the transfer burn")]
+    InvalidTransferTax,
+
+    #[error("The transfer tax has been renounced")]
+    TransferTaxRenounced,
+
+    #[error("Transfers are charged fees, {} has to send the token with TransferFunds", .0)]
+    TransferFeesRequired(String),
+
+    #[error("Only the token can be attached to be transferred")]
+    InvalidTransferFunds,
+
+    #[error("Max-wallet limit must be above zero")]
+    InvalidMaxWallet,
+
+    #[error("Max-wallet limit is not enabled for this token")]
+    NoMaxWallet,
+
+    #[error("{} cannot hold more than {} until {}", .0, .1, .2)]
+    MaxWalletExceeded(String, Uint128, Timestamp),
 }
@@ -0,0 +1,61 @@
+use bech32::{decode, encode, Bech32};
+use cosmwasm_std::{instantiate2_address, Addr, Api, StdError, StdResult};
+use sha2::{Digest, Sha256};
+
+// the module name ibc-hooks derives the addresses it executes contracts from with
+const IBC_HOOK_INTERMEDIARY: &str = "ibc-wasm-hook-intermediary";
+
+// the subdenom a token is created with. unless one is given it is the symbol, under the custom
+// subspace 'tfa/' if the token opted in to identifying it as created by this contract
+pub fn subdenom(symbol: &str, subdenom: Option<&str>, tfa_prefix: bool) -> String {
+    match subdenom {
+        Some(subdenom) => subdenom.to_string(),
+        None if tfa_prefix => format!("tfa/{}", symbol),
+        None => symbol.to_string(),
+    }
+}
+
+// the tokenfactory denom a factory at `contract` creates, in the format
+// "factory/{creator_address}/{subdenom}"
+pub fn denom(contract: &str, symbol: &str, subdenom: Option<&str>, tfa_prefix: bool) -> String {
+    format!(
+        "factory/{}/{}",
+        contract,
+        self::subdenom(symbol, subdenom, tfa_prefix)
+    )
+}
+
+// the address a factory will have when deployed by `creator` with instantiate2
+pub fn predict_address(
+    api: &dyn Api,
+    checksum: &[u8],
+    creator: &str,
+    salt: &[u8],
+) -> StdResult<Addr> {
+    let canonical = instantiate2_address(checksum, &api.addr_canonicalize(creator)?, salt)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    api.addr_humanize(&canonical)
+}
+
+// the address ibc-hooks executes `contract` from for `original_sender` on the other end of
+// `channel`. it is derived like a module address, so a remote sender can't act as a local account
+pub fn ibc_hook_sender(contract: &str, channel: &str, original_sender: &str) -> StdResult<String> {
+    let (hrp, _) = decode(contract).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let hash = Sha256::new()
+        .chain_update(Sha256::digest(IBC_HOOK_INTERMEDIARY))
+        .chain_update(format!("{}/{}", channel, original_sender))
+        .finalize();
+    encode::<Bech32>(hrp, &hash).map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+// the address the ics-20 transfer module escrows tokens sent out over `channel` in. while a transfer
+// fee is set, it has to be exempt or ibc transfers of the token are rejected
+pub fn ibc_escrow_address(contract: &str, port: &str, channel: &str) -> StdResult<String> {
+    let (hrp, _) = decode(contract).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let hash = Sha256::new()
+        .chain_update("ics20-1")
+        .chain_update([0])
+        .chain_update(format!("{}/{}", port, channel))
+        .finalize();
+    encode::<Bech32>(hrp, &hash[..20]).map_err(|err| StdError::generic_err(err.to_string()))
+}
@@ -1,7 +1,9 @@
 pub mod contract;
 pub mod error;
+pub mod helpers;
 pub mod msg;
 pub mod state;
+pub mod tokenfactory;
 
 #[cfg(test)]
 pub mod tests;
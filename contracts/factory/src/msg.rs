@@ -1,32 +1,578 @@
+use crate::state::{
+    Allowance, ClPool, DeadManSwitch, Distribution, Emission, Governance, HistoryEntry, LockTier,
+    MaxWallet, MintFee, MintTax, PendingRevoke, Presale, ProceedsVesting, ProjectInfo, Proposal,
+    PublicMint, QueuedAction, Role, StakePosition, Staking, Stats, SymbolRules, TransferTax,
+    VestingSchedule,
+};
+use assetlist::state::Metadata;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Decimal256, HexBinary, Timestamp, Uint128};
+use cw_utils::{Duration, Threshold};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub symbol: String,
+    // The exact tokenfactory subdenom, e.g. "test" for factory/{contract}/test. Defaults to the
+    // symbol, or "tfa/{symbol}" with the tfa prefix
+    pub subdenom: Option<String>,
+    // Opts in to the "tfa/" prefix on the default subdenom, marking the token as created by this
+    // contract
+    pub tfa_prefix: Option<bool>,
+    // The length bounds and characters accepted in the symbol, for deployments with other denom
+    // rules. Defaults to 2-16 letters and digits
+    pub symbol_rules: Option<SymbolRules>,
     pub initial_supply: Option<Uint128>,
     pub max_supply: Option<Uint128>,
+    // The initial owner. Defaults to the sender
     pub admin: Option<Addr>,
+    pub pre_launch: Option<PreLaunch>,
+    // Delay in seconds that privileged actions (mint, send, supply, exemption and admin changes) must
+    // be queued for
+    pub timelock: Option<u64>,
+    // Minting, by the owners or through the public mint, is only possible from mint_start until
+    // mint_end. After mint_end the supply is final
+    pub mint_start: Option<Timestamp>,
+    pub mint_end: Option<Timestamp>,
+    // Protocol fee charged on every mint by an address other than an owner, forwarded to the collector
+    pub mint_fee: Option<MintFee>,
+    // Exponent of the display unit, used to convert amounts given in whole tokens. Defaults to 6
+    pub exponent: Option<u32>,
+    // Lets anyone call Mint by paying a fixed price per token
+    pub public_mint: Option<PublicMint>,
+    // Vests a share of the public mint proceeds to the team instead of sending it to the treasury
+    pub proceeds_vesting: Option<ProceedsVesting>,
+    // An allowlisted phase with its own price before the public mint opens to everyone
+    pub presale: Option<PresaleMsg>,
+    // Lets a fallback take over the token if the owners perform no action for a period of time
+    pub dead_man_switch: Option<DeadManSwitch>,
+    // Lists the token in an assetlist contract once it is created, paying the listing fee from the
+    // attached funds
+    pub assetlist: Option<AssetlistRegistration>,
+    // Lets the owners move tokens out of any account with Clawback. Requires a timelock, so every
+    // clawback is queued publicly first. Can't be enabled later. Defaults to false
+    pub clawback: Option<bool>,
+    // The address Revoke hands the token admin to. Defaults to the all-zero 20 byte address with the
+    // contract's bech32 prefix
+    pub null_address: Option<String>,
+    // Caps how much a single address can hold during the launch period
+    pub max_wallet: Option<MaxWalletMsg>,
+    // Burns a share of every transfer, taken out of what the recipient receives. The before-send
+    // hook can only reject a bank send, not burn part of it, so every bank send between two
+    // addresses that aren't exempt is rejected and holders transfer through TransferFunds, which
+    // burns it. That includes ibc transfers and pool swaps, so the pools, routers and ibc escrows
+    // the token moves through have to be exempted along with it
+    pub transfer_burn: Option<TransferBurnMsg>,
+    // Links and a description of the project, shown by wallets and aggregators
+    pub project_info: Option<ProjectInfo>,
+    // Instantiates a cw3-fixed-multisig and makes it the only owner. Can't be combined with admin
+    pub multisig: Option<MultisigMsg>,
+}
+
+#[cw_serde]
+pub struct PreLaunch {
+    // Until this time only allowlisted addresses can receive the token
+    pub trading_start: Timestamp,
+    // Addresses allowed to receive the token before trading starts (team, market maker, presale contract)
+    pub allowlist: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MaxWalletMsg {
+    pub max_wallet: MaxWallet,
+    // Addresses that can hold more than the limit, like pools. The contract is always exempt
+    pub exempt: Vec<String>,
+}
+
+#[cw_serde]
+pub struct TransferBurnMsg {
+    // The share of each transfer that is burned
+    pub rate: Decimal,
+    // Addresses added to the transfer fee exemptions, like pools, routers and the ibc escrow of each
+    // channel (see helpers::ibc_escrow_address). Direct transfers from or to them aren't burned
+    pub exempt: Vec<String>,
+}
+
+#[cw_serde]
+pub struct TransferTaxMsg {
+    pub transfer_tax: TransferTax,
+    // Addresses added to the transfer fee exemptions, like pools, routers and the ibc escrow of each
+    // channel (see helpers::ibc_escrow_address). Direct transfers from or to them aren't taxed
+    pub exempt: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MultisigMsg {
+    // The code id of a stored cw3-fixed-multisig contract
+    pub code_id: u64,
+    pub voters: Vec<Voter>,
+    pub threshold: Threshold,
+    pub max_voting_period: Duration,
+}
+
+#[cw_serde]
+pub struct Voter {
+    pub addr: String,
+    pub weight: u64,
+}
+
+// The instantiate message of cw3-fixed-multisig
+#[cw_serde]
+pub struct MultisigInstantiateMsg {
+    pub voters: Vec<Voter>,
+    pub threshold: Threshold,
+    pub max_voting_period: Duration,
+}
+
+#[cw_serde]
+pub struct PresaleMsg {
+    pub presale: Presale,
+    // Addresses that can buy during the presale
+    pub allowlist: Vec<String>,
+}
+
+// Set as the instantiate response data, so contracts instantiating the factory don't have to
+// reconstruct the denom
+#[cw_serde]
+pub struct InstantiateResponse {
+    pub denom: String,
+    pub symbol: String,
+    pub admin: Addr,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    // Mints tokens to a recipient account(s)
+    // Mints tokens to a recipient account(s). When the public mint is enabled anyone can call this,
+    // attaching the price of the total amount
     Mint(Vec<Receiver>),
+    // Public mint for an address in the merkle allowlist. `cap` is the leaf's purchase cap and
+    // `proof` the hex encoded sibling hashes from the leaf up to the root
+    MintWithProof {
+        receivers: Vec<Receiver>,
+        cap: Uint128,
+        proof: Vec<String>,
+    },
+    // Mints tokens to a recipient account(s), drawing from a labeled allocation bucket
+    MintFromBucket {
+        bucket: String,
+        receivers: Vec<Receiver>,
+    },
+    // Mints tokens and executes `msg` on a contract with them attached, so it can react to receiving them
+    MintTo {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    // Mints tokens to the contract that vest to the receiver on the schedule, instead of sending them
+    MintVested {
+        receiver: String,
+        amount: Uint128,
+        schedule: VestingSchedule,
+    },
+    // Pays out the sender's vested tokens from MintVested grants
+    Claim,
+    // Force transfers tokens from an account, such as a compromised or sanctioned allocation. Only
+    // available if the token opted in at instantiate. It can't move the contract's own tokens
+    Clawback {
+        from: String,
+        to: String,
+        amount: Uint128,
+        reason: String,
+    },
+    // Distributes the attached coins to holders pro rata to their balances at a snapshot, given as
+    // a merkle root of sha256(address + balance) leaves
+    CreateDistribution {
+        merkle_root: String,
+        snapshot_height: u64,
+        total: Uint128,
+        expiry: Timestamp,
+    },
+    // Pays out the sender's share of a distribution. Can be called by any holder in the snapshot
+    ClaimDistribution {
+        id: u64,
+        balance: Uint128,
+        proof: Vec<String>,
+    },
+    // Sends the rewards left unclaimed in an expired distribution to `to`, and removes it
+    SweepDistribution {
+        id: u64,
+        to: String,
+    },
+    // Enables staking, or updates its reward rate and lock tiers. The reward denom can't change while
+    // rewards are funded or owed
+    ConfigureStaking {
+        reward_denom: String,
+        reward_rate: Uint128,
+        tiers: Vec<LockTier>,
+    },
+    // Adds the attached reward denom to the rewards emitted to stakers
+    FundStaking,
+    // Locks the attached tokens for one of the configured durations
+    Stake {
+        duration: u64,
+    },
+    // Returns an unlocked stake to its owner
+    Unstake {
+        id: u64,
+    },
+    // Pays out the sender's staking rewards
+    ClaimRewards,
+    // Enables governance by stakers, or updates its parameters. None disables it
+    ConfigureGovernance(Option<Governance>),
+    // Proposes actions for stakers to vote on. Only supply, revoke, treasury and pause actions can
+    // be proposed, up to 16 of them. Must be sent by a staker
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<ExecuteMsg>,
+    },
+    // Votes with the weight of the sender's stakes that stay locked until voting closes
+    Vote {
+        id: u64,
+        approve: bool,
+    },
+    // Executes a passed proposal once voting has closed, before it expires. Can be called by anyone
+    ExecuteProposal {
+        id: u64,
+    },
+    // Refunds a proposal's deposit to the proposer if it reached the quorum, or burns it, once voting
+    // has closed, even if governance has since been disabled. Executing a proposal settles its
+    // deposit too. Can be called by anyone
+    SettleDeposit {
+        id: u64,
+    },
+    // Lets `spender` pull up to `amount` more of the contract's tokens with TransferFrom. Replaces
+    // the expiry, if one is given
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Timestamp>,
+    },
+    // Lowers a spender's allowance, removing it once it reaches zero
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Timestamp>,
+    },
+    // Sends tokens from the contract to `recipient` out of the sender's allowance
+    TransferFrom {
+        recipient: String,
+        amount: Uint128,
+    },
+    // Public mint paid with tokens received through an ibc-hooks transfer. The channel and original
+    // sender must derive the address the hook executes from, and be allowed with UpdateIbcHooks
+    IbcMint {
+        channel: String,
+        original_sender: String,
+        receivers: Vec<Receiver>,
+    },
+    // Accepts ibc-hooks mints over a channel from the given remote senders, or from any sender when
+    // the list is empty. None stops accepting them over the channel
+    UpdateIbcHooks {
+        channel: String,
+        senders: Option<Vec<String>>,
+    },
+    // Creates or resizes an allocation bucket reserving part of the un-minted supply.
+    // Once any bucket exists, all mints must draw from a bucket
+    SetBucket {
+        label: String,
+        amount: Uint128,
+    },
+    // Sets the addresses that can mint from a bucket in addition to the admin.
+    // They can only draw from that bucket and have no other rights
+    SetBucketMinters {
+        label: String,
+        minters: Vec<String>,
+    },
     // Transfers tokens from the contract to a recipient account(s)
     Send(Vec<Receiver>),
-    // Burns tokens held by the contract
-    Burn(Uint128),
+    // Transfers tokens from the contract by executing `msg` on a contract with them attached
+    SendAndCall {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    // Transfers the contract's entire balance of the token to an address
+    Sweep {
+        to: String,
+    },
+    // Transfers out the contract's balance of a denom other than the token, such as funds sent to it
+    // by mistake. Public mint payments held for redemptions or vesting are left in place
+    Rescue {
+        denom: String,
+        to: String,
+    },
+    // Burns tokens held by the contract. None burns its entire balance
+    Burn(Option<Uint128>),
+    // Burns tokens from the sending owner's wallet instead of the contract's balance, without sending
+    // them to the contract first. Only works on chains whose tokenfactory lets the denom admin burn
+    // from other addresses. BurnFunds works everywhere
+    BurnFromWallet(Uint128),
     // Updates the max mintable supply of the token
     UpdateSupply(Uint128),
+    // Updates the max mintable supply, given in whole tokens using the stored exponent
+    UpdateSupplyDisplay(Uint128),
+    // Permanently freezes the max supply. UpdateSupply will fail afterwards
+    LockSupply,
+    // Mints a share of every mint to a treasury on top of it, counted against the max supply.
+    // None removes the tax
+    SetMintTax(Option<MintTax>),
+    // Caps the total any address can receive through mints over the token's lifetime. None removes
+    // the limit
+    SetMintLimit(Option<Uint128>),
+    // Sets up a halving emission, minted to its recipient as it is released. None stops it
+    SetEmission(Option<Emission>),
+    // Mints whatever the emission has released since it was last minted. Can be called by anyone
+    MintEmission,
     // Transfers token admin to a null address, preventing future minting
     Revoke,
+    // Transfers token admin to the given address (e.g. a DAO or the gov module), removing this contract's control
+    RevokeTo {
+        new_admin: String,
+    },
+    // Transfers token admin to the chain's gov module account, making the token chain-owned.
+    // Defaults to the standard gov module address for this chain's bech32 prefix
+    HandoverToGov {
+        gov_module: Option<String>,
+    },
+    // Schedules a revoke that can be confirmed once `delay` seconds have passed.
+    // If no new admin is given the token admin will be transferred to the null address
+    ScheduleRevoke {
+        delay: u64,
+        new_admin: Option<String>,
+    },
+    // Executes a scheduled revoke after its delay has passed
+    ConfirmRevoke,
+    // Cancels a scheduled revoke
+    CancelRevoke,
+    // Adds or removes addresses from the pre-launch transfer allowlist
+    UpdateAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Re-reads the bank supply of the token and raises the minted total to match it, counting mints
+    // made outside the contract. Burns made outside the contract are already reflected in the
+    // burned total, which is derived from the supply
+    Reconcile,
+    // Enables or disables the send policy, and adds or removes its allowed destinations. While it
+    // is enabled, the token held by the contract can only be sent to those destinations
+    UpdateSendPolicy {
+        enabled: Option<bool>,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Permanently stops the contract from moving the token it holds. It can still be burned
+    DisableSend,
+    // Stops burning a share of every transfer. It can't be turned back on
+    DisableTransferBurn,
+    // Takes a share of every transfer out of what the recipient receives, sent to a treasury or
+    // burned. The before-send hook can only reject a bank send, not tax it, so while the tax is set
+    // every bank send between two addresses that aren't exempt is rejected and holders transfer
+    // through TransferFunds instead. That includes ibc transfers and pool swaps, so the pools,
+    // routers and ibc escrows the token moves through have to be exempted along with it. None
+    // removes the tax
+    SetTransferTax(Option<TransferTaxMsg>),
+    // Removes the transfer tax for good. It can't be set again
+    RenounceTransferTax,
+    // Adds or removes addresses whose transfers are not charged transfer fees, like pools and
+    // routers
+    UpdateTransferFeeExempt {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Adds or removes addresses exempt from the max-wallet limit
+    UpdateMaxWalletExempt {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Grants a role to an address
+    AddRole {
+        role: Role,
+        address: String,
+    },
+    // Removes a role from an address. The last owner can't be removed
+    RemoveRole {
+        role: Role,
+        address: String,
+    },
+    // Halts minting and treasury transfers
+    Pause,
+    // Resumes minting and treasury transfers
+    Unpause,
+    // Sets the bank metadata of the denom, using the stored symbol as the display unit
+    SetDenomMetadata(DenomMetadata),
+    // Changes the token's symbol and republishes its denom metadata with it. The denom stays the
+    // same
+    UpdateSymbol(String),
+    // Replaces the project's description and links
+    SetProjectInfo(ProjectInfo),
+    // Burns the attached tokens and pays out their pro-rata share of the public mint reserve.
+    // Can be called by any holder
+    Redeem,
+    // Burns the attached tokens. Can be called by any holder
+    BurnFunds,
+    // Sends the attached tokens to `recipient`, less the transfer burn and tax. While either is set,
+    // holders can only transfer to addresses that aren't exempt this way. Can be called by any holder
+    TransferFunds {
+        recipient: String,
+    },
+    // Pays out the vested public mint proceeds. Must be called by the vesting beneficiary
+    ClaimVested,
+    // Resets the dead man switch timer without doing anything else
+    Heartbeat,
+    // Triggers the dead man switch once the owners have been inactive for long enough. Can be called by anyone
+    ClaimOwnership,
+    // Executes several actions in order as one atomic transaction. Each action is authorized as if
+    // it was sent on its own, and no funds can be attached
+    Multi(Vec<ExecuteMsg>),
+    // Queues a timelocked action. It can be executed by anyone once the timelock has passed
+    Queue(Box<ExecuteMsg>),
+    // Executes a queued action whose timelock has passed
+    ExecuteQueued {
+        id: u64,
+    },
+    // Cancels a queued action. Scheduled actions can't be cancelled
+    CancelQueued {
+        id: u64,
+    },
+    // Commits to executing a Mint, Send, UpdateSupply or Revoke at `execute_at`, which must be at
+    // least the timelock away. It can't be cancelled, and anyone can execute it once it's due
+    Schedule {
+        msg: Box<ExecuteMsg>,
+        execute_at: Timestamp,
+    },
+    // Executes up to `limit` scheduled actions that are due, oldest first. Each runs on its own, so a
+    // failing one records its error without holding up the rest, and is retried by a later crank.
+    // Only actions that can never succeed any more are dropped, like a mint after the mint window.
+    // Can be called by anyone, and pays the crank reward for each action that succeeds if one is set
+    Crank {
+        limit: Option<u32>,
+    },
+    // Sets the reward paid from the contract's balance for each action Crank executes successfully.
+    // None removes it
+    SetCrankReward(Option<Coin>),
+    // Creates a concentrated liquidity pool pairing the token with the quote denom, then opens a
+    // full-range position owned by the contract with `amount` of the token and the quote. The
+    // tokens and the pool creation fee are paid from the contract's balance. The position is locked
+    // until `locked_until`, or permanently when it is None
+    CreateClPool {
+        amount: Uint128,
+        quote: Coin,
+        tick_spacing: u64,
+        spread_factor: Decimal,
+        locked_until: Option<Timestamp>,
+    },
+    // Creates a further denom managed by the contract, capped at `max_supply` (0 for no cap). Its
+    // symbol has to follow the token's symbol rules. Some chains charge a fee to create a denom,
+    // paid from the contract's balance
+    CreateDenom {
+        symbol: String,
+        subdenom: Option<String>,
+        max_supply: Uint128,
+    },
+    // Mints one of the further denoms, in base units. Can be called by an owner or one of the
+    // denom's minters
+    MintDenom {
+        symbol: String,
+        receivers: Vec<Receiver>,
+    },
+    // Burns one of the further denoms from the contract's balance
+    BurnDenom {
+        symbol: String,
+        amount: Uint128,
+    },
+    // Adds or removes the addresses allowed to mint one of the further denoms
+    UpdateDenomMinters {
+        symbol: String,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Creates an incentives gauge paying `amount` of the token over `epochs` epochs to the pool's
+    // liquidity providers. With a lock duration it pays LP shares locked for at least that many
+    // seconds, otherwise it is attached to the pool directly (concentrated liquidity pools). The
+    // tokens are minted when `mint` is set, or else paid from the contract's balance, along with the
+    // gauge creation fee
+    CreateGauge {
+        pool_id: u64,
+        amount: Uint128,
+        epochs: u64,
+        lock_duration: Option<u64>,
+        start_time: Option<Timestamp>,
+        mint: bool,
+    },
+    // Withdraws the contract's position from the pool into its balance, once its lock has expired
+    WithdrawPosition,
+    // Swaps `offer` from the contract's balance into the token through the pool, defaulting to the
+    // concentrated liquidity pool, and burns what it bought. Fails if less than `min_out` is bought
+    Buyback {
+        offer: Coin,
+        pool_id: Option<u64>,
+        min_out: Uint128,
+    },
+    // Mints any remaining supply up to the cap to the recipient, optionally lists the token
+    // in an assetlist contract, then revokes the token admin
+    Finalize {
+        recipient: String,
+        assetlist: Option<AssetlistRegistration>,
+    },
+    // Gives up control of the contract for good. Revokes the token admin if the contract still has
+    // it, optionally sweeps the treasury to `sweep_to`, then removes every role, allowance, queued
+    // action, the dead man switch and governance. Scheduled actions have to be executed and
+    // distributions swept first
+    RenounceContract {
+        sweep_to: Option<String>,
+    },
+    // Submits a governance proposal setting the taker fee for trades between the token and a quote denom.
+    // The deposit is paid from the part of the contract's balance it doesn't have to keep, as a
+    // vetoed proposal burns it
+    ProposeTakerFee {
+        quote_denom: String,
+        taker_fee: Decimal,
+        deposit: Vec<Coin>,
+        title: String,
+        description: String,
+    },
+}
+
+// Called by the tokenfactory module through the before-send hook registered on the denom
+#[cw_serde]
+pub enum SudoMsg {
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+    TrackBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+}
+
+#[cw_serde]
+pub struct AssetlistRegistration {
+    // The assetlist contract to add the listing to
+    pub contract: String,
+    pub metadata: Metadata,
+    // The listing fee. Paid from the attached funds at instantiate, or the contract's balance when
+    // finalizing
+    pub fee: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct DenomMetadata {
+    pub name: String,
+    pub description: String,
+    // The exponent of the display unit (e.g. 6 when 1 token is 1_000_000 base units)
+    pub exponent: u32,
+    pub uri: Option<String>,
 }
 
 #[cw_serde]
 pub struct Receiver {
     pub address: String,
     pub amount: Uint128,
+    // When true the amount is in whole tokens and is converted using the token's exponent
+    pub display_units: Option<bool>,
 }
 
 #[cw_serde]
@@ -35,9 +581,246 @@ pub enum QueryMsg {
     /// Returns the token denom and supply information
     #[returns(TokenInfoResponse)]
     TokenInfo,
+    /// Returns the contract version, token identity, admin and enabled features in one response
+    #[returns(ContractInfoResponse)]
+    ContractInfo,
+    /// Returns the address and denom of a factory deployed by `creator` with instantiate2, before it
+    /// is deployed
+    #[returns(DenomPreviewResponse)]
+    DenomPreview {
+        creator: String,
+        // The checksum of the factory code
+        checksum: HexBinary,
+        salt: Binary,
+        symbol: String,
+        subdenom: Option<String>,
+        tfa_prefix: Option<bool>,
+    },
     /// Returns the token mintable status
     #[returns(MintableResponse)]
     Mintable,
+    /// Simulates a mint to the receivers, from `bucket` if given, checking each address and amount,
+    /// the supply cap, the bucket, and whether minting is currently possible
+    #[returns(CanMintResponse)]
+    CanMint {
+        receivers: Vec<Receiver>,
+        bucket: Option<String>,
+    },
+    /// Returns the scheduled revoke, if any
+    #[returns(Option<PendingRevoke>)]
+    PendingRevoke,
+    /// Returns the arithmetic TWAP of the token in `quote_denom` over the last hour, from the
+    /// concentrated liquidity pool created for the token
+    #[returns(PriceResponse)]
+    Price { quote_denom: String },
+    /// Returns a paginated list of the further denoms created by the contract, by symbol
+    #[returns(DenomsResponse)]
+    Denoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the interfaces the contract implements, cw22 style, followed by the optional
+    /// capabilities this deployment has enabled as `factory:<capability>`
+    #[returns(SupportedInterfacesResponse)]
+    SupportedInterfaces,
+    /// Returns the project's description and links
+    #[returns(ProjectInfo)]
+    ProjectInfo,
+    /// Returns the concentrated liquidity pool created for the token, and the contract's position
+    /// in it with its lock
+    #[returns(Option<ClPool>)]
+    ClPool,
+    /// Returns a paginated list of queued timelocked actions
+    #[returns(Vec<(u64, QueuedAction)>)]
+    QueuedActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the emission schedule, if any, with how much it has released and how much was minted
+    #[returns(Option<EmissionResponse>)]
+    Emission,
+    /// Returns the reward paid for each action executed by Crank, if any
+    #[returns(Option<Coin>)]
+    CrankReward,
+    /// Returns the allocation buckets and the supply left unallocated
+    #[returns(BucketsResponse)]
+    Buckets,
+    /// Returns a paginated list of the taker fee proposals submitted by this contract and their
+    /// current status
+    #[returns(Vec<TakerFeeProposalResponse>)]
+    TakerFeeProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the protocol fee charged on mints by non-owners, if any
+    #[returns(Option<MintFee>)]
+    MintFee,
+    /// Returns the share of every mint additionally minted to a treasury, if any
+    #[returns(Option<MintTax>)]
+    MintTax,
+    /// Returns the most any address can receive through mints, if limited
+    #[returns(Option<Uint128>)]
+    MintLimit,
+    /// Returns the total minted to an address
+    #[returns(Uint128)]
+    MintedTo { address: String },
+    /// Returns the tokens granted to an address with MintVested that are still locked, can be
+    /// claimed, or have been claimed
+    #[returns(VestingResponse)]
+    Vesting { address: String },
+    /// Returns the address Revoke hands the token admin to
+    #[returns(String)]
+    NullAddress,
+    /// Returns whether the send policy is enabled and a paginated list of its allowed destinations
+    #[returns(SendPolicyResponse)]
+    SendPolicy {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns whether the token held by the contract can no longer be moved
+    #[returns(bool)]
+    SendDisabled,
+    /// Returns whether the owners can claw back tokens from holders
+    #[returns(bool)]
+    Clawback,
+    /// Returns a paginated list of distributions that haven't been swept
+    #[returns(Vec<(u64, Distribution)>)]
+    Distributions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns whether an address has claimed from a distribution
+    #[returns(bool)]
+    DistributionClaimed { id: u64, address: String },
+    /// Returns a paginated list of the latest mints, burns and sends, newest first
+    #[returns(Vec<(u64, HistoryEntry)>)]
+    History {
+        start_before: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns how much of the contract's tokens a spender can pull
+    #[returns(Allowance)]
+    Allowance { spender: String },
+    /// Returns a paginated list of spenders and their allowances
+    #[returns(Vec<(Addr, Allowance)>)]
+    Allowances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns mint and burn totals for the token
+    #[returns(Stats)]
+    Stats,
+    /// Returns the staking configuration and totals, if enabled
+    #[returns(Option<Staking>)]
+    Staking,
+    /// Returns an address's stakes and the rewards it can claim
+    #[returns(StakesResponse)]
+    Stakes { address: String },
+    /// Returns the governance parameters, if enabled
+    #[returns(Option<Governance>)]
+    Governance,
+    /// Returns a paginated list of proposals and their status
+    #[returns(Vec<ProposalResponse>)]
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns how an address voted on a proposal, if it did
+    #[returns(Option<bool>)]
+    Vote { id: u64, address: String },
+    /// Returns the public mint configuration, if enabled
+    #[returns(Option<PublicMint>)]
+    PublicMint,
+    /// Returns the current public mint price and the cost of minting an amount
+    #[returns(MintCostResponse)]
+    MintCost { amount: Uint128 },
+    /// Returns the public mint payments held by the contract
+    #[returns(Coin)]
+    Reserve,
+    /// Returns the proceeds vesting schedule and its progress, if one is set
+    #[returns(Option<ProceedsVestingResponse>)]
+    ProceedsVesting,
+    /// Returns the current phase of the public mint and the amount sold in each phase
+    #[returns(PhaseResponse)]
+    Phase,
+    /// Returns the amount an address has bought through the public mint
+    #[returns(Uint128)]
+    PublicMinted { address: String },
+    /// Returns the amounts an address has bought in each phase of the public mint
+    #[returns(PurchasesResponse)]
+    Purchases { address: String },
+    /// Returns the dead man switch configuration and when it can be triggered, if one is set
+    #[returns(Option<DeadManSwitchResponse>)]
+    DeadManSwitch,
+    /// Returns the addresses holding each role
+    #[returns(RolesResponse)]
+    Roles,
+    /// Returns the channels ibc-hooks mints are accepted from, with the remote senders allowed on each
+    #[returns(Vec<(String, Vec<String>)>)]
+    IbcHooks,
+    /// Returns the address ibc-hooks executes this contract from for a remote sender
+    #[returns(Addr)]
+    IbcHookSender {
+        channel: String,
+        original_sender: String,
+    },
+    /// Returns the number of entries in each storage map, to monitor state growth
+    #[returns(StorageStatsResponse)]
+    StorageStats,
+    /// Returns the events and attribute keys emitted by this contract version
+    #[returns(EventsSchemaResponse)]
+    EventsSchema,
+    /// Returns the trading start time and a paginated list of pre-launch allowlisted addresses
+    #[returns(AllowlistResponse)]
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the max-wallet limit, if any, and a paginated list of exempt addresses
+    #[returns(MaxWalletResponse)]
+    MaxWallet {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the transfer tax, if any
+    #[returns(Option<TransferTax>)]
+    TransferTax,
+    /// Returns a paginated list of addresses exempt from transfer fees
+    #[returns(Vec<Addr>)]
+    TransferFeeExempt {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct DenomPreviewResponse {
+    pub address: Addr,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct EmissionResponse {
+    pub emission: Emission,
+    pub released: Uint128,
+    pub minted: Uint128,
+    // The total the emission will release over all of its epochs
+    pub total: Uint128,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    // The cw2 contract name and version
+    pub contract: String,
+    pub version: String,
+    pub denom: String,
+    pub symbol: String,
+    // The tokenfactory admin of the denom
+    pub admin: Option<String>,
+    pub owners: Vec<Addr>,
+    pub revoked: bool,
+    // The optional features configured for this token, such as "timelock" or "public_mint"
+    pub features: Vec<String>,
 }
 
 #[cw_serde]
@@ -48,10 +831,221 @@ pub struct TokenInfoResponse {
     pub max_supply: Uint128,
     pub minted: Uint128,
     pub burned: Uint128,
+    pub supply_locked: bool,
+    pub paused: bool,
+    // The exponent used to convert amounts given in whole tokens
+    pub exponent: u32,
+    // The share of every transfer that is burned, and the total burned that way
+    pub transfer_burn: Option<Decimal>,
+    pub transfer_burned: Uint128,
 }
 
 #[cw_serde]
 pub struct MintableResponse {
     pub cap_reached: bool,
     pub revoked: bool,
+    // Whether the current time is within the mint window
+    pub window_open: bool,
+    pub mint_start: Option<Timestamp>,
+    pub mint_end: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct VestingResponse {
+    pub locked: Uint128,
+    pub claimable: Uint128,
+    pub claimed: Uint128,
+}
+
+#[cw_serde]
+pub struct StakesResponse {
+    pub positions: Vec<(u64, StakePosition)>,
+    pub weight: Uint128,
+    pub rewards: Uint128,
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposal: Proposal,
+    // "open", "passed", "rejected" or "executed"
+    pub status: String,
+}
+
+#[cw_serde]
+pub struct CanMintResponse {
+    pub receivers: Vec<ReceiverCheck>,
+    // The total of the valid receivers' amounts, in base units
+    pub total: Uint128,
+    // The tax minted to the treasury on top of the total
+    pub tax: Uint128,
+    // The minted supply if the valid receivers and the tax were minted to
+    pub total_minted: Uint128,
+    pub within_cap: bool,
+    // False when the bucket can't cover the total and the tax, or when buckets are configured and
+    // none was given
+    pub within_bucket: bool,
+    // False while paused or outside of the mint window
+    pub open: bool,
+    // True when every receiver is valid and every check passes
+    pub valid: bool,
+}
+
+#[cw_serde]
+pub struct ReceiverCheck {
+    pub address: String,
+    // The amount in base units
+    pub amount: Uint128,
+    pub valid: bool,
+}
+
+#[cw_serde]
+pub struct BucketsResponse {
+    pub buckets: Vec<BucketInfo>,
+    // Cap not yet minted or reserved by a bucket. None when the supply is uncapped
+    pub unallocated: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct BucketInfo {
+    pub label: String,
+    pub allocated: Uint128,
+    pub minted: Uint128,
+    pub remaining: Uint128,
+    pub minters: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct ProceedsVestingResponse {
+    pub schedule: ProceedsVesting,
+    // Proceeds held back so far
+    pub deposited: Coin,
+    // How much of the deposits has vested, including what has been claimed
+    pub vested: Coin,
+    pub claimed: Coin,
+}
+
+#[cw_serde]
+pub struct PhaseResponse {
+    // "closed" before the presale, "presale" or "public"
+    pub phase: String,
+    pub presale: Option<Presale>,
+    pub presale_minted: Uint128,
+    pub public_minted: Uint128,
+}
+
+#[cw_serde]
+pub struct PurchasesResponse {
+    pub presale: Uint128,
+    pub public: Uint128,
+}
+
+#[cw_serde]
+pub struct MintCostResponse {
+    // The price of the next base unit, in base units of the payment denom
+    pub spot_price: Decimal256,
+    pub cost: Coin,
+}
+
+#[cw_serde]
+pub struct DeadManSwitchResponse {
+    pub inactivity: u64,
+    pub fallback: Option<Addr>,
+    pub last_activity: Timestamp,
+    pub claimable_after: Timestamp,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub owners: Vec<Addr>,
+    pub minters: Vec<Addr>,
+    pub pausers: Vec<Addr>,
+    pub metadata_managers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct AllowlistResponse {
+    pub trading_start: Option<Timestamp>,
+    pub addresses: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct MaxWalletResponse {
+    pub max_wallet: Option<MaxWallet>,
+    pub exempt: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub pool_id: u64,
+    pub quote_denom: String,
+    // The average price of one unit of the token, in units of the quote denom
+    pub price: Decimal,
+    // The start of the averaging window, which ends at the current block
+    pub start_time: Timestamp,
+}
+
+#[cw_serde]
+pub struct DenomsResponse {
+    pub denoms: Vec<DenomInfo>,
+}
+
+#[cw_serde]
+pub struct DenomInfo {
+    pub symbol: String,
+    pub denom: String,
+    pub max_supply: Uint128,
+    pub minted: Uint128,
+    pub burned: Uint128,
+    pub minters: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct SupportedInterfacesResponse {
+    pub interfaces: Vec<SupportedInterface>,
+}
+
+#[cw_serde]
+pub struct SupportedInterface {
+    // e.g. "crates.io:factory"
+    pub interface: String,
+    pub version: String,
+}
+
+#[cw_serde]
+pub struct SendPolicyResponse {
+    pub enabled: bool,
+    pub destinations: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct StorageStatsResponse {
+    // (map name, entry count) pairs
+    pub maps: Vec<(String, u64)>,
+}
+
+#[cw_serde]
+pub struct EventsSchemaResponse {
+    pub version: String,
+    pub events: Vec<EventSchema>,
+}
+
+#[cw_serde]
+pub struct EventSchema {
+    // The type of the emitted event
+    pub event_type: String,
+    // The value of the `action` attribute identifying the operation. Empty for typed events, which
+    // are identified by their type
+    pub action: String,
+    // Other attribute keys that can be emitted with this action
+    pub attributes: Vec<String>,
+}
+
+#[cw_serde]
+pub struct TakerFeeProposalResponse {
+    pub proposal_id: u64,
+    pub quote_denom: String,
+    pub taker_fee: Decimal,
+    // The proposal status reported by the gov module, or "unknown" if it can't be queried
+    pub status: String,
 }
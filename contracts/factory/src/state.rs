@@ -1,13 +1,98 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use crate::msg::ExecuteMsg;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Coin, Decimal, Decimal256, Empty, Order, StdError, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::{Bound, Item, KeyDeserialize, Map, Prefix, PrimaryKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 #[repr(u8)]
 pub enum TopKey {
-    Admin = b'a',
+    // b'a' held the single admin address, before it was replaced by roles
     Symbol = b'b',
     Denom = b'c',
     MaxSupply = b'd',
     TotalMinted = b'e',
+    TradingStart = b'f',
+    Allowlist = b'g',
+    PendingRevoke = b'h',
+    Timelock = b'i',
+    QueueId = b'j',
+    QueuedActions = b'k',
+    PendingTakerFee = b'l',
+    TakerFeeProposals = b'm',
+    SupplyLocked = b'n',
+    Buckets = b'o',
+    Paused = b'p',
+    MintFee = b'q',
+    DeadManSwitch = b'r',
+    LastActivity = b's',
+    PublicMint = b't',
+    PublicMinted = b'u',
+    Reserve = b'v',
+    Exponent = b'w',
+    Presale = b'x',
+    PresaleAllowlist = b'y',
+    PresalePurchased = b'z',
+    PhaseMinted = b'A',
+    MintStart = b'B',
+    MintEnd = b'C',
+    ProceedsVesting = b'D',
+    VestingDeposited = b'E',
+    VestingClaimed = b'F',
+    SymbolRules = b'G',
+    IbcHooks = b'I',
+    ClPool = b'J',
+    PendingPosition = b'K',
+    MintTax = b'L',
+    MintLimit = b'M',
+    MintedTo = b'N',
+    VestingGrants = b'O',
+    VestingLocked = b'P',
+    Clawback = b'Q',
+    DistributionId = b'R',
+    Distributions = b'S',
+    DistributionClaims = b'T',
+    Staking = b'U',
+    Stakers = b'V',
+    StakeId = b'W',
+    StakePositions = b'X',
+    Governance = b'Y',
+    ProposalId = b'Z',
+    Proposals = b'0',
+    Votes = b'1',
+    HistoryId = b'2',
+    History = b'3',
+    Stats = b'4',
+    Allowances = b'5',
+    NullAddress = b'6',
+    CrankReward = b'7',
+    Emission = b'8',
+    EmissionMinted = b'9',
+    // the alphanumeric keys are all taken, later ones use ascii punctuation
+    MaxWallet = b'!',
+    MaxWalletExempt = b'#',
+    TransferBurn = b'$',
+    TransferBurned = b'%',
+    ProjectInfo = b'&',
+    Denoms = b'(',
+    DenomMinters = b')',
+    SendDisabled = b'*',
+    SendPolicy = b'+',
+    SendDestinations = b',',
+    EntryCounts = b'-',
+    TransferFeeExempt = b'.',
+    TransferTax = b':',
+    TransferTaxRenounced = b';',
+    Schedule = b'<',
+    DistributionHeld = b'=',
+    UnlockWeights = b'>',
+    ProposalDeposits = b'?',
+    Roles = b'@',
+    TfaPrefix = b'[',
+    Cranker = b']',
+    Redeemed = b'^',
 }
 
 impl TopKey {
@@ -15,13 +100,681 @@ impl TopKey {
         let array_ref = unsafe { std::mem::transmute::<_, &[u8; 1]>(self) };
         match core::str::from_utf8(array_ref) {
             Ok(a) => a,
-            Err(_) => panic!("Non-utf8 enum value found. Use a-z, A-Z and 0-9"),
+            Err(_) => panic!("Non-utf8 enum value found. Use printable ascii"),
         }
     }
 }
 
-pub const ADMIN: Item<Addr> = Item::new(TopKey::Admin.as_str());
+// the interfaces the contract implements, by name, with their versions. it uses the cw22 namespace
+// instead of a top key so integrators can read it with a raw query, like the cw2 contract info
+pub const SUPPORTED_INTERFACES: Map<&str, String> = Map::new("supported_interfaces");
+// (role, address) pairs. owners implicitly hold every other role
+pub const ROLES: CountedMap<(&str, &Addr), Empty> =
+    CountedMap::new(TopKey::Roles.as_str(), "roles");
+// while set, minting and treasury transfers are halted
+pub const PAUSED: Item<bool> = Item::new(TopKey::Paused.as_str());
+// protocol fee charged on mints by non-owners. unset when no fee is configured
+pub const MINT_FEE: Item<MintFee> = Item::new(TopKey::MintFee.as_str());
+// share of every mint additionally minted to a treasury. unset when there is no tax
+pub const MINT_TAX: Item<MintTax> = Item::new(TopKey::MintTax.as_str());
+// the most any address can receive through mints in total. unset for no limit
+pub const MINT_LIMIT: Item<Uint128> = Item::new(TopKey::MintLimit.as_str());
+// the amount minted to each address, tracked whether or not a limit is set
+pub const MINTED_TO: CountedMap<&Addr, Uint128> =
+    CountedMap::new(TopKey::MintedTo.as_str(), "minted_to");
+// lets anyone mint by paying a fixed price. unset when public minting is disabled
+pub const PUBLIC_MINT: Item<PublicMint> = Item::new(TopKey::PublicMint.as_str());
+// the amount each address has bought through the public mint
+pub const PUBLIC_MINTED: CountedMap<&Addr, Uint128> =
+    CountedMap::new(TopKey::PublicMinted.as_str(), "public_minted");
+// an allowlisted phase of the public mint with its own price. unset when there is no presale
+pub const PRESALE: Item<Presale> = Item::new(TopKey::Presale.as_str());
+pub const PRESALE_ALLOWLIST: CountedMap<&Addr, Empty> =
+    CountedMap::new(TopKey::PresaleAllowlist.as_str(), "presale_allowlist");
+// the amount each address has bought during the presale
+pub const PRESALE_PURCHASED: CountedMap<&Addr, Uint128> =
+    CountedMap::new(TopKey::PresalePurchased.as_str(), "presale_purchased");
+// the amount sold in each phase of the public mint ("presale" or "public")
+pub const PHASE_MINTED: CountedMap<&str, Uint128> =
+    CountedMap::new(TopKey::PhaseMinted.as_str(), "phase_minted");
+// payments from the public mint that are kept in the contract
+pub const RESERVE: Item<Uint128> = Item::new(TopKey::Reserve.as_str());
+// the tokens redeemed against the reserve, which move the bonding curve back down
+pub const REDEEMED: Item<u128> = Item::new(TopKey::Redeemed.as_str());
+// holds back a share of public mint proceeds and vests it to the team. unset when disabled
+pub const PROCEEDS_VESTING: Item<ProceedsVesting> = Item::new(TopKey::ProceedsVesting.as_str());
+// the proceeds held back for vesting, and how much of them has been claimed
+pub const VESTING_DEPOSITED: Item<Uint128> = Item::new(TopKey::VestingDeposited.as_str());
+pub const VESTING_CLAIMED: Item<Uint128> = Item::new(TopKey::VestingClaimed.as_str());
+// tokens minted to the contract that vest to each address, in the order they were granted
+pub const VESTING_GRANTS: CountedMap<&Addr, Vec<VestingGrant>> =
+    CountedMap::new(TopKey::VestingGrants.as_str(), "vesting_grants");
+// the total of the grants that hasn't been claimed yet, which the contract must keep
+pub const VESTING_LOCKED: Item<Uint128> = Item::new(TopKey::VestingLocked.as_str());
+// lets a fallback take over if the owners go inactive. removed once triggered
+pub const DEAD_MAN_SWITCH: Item<DeadManSwitch> = Item::new(TopKey::DeadManSwitch.as_str());
+// the last time an owner executed an action
+pub const LAST_ACTIVITY: Item<Timestamp> = Item::new(TopKey::LastActivity.as_str());
 pub const SYMBOL: Item<String> = Item::new(TopKey::Symbol.as_str());
+// the rules the symbol was validated against
+pub const SYMBOL_RULES: Item<SymbolRules> = Item::new(TopKey::SymbolRules.as_str());
 pub const DENOM: Item<String> = Item::new(TopKey::Denom.as_str());
+// set when the token opted in to the tfa/ subdenom
+pub const TFA_PREFIX: Item<bool> = Item::new(TopKey::TfaPrefix.as_str());
 pub const MAX_SUPPLY: Item<u128> = Item::new(TopKey::MaxSupply.as_str());
 pub const TOTAL_MINTED: Item<u128> = Item::new(TopKey::TotalMinted.as_str());
+// minting is only possible from MINT_START until MINT_END, when they are set
+pub const MINT_START: Item<Timestamp> = Item::new(TopKey::MintStart.as_str());
+pub const MINT_END: Item<Timestamp> = Item::new(TopKey::MintEnd.as_str());
+// the exponent of the token's display unit
+pub const EXPONENT: Item<u32> = Item::new(TopKey::Exponent.as_str());
+// set once the max supply has been locked. it can never be unset
+pub const SUPPLY_LOCKED: Item<bool> = Item::new(TopKey::SupplyLocked.as_str());
+// labeled allocations of the supply (team, airdrop, liquidity...). mints must name a bucket once any exist
+pub const BUCKETS: CountedMap<&str, Bucket> = CountedMap::new(TopKey::Buckets.as_str(), "buckets");
+// transfers are restricted to allowlisted recipients until this time. unset when no pre-launch phase is configured
+pub const TRADING_START: Item<Timestamp> = Item::new(TopKey::TradingStart.as_str());
+pub const ALLOWLIST: CountedMap<&Addr, Empty> =
+    CountedMap::new(TopKey::Allowlist.as_str(), "allowlist");
+// the most any address can hold during the launch period, enforced by the before-send hook.
+// exempt addresses (pools, the team) can hold more
+pub const MAX_WALLET: Item<MaxWallet> = Item::new(TopKey::MaxWallet.as_str());
+pub const MAX_WALLET_EXEMPT: CountedMap<&Addr, Empty> =
+    CountedMap::new(TopKey::MaxWalletExempt.as_str(), "max_wallet_exempt");
+// the share of every transfer burned, until it is disabled for good. the before-send hook can't
+// change a transfer, so it rejects direct ones and TransferFunds burns the share instead
+pub const TRANSFER_BURN: Item<Decimal> = Item::new(TopKey::TransferBurn.as_str());
+pub const TRANSFER_BURNED: Item<Uint128> = Item::new(TopKey::TransferBurned.as_str());
+// a share of every transfer sent to a treasury or burned, taken by TransferFunds like the transfer
+// burn
+pub const TRANSFER_TAX: Item<TransferTax> = Item::new(TopKey::TransferTax.as_str());
+// set once the transfer tax has been renounced. it can never be set again
+pub const TRANSFER_TAX_RENOUNCED: Item<bool> = Item::new(TopKey::TransferTaxRenounced.as_str());
+// transfers from or to these addresses (pools, routers) are not charged transfer fees
+pub const TRANSFER_FEE_EXEMPT: CountedMap<&Addr, Empty> =
+    CountedMap::new(TopKey::TransferFeeExempt.as_str(), "transfer_fee_exempt");
+// links and a description of the project behind the token, for wallets and aggregators
+pub const PROJECT_INFO: Item<ProjectInfo> = Item::new(TopKey::ProjectInfo.as_str());
+// further denoms created by the contract, keyed by symbol, with the addresses allowed to mint each.
+// owners can mint all of them. the token itself is not included
+pub const DENOMS: CountedMap<&str, ManagedDenom> =
+    CountedMap::new(TopKey::Denoms.as_str(), "denoms");
+pub const DENOM_MINTERS: CountedMap<(&str, &Addr), Empty> =
+    CountedMap::new(TopKey::DenomMinters.as_str(), "denom_minters");
+// once set, the token held by the contract can only be burned, never moved out
+pub const SEND_DISABLED: Item<bool> = Item::new(TopKey::SendDisabled.as_str());
+// while the send policy is enabled, the token held by the contract can only be sent to these
+// destinations (pools, lockers, vesting contracts)
+pub const SEND_POLICY: Item<bool> = Item::new(TopKey::SendPolicy.as_str());
+pub const SEND_DESTINATIONS: CountedMap<&Addr, Empty> =
+    CountedMap::new(TopKey::SendDestinations.as_str(), "send_destinations");
+pub const PENDING_REVOKE: Item<PendingRevoke> = Item::new(TopKey::PendingRevoke.as_str());
+// channels ibc-hooks mints are accepted from, with the remote senders allowed on each. an empty
+// list allows any sender on the channel
+pub const IBC_HOOKS: CountedMap<&str, Vec<String>> =
+    CountedMap::new(TopKey::IbcHooks.as_str(), "ibc_hooks");
+// the concentrated liquidity pool created for the token, and the contract's position in it
+pub const CL_POOL: Item<ClPool> = Item::new(TopKey::ClPool.as_str());
+// the position to open, held until the pool creation replies with its id
+pub const PENDING_POSITION: Item<PendingPosition> = Item::new(TopKey::PendingPosition.as_str());
+// rewards deposited for holders at a snapshot. removed once the unclaimed rewards are swept
+pub const DISTRIBUTION_ID: Item<u64> = Item::new(TopKey::DistributionId.as_str());
+pub const DISTRIBUTIONS: CountedMap<u64, Distribution> =
+    CountedMap::new(TopKey::Distributions.as_str(), "distributions");
+// the rewards of all distributions that are still unclaimed, by denom
+pub const DISTRIBUTION_HELD: Map<&str, Uint128> = Map::new(TopKey::DistributionHeld.as_str());
+// the holders that have claimed from each distribution
+pub const DISTRIBUTION_CLAIMS: CountedMap<(u64, &Addr), Empty> =
+    CountedMap::new(TopKey::DistributionClaims.as_str(), "distribution_claims");
+// lets holders lock the token in the contract for rewards. unset when staking is disabled
+pub const STAKING: Item<Staking> = Item::new(TopKey::Staking.as_str());
+// each holder's combined stake weight and the rewards owed to them
+pub const STAKERS: CountedMap<&Addr, Staker> = CountedMap::new(TopKey::Stakers.as_str(), "stakers");
+pub const STAKE_ID: Item<u64> = Item::new(TopKey::StakeId.as_str());
+// the locked stakes of each holder, by id
+pub const STAKE_POSITIONS: CountedMap<(&Addr, u64), StakePosition> =
+    CountedMap::new(TopKey::StakePositions.as_str(), "stake_positions");
+// the weight of the stakes unlocking at each time in nanoseconds, until they are unstaked
+pub const UNLOCK_WEIGHTS: Map<u64, Uint128> = Map::new(TopKey::UnlockWeights.as_str());
+// lets stakers propose and vote on factory actions. unset when governance is disabled
+pub const GOVERNANCE: Item<Governance> = Item::new(TopKey::Governance.as_str());
+pub const PROPOSAL_ID: Item<u64> = Item::new(TopKey::ProposalId.as_str());
+pub const PROPOSALS: CountedMap<u64, Proposal> =
+    CountedMap::new(TopKey::Proposals.as_str(), "proposals");
+// the proposal deposits the contract holds until they are refunded or burned
+pub const PROPOSAL_DEPOSITS: Item<Uint128> = Item::new(TopKey::ProposalDeposits.as_str());
+// each voter's choice on each proposal, true to approve
+pub const VOTES: CountedMap<(u64, &Addr), bool> = CountedMap::new(TopKey::Votes.as_str(), "votes");
+// the last mints, burns and sends, keyed by an increasing id. older entries are pruned
+pub const HISTORY_ID: Item<u64> = Item::new(TopKey::HistoryId.as_str());
+pub const HISTORY: CountedMap<u64, HistoryEntry> =
+    CountedMap::new(TopKey::History.as_str(), "history");
+// running totals of the token's mints and burns
+pub const STATS: Item<Stats> = Item::new(TopKey::Stats.as_str());
+// treasury tokens each spender can pull with TransferFrom
+pub const ALLOWANCES: CountedMap<&Addr, Allowance> =
+    CountedMap::new(TopKey::Allowances.as_str(), "allowances");
+// where Revoke sends the token admin. derived from the contract address when unset
+pub const NULL_ADDRESS: Item<Addr> = Item::new(TopKey::NullAddress.as_str());
+// set when the token opted in to clawbacks at instantiate. it can never be set afterwards
+pub const CLAWBACK: Item<bool> = Item::new(TopKey::Clawback.as_str());
+// delay in seconds applied to privileged actions. unset when no timelock is configured
+pub const TIMELOCK: Item<u64> = Item::new(TopKey::Timelock.as_str());
+pub const QUEUE_ID: Item<u64> = Item::new(TopKey::QueueId.as_str());
+pub const QUEUED_ACTIONS: CountedMap<u64, QueuedAction> =
+    CountedMap::new(TopKey::QueuedActions.as_str(), "queued_actions");
+// the ids of the scheduled actions, by the time in nanoseconds they are due, for the crank
+pub const SCHEDULE: Map<(u64, u64), Empty> = Map::new(TopKey::Schedule.as_str());
+// paid from the contract's balance to whoever cranks, for each action executed successfully
+pub const CRANK_REWARD: Item<Coin> = Item::new(TopKey::CrankReward.as_str());
+// the address of the last crank, which the replies of its actions pay the reward to
+pub const CRANKER: Item<Addr> = Item::new(TopKey::Cranker.as_str());
+// a halving emission schedule that anyone can mint from as it is released, and how much of it has
+// been minted
+pub const EMISSION: Item<Emission> = Item::new(TopKey::Emission.as_str());
+pub const EMISSION_MINTED: Item<Uint128> = Item::new(TopKey::EmissionMinted.as_str());
+// holds a submitted taker fee proposal until the gov module replies with its id
+pub const PENDING_TAKER_FEE: Item<TakerFeeProposal> = Item::new(TopKey::PendingTakerFee.as_str());
+pub const TAKER_FEE_PROPOSALS: CountedMap<u64, TakerFeeProposal> =
+    CountedMap::new(TopKey::TakerFeeProposals.as_str(), "taker_fee_proposals");
+
+#[cw_serde]
+pub struct PendingRevoke {
+    // The revoke can be confirmed at or after this time
+    pub executable_after: Timestamp,
+    // The address that will receive the token admin. None for the null address
+    pub new_admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct QueuedAction {
+    pub msg: ExecuteMsg,
+    // The action can be executed by anyone at or after this time
+    pub executable_after: Timestamp,
+    // Set for scheduled actions, which are commitments and can't be cancelled
+    #[serde(default)]
+    pub committed: bool,
+}
+
+#[cw_serde]
+pub struct ClPool {
+    pub pool_id: u64,
+    // The contract's full-range position. None until it has been opened, or once withdrawn
+    pub position_id: Option<u64>,
+    // The position can't be withdrawn before this time. None if it is locked permanently
+    pub locked_until: Option<Timestamp>,
+    // When the pool was created. None for pools created before this was recorded
+    #[serde(default)]
+    pub created: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct PendingPosition {
+    pub tokens: Vec<Coin>,
+    pub locked_until: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct TakerFeeProposal {
+    pub quote_denom: String,
+    pub taker_fee: Decimal,
+}
+
+#[cw_serde]
+pub struct SymbolRules {
+    pub min_length: u32,
+    pub max_length: u32,
+    // Characters allowed in addition to ASCII letters and digits. `/` is never allowed
+    pub extra_chars: String,
+}
+
+impl Default for SymbolRules {
+    // fits the default tfa/ subdenom within tokenfactory's limits on Osmosis
+    fn default() -> Self {
+        SymbolRules {
+            min_length: 2,
+            max_length: 16,
+            extra_chars: String::new(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct MintFee {
+    // The fee paid with each mint operation
+    pub fee: Coin,
+    // The address fees are forwarded to
+    pub collector: Addr,
+}
+
+#[cw_serde]
+pub struct MintTax {
+    // The share of each mint minted to the treasury on top of it
+    pub rate: Decimal,
+    // The address the tax is minted to
+    pub treasury: Addr,
+}
+
+#[cw_serde]
+pub struct PublicMint {
+    pub payment_denom: String,
+    // Price of one base unit of the token, in base units of the payment denom
+    pub price: Decimal,
+    // The most any address can buy in total. None for no limit
+    pub limit_per_address: Option<Uint128>,
+    // The most that can be sold in the public phase. None for no limit other than the max supply
+    pub cap: Option<Uint128>,
+    // Hex encoded sha256 merkle root of the addresses allowed to buy, with leaves of
+    // sha256(address + cap). None to allow anyone
+    pub merkle_root: Option<String>,
+    // Where payments are forwarded. None to keep them in the contract as a reserve
+    pub treasury: Option<Addr>,
+    // Makes the price increase with the minted supply, starting from `price`. Payments are kept as a reserve
+    pub curve: Option<Curve>,
+    // Tighter limits for the opening of the public phase, to blunt sniper bots at launch
+    pub anti_snipe: Option<AntiSnipe>,
+}
+
+#[cw_serde]
+pub struct Emission {
+    // Where the emission is minted to
+    pub recipient: Addr,
+    pub start: Timestamp,
+    // Released linearly over the first epoch. Every following epoch releases half of the one before
+    pub initial_reward: Uint128,
+    // The length of an epoch in seconds
+    pub halving_interval: u64,
+    // Nothing is released after this many epochs
+    pub epochs: u32,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ProjectInfo {
+    pub description: Option<String>,
+    pub website: Option<String>,
+    pub x: Option<String>,
+    pub telegram: Option<String>,
+    pub logo_uri: Option<String>,
+}
+
+#[cw_serde]
+pub struct ManagedDenom {
+    pub denom: String,
+    pub max_supply: Uint128,
+    pub minted: Uint128,
+    pub burned: Uint128,
+}
+
+#[cw_serde]
+pub struct TransferTax {
+    // The share of each transfer taken from what the recipient receives
+    pub rate: Decimal,
+    // The address the tax is sent to. None burns it
+    pub treasury: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct MaxWallet {
+    // The most a single address can hold
+    pub amount: Uint128,
+    // The limit is lifted at this time
+    pub until: Timestamp,
+}
+
+#[cw_serde]
+pub struct AntiSnipe {
+    // The limits apply to public phase purchases made before this time
+    pub until: Timestamp,
+    // The most a single mint can buy
+    pub max_per_tx: Option<Uint128>,
+    // The most any address can buy in the public phase until then
+    pub max_per_address: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct Presale {
+    // The public mint is closed before this time
+    pub start: Timestamp,
+    // The public phase starts at this time
+    pub end: Timestamp,
+    // Price of one base unit of the token during the presale, in base units of the payment denom
+    pub price: Decimal,
+    // The most that can be sold in the presale
+    pub cap: Option<Uint128>,
+    // The most any allowlisted address can buy during the presale
+    pub limit_per_address: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct ProceedsVesting {
+    // The share of each payment held back from the treasury
+    pub share: Decimal,
+    // The team address the proceeds vest to
+    pub beneficiary: Addr,
+    // Proceeds vest linearly from this time
+    pub start: Timestamp,
+    // Seconds after the start before anything can be claimed
+    pub cliff: u64,
+    // Seconds after the start until everything has vested
+    pub duration: u64,
+}
+
+#[cw_serde]
+pub struct VestingSchedule {
+    // Tokens vest linearly from this time
+    pub start: Timestamp,
+    // Seconds after the start before anything can be claimed
+    pub cliff: u64,
+    // Seconds after the start until everything has vested
+    pub duration: u64,
+}
+
+#[cw_serde]
+pub struct VestingGrant {
+    pub amount: Uint128,
+    pub claimed: Uint128,
+    pub schedule: VestingSchedule,
+}
+
+#[cw_serde]
+pub struct Distribution {
+    // Hex encoded sha256 merkle root of the holder balances at the snapshot, with leaves of
+    // sha256(address + balance)
+    pub merkle_root: String,
+    pub snapshot_height: u64,
+    // The sum of the balances in the snapshot. Each holder claims their balance's share of it
+    pub total: Uint128,
+    pub rewards: Vec<Coin>,
+    pub claimed: Vec<Coin>,
+    // Claims close at this time, and whatever is unclaimed can be swept
+    pub expiry: Timestamp,
+}
+
+#[cw_serde]
+pub struct HistoryEntry {
+    // One of "mint", "burn" or "send"
+    pub action: String,
+    // The recipient of a mint or send
+    pub address: Option<String>,
+    pub amount: Uint128,
+    pub height: u64,
+    pub time: Timestamp,
+    // The denom minted, burned or sent, for the further denoms. None for the token itself
+    #[serde(default)]
+    pub denom: Option<String>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Allowance {
+    pub amount: Uint128,
+    // The allowance can't be spent after this time, if set
+    pub expires: Option<Timestamp>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Stats {
+    // Successful mint operations. A batch counts once, and the tax minted along with it doesn't count
+    pub mints: u64,
+    // Distinct addresses that have been minted to
+    pub recipients: u64,
+    pub largest_mint: Uint128,
+    pub last_mint: Option<Timestamp>,
+    pub last_burn: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct LockTier {
+    // Seconds the stake is locked for
+    pub duration: u64,
+    // The stake's weight in rewards per token
+    pub multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct Staking {
+    pub reward_denom: String,
+    // Rewards emitted per second, shared by weight between all stakes
+    pub reward_rate: Uint128,
+    pub tiers: Vec<LockTier>,
+    pub total_staked: Uint128,
+    pub total_weight: Uint128,
+    // Rewards emitted per unit of weight since staking was enabled
+    pub reward_per_weight: Decimal256,
+    pub last_update: Timestamp,
+    // Funded rewards that haven't been emitted yet. Emission stops when they run out
+    pub rewards_left: Uint128,
+    // Emitted rewards that haven't been claimed yet
+    pub unclaimed: Uint128,
+    // The part of the unclaimed rewards settled into stakers' pending rewards. The rest is
+    // owed to the current stakes, or is rounding dust once nothing is staked
+    #[serde(default)]
+    pub settled: Uint128,
+}
+
+#[cw_serde]
+pub struct Staker {
+    pub weight: Uint128,
+    // The reward per weight when the staker's rewards were last settled
+    pub reward_per_weight: Decimal256,
+    // Rewards settled but not claimed yet
+    pub pending: Uint128,
+}
+
+#[cw_serde]
+pub struct StakePosition {
+    pub amount: Uint128,
+    pub weight: Uint128,
+    pub unlock: Timestamp,
+}
+
+#[cw_serde]
+pub struct Governance {
+    // Seconds proposals are open for voting
+    pub voting_period: u64,
+    // The share of the total stake weight that must vote for a proposal to be valid
+    pub quorum: Decimal,
+    // The share of the votes that must approve for a proposal to pass
+    pub threshold: Decimal,
+    // Seconds a passed proposal can be executed for once voting closes
+    pub execution_period: u64,
+    // Tokens a proposer must attach, refunded once the proposal reaches the quorum and burned
+    // otherwise. Zero for none
+    #[serde(default)]
+    pub deposit: Uint128,
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<ExecuteMsg>,
+    // Voting closes at this time, after which a passed proposal can be executed by anyone
+    pub end: Timestamp,
+    // A passed proposal expires at this time if it hasn't been executed
+    pub expires: Timestamp,
+    // The stake weight locked until voting closes when the proposal was created. Only it can
+    // vote, so the quorum is a share of it
+    pub total_weight: Uint128,
+    pub yes: Uint128,
+    pub no: Uint128,
+    pub executed: bool,
+    // The deposit the contract still holds for the proposal
+    #[serde(default)]
+    pub deposit: Uint128,
+    // The quorum when the proposal was created. The deposit is settled against it, so it can be
+    // refunded or burned even after governance is disabled
+    #[serde(default)]
+    pub quorum: Decimal,
+}
+
+#[cw_serde]
+pub enum Curve {
+    // The price increases by `slope` for every base unit minted
+    Linear { slope: Decimal },
+    // The price is multiplied by (1 + growth) for every `step` base units minted
+    Exponential { growth: Decimal, step: Uint128 },
+}
+
+#[cw_serde]
+pub struct DeadManSwitch {
+    // Seconds without owner activity after which the switch can be triggered
+    pub inactivity: u64,
+    // The address that becomes the sole owner. None to revoke the token admin to the null address instead
+    pub fallback: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct Bucket {
+    // Total amount reserved for this bucket, including what has been minted
+    pub allocated: Uint128,
+    pub minted: Uint128,
+    // Addresses other than the admin allowed to mint from this bucket only
+    pub minters: Vec<Addr>,
+}
+
+#[cw_serde]
+pub enum Role {
+    // Full control of the contract, including role management
+    Owner,
+    Minter,
+    Pauser,
+    MetadataManager,
+}
+
+impl Role {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::Minter => "minter",
+            Role::Pauser => "pauser",
+            Role::MetadataManager => "metadata_manager",
+        }
+    }
+}
+
+// entry counts of the CountedMaps by name, so StorageStats doesn't have to iterate over every map
+pub const ENTRY_COUNTS: Map<&str, u64> = Map::new(TopKey::EntryCounts.as_str());
+
+// a Map that keeps its entry count in ENTRY_COUNTS. it only exposes the reads of the wrapped Map,
+// so every write goes through save, update, remove and clear from here and the count stays in sync
+pub struct CountedMap<'a, K, T> {
+    map: Map<'a, K, T>,
+    name: &'a str,
+}
+
+impl<'a, K, T> CountedMap<'a, K, T> {
+    pub const fn new(namespace: &'a str, name: &'a str) -> Self {
+        CountedMap {
+            map: Map::new(namespace),
+            name,
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+}
+
+impl<'a, K, T> CountedMap<'a, K, T>
+where
+    K: PrimaryKey<'a>,
+    T: Serialize + DeserializeOwned,
+{
+    pub fn load(&self, store: &dyn Storage, k: K) -> StdResult<T> {
+        self.map.load(store, k)
+    }
+
+    pub fn may_load(&self, store: &dyn Storage, k: K) -> StdResult<Option<T>> {
+        self.map.may_load(store, k)
+    }
+
+    pub fn has(&self, store: &dyn Storage, k: K) -> bool {
+        self.map.has(store, k)
+    }
+
+    pub fn is_empty(&self, store: &dyn Storage) -> bool {
+        self.map.is_empty(store)
+    }
+
+    pub fn prefix(&self, p: K::Prefix) -> Prefix<K::Suffix, T, K::Suffix> {
+        self.map.prefix(p)
+    }
+
+    pub fn save(&self, store: &mut dyn Storage, k: K, data: &T) -> StdResult<()> {
+        if !self.map.has(store, k.clone()) {
+            add_entries(store, self.name, 1)?;
+        }
+        self.map.save(store, k, data)
+    }
+
+    pub fn update<A, E>(&self, store: &mut dyn Storage, k: K, action: A) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        let existed = self.map.has(store, k.clone());
+        let data = self.map.update(store, k, action)?;
+        if !existed {
+            add_entries(store, self.name, 1)?;
+        }
+        Ok(data)
+    }
+
+    pub fn remove(&self, store: &mut dyn Storage, k: K) -> StdResult<()> {
+        if self.map.has(store, k.clone()) {
+            self.map.remove(store, k);
+            add_entries(store, self.name, -1)?;
+        }
+        Ok(())
+    }
+
+    pub fn clear(&self, store: &mut dyn Storage) -> StdResult<()> {
+        self.map.clear(store);
+        ENTRY_COUNTS.save(store, self.name, &0)
+    }
+}
+
+impl<'a, K, T> CountedMap<'a, K, T>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    T: Serialize + DeserializeOwned,
+{
+    pub fn range<'c>(
+        &self,
+        store: &'c dyn Storage,
+        min: Option<Bound<'a, K>>,
+        max: Option<Bound<'a, K>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<(K::Output, T)>> + 'c>
+    where
+        T: 'c,
+        K::Output: 'static,
+    {
+        self.map.range(store, min, max, order)
+    }
+
+    pub fn keys<'c>(
+        &self,
+        store: &'c dyn Storage,
+        min: Option<Bound<'a, K>>,
+        max: Option<Bound<'a, K>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<K::Output>> + 'c>
+    where
+        T: 'c,
+        K::Output: 'static,
+    {
+        self.map.keys(store, min, max, order)
+    }
+}
+
+fn add_entries(store: &mut dyn Storage, name: &str, delta: i64) -> StdResult<()> {
+    ENTRY_COUNTS.update(store, name, |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default().saturating_add_signed(delta))
+    })?;
+    Ok(())
+}
@@ -1,14 +1,43 @@
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, MintableResponse, QueryMsg, Receiver, TokenInfoResponse,
+    AllowlistResponse, AssetlistRegistration, BucketsResponse, CanMintResponse,
+    ContractInfoResponse, DenomInfo, DenomMetadata, DenomPreviewResponse, DenomsResponse,
+    EmissionResponse, EventsSchemaResponse, ExecuteMsg, InstantiateMsg, InstantiateResponse,
+    MaxWalletMsg, MintCostResponse, MintableResponse, MultisigMsg, PhaseResponse, PreLaunch,
+    PresaleMsg, PriceResponse, ProceedsVestingResponse, ProposalResponse, PurchasesResponse,
+    QueryMsg, Receiver, RolesResponse, SendPolicyResponse, StakesResponse, StorageStatsResponse,
+    SupportedInterfacesResponse, TakerFeeProposalResponse, TokenInfoResponse, TransferBurnMsg,
+    TransferTaxMsg, VestingResponse, Voter,
 };
-use cosmwasm_std::{Coin, Uint128};
+use crate::state::{
+    Allowance, AntiSnipe, ClPool, Curve, DeadManSwitch, Distribution, Emission, Governance,
+    HistoryEntry, LockTier, MaxWallet, MintFee, MintTax, PendingRevoke, Presale, ProceedsVesting,
+    ProjectInfo, PublicMint, QueuedAction, Role, Staking, Stats, SymbolRules, TransferTax,
+    VestingSchedule,
+};
+use assetlist::{
+    msg::{
+        ExecuteMsg as AssetlistExecuteMsg, InstantiateMsg as AssetlistInstantiateMsg,
+        ListingMsg as AssetlistListingMsg, ListingQuery, QueryMsg as AssetlistQueryMsg,
+    },
+    state::{Config as AssetlistConfig, Metadata},
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, to_json_vec, Addr, Binary, Coin, Decimal, Decimal256, HexBinary,
+    Timestamp, Uint128,
+};
+use cw_utils::{Duration, Threshold};
 use osmosis_test_tube::{
     osmosis_std::types::{
-        cosmos::bank::v1beta1::QueryBalanceRequest,
-        osmosis::tokenfactory::v1beta1::QueryDenomAuthorityMetadataRequest,
+        cosmos::{
+            bank::v1beta1::{MsgSend, QueryBalanceRequest},
+            base::v1beta1::Coin as ProtoCoin,
+        },
+        cosmwasm::wasm::v1::{MsgInstantiateContract2, MsgInstantiateContract2Response},
+        osmosis::tokenfactory::v1beta1::{MsgMint, QueryDenomAuthorityMetadataRequest},
     },
-    Account, Bank, Module, OsmosisTestApp, SigningAccount, TokenFactory, Wasm,
+    Account, Bank, Module, OsmosisTestApp, Runner, SigningAccount, TokenFactory, Wasm,
 };
+use sha2::{Digest, Sha256};
 
 struct TestEnv {
     app: OsmosisTestApp,
@@ -32,14 +61,77 @@ fn get_modules(test_env: &'_ TestEnv) -> Modules<'_> {
     }
 }
 
+fn balance(test_env: &TestEnv, address: &str, denom: &str) -> u128 {
+    get_modules(test_env)
+        .bank
+        .query_balance(&QueryBalanceRequest {
+            address: address.to_string(),
+            denom: denom.to_string(),
+        })
+        .unwrap()
+        .balance
+        .unwrap()
+        .amount
+        .parse::<u128>()
+        .unwrap()
+}
+
+// the escrow of transfer channel-0 on the chain of `address`, where ibc transfers of the token go
+fn escrow(address: &str) -> String {
+    crate::helpers::ibc_escrow_address(address, "transfer", "channel-0").unwrap()
+}
+
 fn instantiate_contract(initial_supply: Uint128, max_supply: Uint128) -> TestEnv {
+    instantiate_with(|_, _| InstantiateMsg {
+        initial_supply: Some(initial_supply),
+        max_supply: Some(max_supply),
+        ..base_msg()
+    })
+}
+
+// a TEST token with every option left unset, for tests to override the fields they exercise
+fn base_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        symbol: "TEST".to_string(),
+        subdenom: None,
+        tfa_prefix: None,
+        symbol_rules: None,
+        initial_supply: None,
+        max_supply: None,
+        admin: None,
+        pre_launch: None,
+        timelock: None,
+        mint_start: None,
+        mint_end: None,
+        mint_fee: None,
+        dead_man_switch: None,
+        exponent: None,
+        public_mint: None,
+        proceeds_vesting: None,
+        presale: None,
+        assetlist: None,
+        clawback: None,
+        null_address: None,
+        max_wallet: None,
+        transfer_burn: None,
+        project_info: None,
+        multisig: None,
+    }
+}
+
+// instantiates the contract with a message built from the fresh app and its funded user accounts
+fn instantiate_with(
+    build_msg: impl FnOnce(&OsmosisTestApp, &[SigningAccount]) -> InstantiateMsg,
+) -> TestEnv {
     let app = OsmosisTestApp::new();
 
     let admin = app
         .init_account(&[Coin::new(1_000_000_000_000, "uosmo")])
         .unwrap();
 
-    let users: Vec<SigningAccount> = app.init_accounts(&[], 2).unwrap();
+    let users: Vec<SigningAccount> = app
+        .init_accounts(&[Coin::new(1_000_000_000, "uosmo")], 2)
+        .unwrap();
 
     let mut test_env = TestEnv {
         app,
@@ -49,6 +141,8 @@ fn instantiate_contract(initial_supply: Uint128, max_supply: Uint128) -> TestEnv
         denom: String::default(),
     };
 
+    let msg = build_msg(&test_env.app, &test_env.users);
+
     let modules = get_modules(&test_env);
 
     let wasm_byte_code = std::fs::read("../../target/wasm32-unknown-unknown/release/factory.wasm")
@@ -65,12 +159,7 @@ fn instantiate_contract(initial_supply: Uint128, max_supply: Uint128) -> TestEnv
         .wasm
         .instantiate(
             code_id,
-            &InstantiateMsg {
-                symbol: "TEST".to_string(),
-                initial_supply: Some(initial_supply),
-                max_supply: Some(max_supply),
-                admin: None,
-            },
+            &msg,
             Some(&test_env.admin.address()),
             Some("test"),
             &[],
@@ -81,11 +170,62 @@ fn instantiate_contract(initial_supply: Uint128, max_supply: Uint128) -> TestEnv
         .address;
 
     test_env.contract_addr = contract_addr.clone();
-    test_env.denom = format!("factory/{}/tfa/TEST", contract_addr);
+    test_env.denom = crate::helpers::denom(
+        &contract_addr,
+        &msg.symbol,
+        msg.subdenom.as_deref(),
+        msg.tfa_prefix.unwrap_or_default(),
+    );
 
     test_env
 }
 
+// deploys an assetlist contract charging the given listing fee
+fn instantiate_assetlist(test_env: &TestEnv, fee: Coin) -> String {
+    let modules = get_modules(test_env);
+
+    let wasm_byte_code =
+        std::fs::read("../../target/wasm32-unknown-unknown/release/assetlist.wasm")
+            .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+
+    let code_id = modules
+        .wasm
+        .store_code(&wasm_byte_code, None, &test_env.admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    modules
+        .wasm
+        .instantiate(
+            code_id,
+            &AssetlistInstantiateMsg(AssetlistConfig {
+                add_permissioned: None,
+                remove_permissioned: None,
+                required_fields: None,
+                fee: Some(vec![fee]),
+                deposit: None,
+                update_fee: None,
+                renewal_fee: None,
+                listing_ttl: None,
+                fee_collector: None,
+                admins: None,
+                owner: None,
+                confirm_threshold: None,
+                confirm_period: None,
+                review: None,
+                extension_limits: None,
+            }),
+            Some(&test_env.admin.address()),
+            Some("assetlist"),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap()
+        .data
+        .address
+}
+
 #[test]
 fn test_instantiate() {
     // instantiate the contract with 100 initial supply
@@ -125,6 +265,344 @@ fn test_instantiate() {
     assert_eq!(balance, 0u128);
 }
 
+#[test]
+fn test_custom_subdenom() {
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        subdenom: Some("test".to_string()),
+        initial_supply: Some(Uint128::from(100u128)),
+        ..base_msg()
+    });
+
+    let res: TokenInfoResponse = get_modules(&test_env)
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(
+        res.denom,
+        format!("factory/{}/test", test_env.contract_addr)
+    );
+    assert_eq!(balance(&test_env, &test_env.contract_addr, &res.denom), 100);
+}
+
+#[test]
+fn test_tfa_prefix() {
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        tfa_prefix: Some(true),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    // the token opted in to the tfa/ subdenom
+    assert_eq!(
+        test_env.denom,
+        format!("factory/{}/tfa/TEST", test_env.contract_addr)
+    );
+
+    let res = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(100u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert!(res.events.iter().any(|event| event.ty == "wasm-tfa-mint"));
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        100
+    );
+}
+
+#[test]
+fn test_symbol_validation() {
+    let app = OsmosisTestApp::new();
+    let admin = app
+        .init_account(&[Coin::new(1_000_000_000_000, "uosmo")])
+        .unwrap();
+    let wasm = Wasm::new(&app);
+
+    let wasm_byte_code = std::fs::read("../../target/wasm32-unknown-unknown/release/factory.wasm")
+        .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+    let code_id = wasm
+        .store_code(&wasm_byte_code, None, &admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    let instantiate = |symbol: &str, symbol_rules: Option<SymbolRules>| {
+        wasm.instantiate(
+            code_id,
+            &InstantiateMsg {
+                symbol: symbol.to_string(),
+                symbol_rules,
+                ..base_msg()
+            },
+            None,
+            Some("test"),
+            &[],
+            &admin,
+        )
+    };
+
+    // a slash would split the subdenom, and the default rules only allow letters and digits
+    assert!(instantiate("TE/ST", None).is_err());
+    assert!(instantiate("TE.ST", None).is_err());
+    assert!(instantiate("T", None).is_err());
+    assert!(instantiate("TEST", None).is_ok());
+
+    let rules = SymbolRules {
+        min_length: 1,
+        max_length: 8,
+        extra_chars: ".".to_string(),
+    };
+
+    assert!(instantiate("T.S", Some(rules.clone())).is_ok());
+    assert!(instantiate("T.S.T.S.T", Some(rules)).is_err());
+}
+
+#[test]
+fn test_multisig_validation() {
+    let app = OsmosisTestApp::new();
+    let accounts = app
+        .init_accounts(&[Coin::new(1_000_000_000_000, "uosmo")], 2)
+        .unwrap();
+    let admin = &accounts[0];
+    let wasm = Wasm::new(&app);
+
+    let wasm_byte_code = std::fs::read("../../target/wasm32-unknown-unknown/release/factory.wasm")
+        .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+    let code_id = wasm
+        .store_code(&wasm_byte_code, None, admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    let voters = vec![
+        Voter {
+            addr: accounts[0].address(),
+            weight: 1,
+        },
+        Voter {
+            addr: accounts[1].address(),
+            weight: 1,
+        },
+    ];
+
+    let instantiate = |admin_addr: Option<Addr>, voters: Vec<Voter>, weight: u64| {
+        wasm.instantiate(
+            code_id,
+            &InstantiateMsg {
+                admin: admin_addr,
+                multisig: Some(MultisigMsg {
+                    code_id,
+                    voters,
+                    threshold: Threshold::AbsoluteCount { weight },
+                    max_voting_period: Duration::Time(3600),
+                }),
+                ..base_msg()
+            },
+            None,
+            Some("test"),
+            &[],
+            admin,
+        )
+    };
+
+    // the multisig is the only owner, so an admin can't be given as well
+    assert!(instantiate(Some(Addr::unchecked(admin.address())), voters.clone(), 2).is_err());
+    // the threshold has to be reachable by the voters
+    assert!(instantiate(None, voters.clone(), 3).is_err());
+    assert!(instantiate(None, vec![], 1).is_err());
+    let mut zero_weight = voters;
+    zero_weight[1].weight = 0;
+    assert!(instantiate(None, zero_weight, 1).is_err());
+}
+
+#[test]
+fn test_creation_fee_refund() {
+    let app = OsmosisTestApp::new();
+    let admin = app
+        .init_account(&[Coin::new(1_000_000_000_000, "uosmo")])
+        .unwrap();
+    let wasm = Wasm::new(&app);
+
+    let wasm_byte_code = std::fs::read("../../target/wasm32-unknown-unknown/release/factory.wasm")
+        .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+    let code_id = wasm
+        .store_code(&wasm_byte_code, None, &admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    // funds beyond the chain's denom creation fee are returned to the sender
+    let contract_addr = wasm
+        .instantiate(
+            code_id,
+            &base_msg(),
+            None,
+            Some("test"),
+            &[Coin::new(100_000_000, "uosmo")],
+            &admin,
+        )
+        .unwrap()
+        .data
+        .address;
+
+    let balance = Bank::new(&app)
+        .query_balance(&QueryBalanceRequest {
+            address: contract_addr,
+            denom: "uosmo".to_string(),
+        })
+        .unwrap()
+        .balance
+        .unwrap()
+        .amount;
+
+    assert_eq!(balance, "0");
+}
+
+#[test]
+fn test_instantiate_listing() {
+    let test_env = instantiate_contract(Uint128::zero(), Uint128::from(1_000u128));
+    let assetlist = instantiate_assetlist(&test_env, Coin::new(1_000_000, "uosmo"));
+    let modules = get_modules(&test_env);
+
+    let wasm_byte_code = std::fs::read("../../target/wasm32-unknown-unknown/release/factory.wasm")
+        .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+    let code_id = modules
+        .wasm
+        .store_code(&wasm_byte_code, None, &test_env.admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    let metadata = Metadata {
+        symbol: "LIST".to_string(),
+        exp: Some(6),
+        logo: None,
+        chain: Some("osmosis-1".to_string()),
+        extensions: None,
+    };
+    let msg = InstantiateMsg {
+        symbol: "LIST".to_string(),
+        assetlist: Some(AssetlistRegistration {
+            contract: assetlist.clone(),
+            metadata: metadata.clone(),
+            fee: vec![Coin::new(1_000_000, "uosmo")],
+        }),
+        ..base_msg()
+    };
+
+    // the listing fee has to be attached
+    let res = modules
+        .wasm
+        .instantiate(code_id, &msg, None, Some("list"), &[], &test_env.admin);
+
+    assert!(res.is_err());
+
+    let contract_addr = modules
+        .wasm
+        .instantiate(
+            code_id,
+            &msg,
+            None,
+            Some("list"),
+            &[Coin::new(1_000_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap()
+        .data
+        .address;
+
+    let denom = format!("factory/{}/LIST", contract_addr);
+    let res: Vec<(String, Metadata)> = modules
+        .wasm
+        .query(
+            &assetlist,
+            &AssetlistQueryMsg::Listing(ListingQuery::Denom(vec![denom])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1, metadata);
+}
+
+#[test]
+fn test_denom_preview() {
+    let test_env = instantiate_contract(Uint128::zero(), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let wasm_byte_code = std::fs::read("../../target/wasm32-unknown-unknown/release/factory.wasm")
+        .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+    let code_id = modules
+        .wasm
+        .store_code(&wasm_byte_code, None, &test_env.admin)
+        .unwrap()
+        .data
+        .code_id;
+
+    let preview: DenomPreviewResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::DenomPreview {
+                creator: test_env.admin.address(),
+                checksum: HexBinary::from(Sha256::digest(&wasm_byte_code).to_vec()),
+                salt: Binary::from(b"launch".as_slice()),
+                symbol: "NEXT".to_string(),
+                subdenom: None,
+                tfa_prefix: None,
+            },
+        )
+        .unwrap();
+
+    // deploy with instantiate2 and check the token matches the preview
+    let res = test_env
+        .app
+        .execute::<MsgInstantiateContract2, MsgInstantiateContract2Response>(
+            MsgInstantiateContract2 {
+                sender: test_env.admin.address(),
+                admin: test_env.admin.address(),
+                code_id,
+                label: "next".to_string(),
+                msg: to_json_vec(&InstantiateMsg {
+                    symbol: "NEXT".to_string(),
+                    ..base_msg()
+                })
+                .unwrap(),
+                funds: vec![],
+                salt: b"launch".to_vec(),
+                fix_msg: false,
+            },
+            MsgInstantiateContract2::TYPE_URL,
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(preview.address, Addr::unchecked(res.data.address.clone()));
+
+    // the instantiate response data carries the denom for the instantiating contract
+    let data: InstantiateResponse = from_json(&res.data.data).unwrap();
+    assert_eq!(data.denom, preview.denom);
+    assert_eq!(data.symbol, "NEXT");
+    assert_eq!(data.admin, Addr::unchecked(test_env.admin.address()));
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&res.data.address, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.denom, preview.denom);
+}
+
 #[test]
 fn mint_burn() {
     let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
@@ -140,10 +618,12 @@ fn mint_burn() {
                 Receiver {
                     address: test_env.users[0].address(),
                     amount: Uint128::from(100u128),
+                    display_units: None,
                 },
                 Receiver {
                     address: test_env.users[1].address(),
                     amount: Uint128::from(100u128),
+                    display_units: None,
                 },
             ]),
             &[],
@@ -165,6 +645,7 @@ fn mint_burn() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(1u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -186,7 +667,7 @@ fn mint_burn() {
         .wasm
         .execute(
             &test_env.contract_addr,
-            &ExecuteMsg::Burn(Uint128::from(100u128)),
+            &ExecuteMsg::Burn(Some(Uint128::from(100u128))),
             &[],
             &test_env.admin,
         )
@@ -211,55 +692,163 @@ fn mint_burn() {
 }
 
 #[test]
-fn test_revoke() {
+fn test_burn_all() {
     let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
 
     let modules = get_modules(&test_env);
 
-    // mint 50 each tokens to 2 test addresses
-
+    // burn whatever the contract holds without naming an amount
     let _ = modules
         .wasm
         .execute(
             &test_env.contract_addr,
-            &ExecuteMsg::Mint(vec![
-                Receiver {
-                    address: test_env.users[0].address(),
-                    amount: Uint128::from(50u128),
-                },
-                Receiver {
-                    address: test_env.users[1].address(),
-                    amount: Uint128::from(50u128),
-                },
-            ]),
+            &ExecuteMsg::Burn(None),
             &[],
             &test_env.admin,
         )
         .unwrap();
 
-    // check that the minted total is now 200
     let res: TokenInfoResponse = modules
         .wasm
         .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
         .unwrap();
 
-    assert_eq!(res.minted, Uint128::from(200u128));
+    assert_eq!(res.current_supply, Uint128::zero());
+
+    // there is nothing left to burn
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Burn(None),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_burn_funds() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
 
-    // revoke token admin
     let _ = modules
         .wasm
         .execute(
             &test_env.contract_addr,
-            &ExecuteMsg::Revoke,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(50u128),
+                display_units: None,
+            }]),
             &[],
             &test_env.admin,
         )
         .unwrap();
 
-    // check that the mintable query shows that the admin has been revoked and the cap is not reached
-    let res: MintableResponse = modules
-        .wasm
-        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+    // only the token can be burned
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::BurnFunds,
+        &[Coin::new(10, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let res = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::BurnFunds,
+            &[Coin::new(20, &test_env.denom)],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert!(res.events.iter().any(
+        |event| event
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "burner"
+                && attribute.value == test_env.users[0].address())
+    ));
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        30
+    );
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.burned, Uint128::from(20u128));
+
+    // holders can't burn from their wallets through the token admin, only owners can
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::BurnFromWallet(Uint128::from(10u128)),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_revoke() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // mint 50 each tokens to 2 test addresses
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![
+                Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(50u128),
+                    display_units: None,
+                },
+                Receiver {
+                    address: test_env.users[1].address(),
+                    amount: Uint128::from(50u128),
+                    display_units: None,
+                },
+            ]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // check that the minted total is now 200
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(200u128));
+
+    // revoke token admin
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Revoke,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // check that the mintable query shows that the admin has been revoked and the cap is not reached
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
         .unwrap();
 
     assert!(res.revoked);
@@ -272,6 +861,7 @@ fn test_revoke() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(1u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -282,7 +872,7 @@ fn test_revoke() {
     // try to burn 1 token, should fail
     let res = modules.wasm.execute(
         &test_env.contract_addr,
-        &ExecuteMsg::Burn(Uint128::from(1u128)),
+        &ExecuteMsg::Burn(Some(Uint128::from(1u128))),
         &[],
         &test_env.admin,
     );
@@ -303,6 +893,36 @@ fn test_revoke() {
     assert_eq!(new_admin, "osmo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqmcn030");
 }
 
+#[test]
+fn test_handover_to_gov() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::HandoverToGov { gov_module: None },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let new_admin = modules
+        .tf
+        .query_denom_authority_metadata(&QueryDenomAuthorityMetadataRequest {
+            denom: test_env.denom.clone(),
+        })
+        .unwrap()
+        .authority_metadata
+        .unwrap()
+        .admin;
+
+    // the osmosis gov module account
+    assert_eq!(new_admin, "osmo10d07y265gmmuvt4z0w9aw880jnsr700jjeq4qp");
+}
+
 #[test]
 fn test_cap() {
     let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
@@ -318,10 +938,12 @@ fn test_cap() {
                 Receiver {
                     address: test_env.users[0].address(),
                     amount: Uint128::from(100u128),
+                    display_units: None,
                 },
                 Receiver {
                     address: test_env.users[1].address(),
                     amount: Uint128::from(100u128),
+                    display_units: None,
                 },
             ]),
             &[],
@@ -351,6 +973,7 @@ fn test_cap() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(1u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -363,7 +986,7 @@ fn test_cap() {
         .wasm
         .execute(
             &test_env.contract_addr,
-            &ExecuteMsg::Burn(Uint128::from(100u128)),
+            &ExecuteMsg::Burn(Some(Uint128::from(100u128))),
             &[],
             &test_env.admin,
         )
@@ -375,6 +998,7 @@ fn test_cap() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(1u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -407,6 +1031,7 @@ fn test_cap() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(101u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -422,6 +1047,7 @@ fn test_cap() {
             &ExecuteMsg::Mint(vec![Receiver {
                 address: test_env.users[0].address(),
                 amount: Uint128::from(100u128),
+                display_units: None,
             }]),
             &[],
             &test_env.admin,
@@ -474,6 +1100,7 @@ fn test_cap() {
             &ExecuteMsg::Mint(vec![Receiver {
                 address: test_env.users[0].address(),
                 amount: Uint128::from(100u128),
+                display_units: None,
             }]),
             &[],
             &test_env.admin,
@@ -497,12 +1124,287 @@ fn test_cap() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(1u128),
+            display_units: None,
+        }]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_failed_mint_rollback() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // module accounts can't receive funds, so the mint to the gov module fails the whole batch
+    modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![
+                Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(100u128),
+                    display_units: None,
+                },
+                Receiver {
+                    address: "osmo10d07y265gmmuvt4z0w9aw880jnsr700jjeq4qp".to_string(),
+                    amount: Uint128::from(100u128),
+                    display_units: None,
+                },
+            ]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        0
+    );
+
+    // neither mint is counted
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(100u128));
+}
+
+#[test]
+fn test_can_mint() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let can_mint = |receivers: Vec<Receiver>| -> CanMintResponse {
+        get_modules(&test_env)
+            .wasm
+            .query(
+                &test_env.contract_addr,
+                &QueryMsg::CanMint {
+                    receivers,
+                    bucket: None,
+                },
+            )
+            .unwrap()
+    };
+
+    let res = can_mint(vec![
+        Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(150u128),
+            display_units: None,
+        },
+        Receiver {
+            address: "invalid".to_string(),
+            amount: Uint128::from(10u128),
+            display_units: None,
+        },
+        Receiver {
+            address: test_env.users[1].address(),
+            amount: Uint128::zero(),
+            display_units: None,
+        },
+    ]);
+
+    assert_eq!(
+        res.receivers
+            .iter()
+            .map(|receiver| receiver.valid)
+            .collect::<Vec<_>>(),
+        vec![true, false, false]
+    );
+    assert_eq!(res.total, Uint128::from(150u128));
+    assert_eq!(res.total_minted, Uint128::from(250u128));
+    assert!(res.within_cap);
+    assert!(!res.valid);
+
+    // 201 more would exceed the cap of 300
+    let res = can_mint(vec![Receiver {
+        address: test_env.users[0].address(),
+        amount: Uint128::from(201u128),
+        display_units: None,
+    }]);
+
+    assert!(!res.within_cap);
+    assert!(!res.valid);
+
+    // the tax counts towards the cap
+    get_modules(&test_env)
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetMintTax(Some(MintTax {
+                rate: Decimal::percent(10),
+                treasury: Addr::unchecked(test_env.users[1].address()),
+            })),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res = can_mint(vec![Receiver {
+        address: test_env.users[0].address(),
+        amount: Uint128::from(190u128),
+        display_units: None,
+    }]);
+
+    assert_eq!(res.tax, Uint128::from(19u128));
+    assert_eq!(res.total_minted, Uint128::from(309u128));
+    assert!(!res.within_cap);
+    assert!(res.within_bucket);
+    assert!(res.open);
+}
+
+#[test]
+fn test_multi() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let batch = ExecuteMsg::Multi(vec![
+        ExecuteMsg::UpdateSupply(Uint128::from(500u128)),
+        ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(350u128),
+            display_units: None,
         }]),
+        ExecuteMsg::Send(vec![Receiver {
+            address: test_env.users[1].address(),
+            amount: Uint128::from(50u128),
+            display_units: None,
+        }]),
+    ]);
+
+    // every action is authorized on its own, so a user can't batch owner actions
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &batch, &[], &test_env.users[0]);
+
+    assert!(res.is_err());
+
+    // the mint only fits under the cap because the supply is raised first
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &batch, &[], &test_env.admin)
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        350
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        50
+    );
+
+    // batches can't be nested
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Multi(vec![ExecuteMsg::Multi(vec![])]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_sweep() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let sweep = ExecuteMsg::Sweep {
+        to: test_env.users[0].address(),
+    };
+
+    // only an owner can sweep
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &sweep, &[], &test_env.users[0]);
+
+    assert!(res.is_err());
+
+    // the whole initial supply held by the contract is sent
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &sweep, &[], &test_env.admin)
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        100
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.contract_addr, &test_env.denom),
+        0
+    );
+
+    // nothing is left to sweep
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &sweep, &[], &test_env.admin);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_rescue() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // a user sends OSMO to the contract by mistake
+    modules
+        .bank
+        .send(
+            MsgSend {
+                from_address: test_env.users[0].address(),
+                to_address: test_env.contract_addr.clone(),
+                amount: vec![ProtoCoin {
+                    denom: "uosmo".to_string(),
+                    amount: "1000".to_string(),
+                }],
+            },
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // the token itself can't be rescued
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Rescue {
+            denom: test_env.denom.clone(),
+            to: test_env.admin.address(),
+        },
         &[],
         &test_env.admin,
     );
 
     assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Rescue {
+                denom: "uosmo".to_string(),
+                to: test_env.users[1].address(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), "uosmo"),
+        1_000_001_000
+    );
+    assert_eq!(balance(&test_env, &test_env.contract_addr, "uosmo"), 0);
 }
 
 #[test]
@@ -517,6 +1419,7 @@ fn test_invalid_messages() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: "invalid_address".to_string(),
             amount: Uint128::from(100u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -530,6 +1433,7 @@ fn test_invalid_messages() {
         &ExecuteMsg::Mint(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(0u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -543,6 +1447,7 @@ fn test_invalid_messages() {
         &ExecuteMsg::Send(vec![Receiver {
             address: "invalid_address".to_string(),
             amount: Uint128::from(100u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -556,6 +1461,7 @@ fn test_invalid_messages() {
         &ExecuteMsg::Send(vec![Receiver {
             address: test_env.users[0].address(),
             amount: Uint128::from(0u128),
+            display_units: None,
         }]),
         &[],
         &test_env.admin,
@@ -563,3 +1469,5416 @@ fn test_invalid_messages() {
 
     assert!(res.is_err());
 }
+
+#[test]
+fn test_pre_launch_allowlist() {
+    // only user[0] can receive tokens until trading starts
+    let test_env = instantiate_with(|app, users| InstantiateMsg {
+        initial_supply: Some(Uint128::from(1_00u128)),
+        max_supply: Some(Uint128::from(1_000u128)),
+        pre_launch: Some(PreLaunch {
+            trading_start: app.get_block_timestamp().plus_seconds(86_400),
+            allowlist: vec![users[0].address()],
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+    let res: AllowlistResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Allowlist {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.addresses.len(), 1);
+    assert_eq!(res.addresses[0].as_str(), test_env.users[0].address());
+
+    // minting to an allowlisted address passes
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(100u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // minting to an address that is not allowlisted fails
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[1].address(),
+            amount: Uint128::from(100u128),
+            display_units: None,
+        }]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // neither can the contract send to it
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Send(vec![Receiver {
+            address: test_env.users[1].address(),
+            amount: Uint128::from(10u128),
+            display_units: None,
+        }]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // user[0] cannot pass tokens on to user[1] before trading starts
+    let send_msg = MsgSend {
+        from_address: test_env.users[0].address(),
+        to_address: test_env.users[1].address(),
+        amount: vec![ProtoCoin {
+            denom: test_env.denom.clone(),
+            amount: "10".to_string(),
+        }],
+    };
+
+    let res = modules.bank.send(send_msg.clone(), &test_env.users[0]);
+
+    assert!(res.is_err());
+
+    // once trading starts the restriction is lifted automatically
+    test_env.app.increase_time(86_400);
+
+    let _ = modules.bank.send(send_msg, &test_env.users[0]).unwrap();
+}
+
+#[test]
+fn test_max_wallet() {
+    // nobody but user[1] can hold more than 100 for the first day
+    let test_env = instantiate_with(|app, users| InstantiateMsg {
+        initial_supply: Some(Uint128::from(1_00u128)),
+        max_supply: Some(Uint128::from(1_000u128)),
+        max_wallet: Some(MaxWalletMsg {
+            max_wallet: MaxWallet {
+                amount: Uint128::from(100u128),
+                until: app.get_block_timestamp().plus_seconds(86_400),
+            },
+            exempt: vec![users[1].address()],
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint = |address: String, amount: u128| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address,
+                amount: Uint128::from(amount),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = mint(test_env.users[0].address(), 100).unwrap();
+
+    let res = mint(test_env.users[0].address(), 1);
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("cannot hold more than"));
+
+    let _ = mint(test_env.users[1].address(), 500).unwrap();
+
+    // transfers are capped the same way
+    let send_msg = MsgSend {
+        from_address: test_env.users[1].address(),
+        to_address: test_env.users[0].address(),
+        amount: vec![ProtoCoin {
+            denom: test_env.denom.clone(),
+            amount: "50".to_string(),
+        }],
+    };
+
+    let res = modules.bank.send(send_msg.clone(), &test_env.users[1]);
+
+    assert!(res.is_err());
+
+    // until the launch period is over
+    test_env.app.increase_time(86_400);
+
+    let _ = modules.bank.send(send_msg, &test_env.users[1]).unwrap();
+    let _ = mint(test_env.users[0].address(), 100).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        250
+    );
+}
+
+#[test]
+fn test_transfer_burn() {
+    // 10% of every transfer is burned, except through the ibc escrow of channel-0
+    let test_env = instantiate_with(|_, users| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000u128)),
+        transfer_burn: Some(TransferBurnMsg {
+            rate: Decimal::percent(10),
+            exempt: vec![escrow(&users[0].address())],
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    // mints are not burned
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(500u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the admin is not exempt, and stands in for a third user
+    let accounts = [&test_env.users[0], &test_env.users[1], &test_env.admin];
+    let send = |from: usize, to: usize, amount: u128| {
+        modules.bank.send(
+            MsgSend {
+                from_address: accounts[from].address(),
+                to_address: accounts[to].address(),
+                amount: vec![ProtoCoin {
+                    denom: test_env.denom.clone(),
+                    amount: amount.to_string(),
+                }],
+            },
+            accounts[from],
+        )
+    };
+    let transfer_funds = |from: usize, to: usize, amount: u128| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::TransferFunds {
+                recipient: accounts[to].address(),
+            },
+            &[Coin::new(amount, &test_env.denom)],
+            accounts[from],
+        )
+    };
+    let info = || -> TokenInfoResponse {
+        modules
+            .wasm
+            .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+            .unwrap()
+    };
+
+    // direct transfers would skip the burn
+    assert!(send(0, 1, 100).is_err());
+
+    // so they go through the contract, which burns the share on the way
+    let _ = transfer_funds(0, 1, 100).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        400
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        90
+    );
+
+    let info_res = info();
+    assert_eq!(info_res.transfer_burned, Uint128::from(10u128));
+    assert_eq!(info_res.current_supply, Uint128::from(490u128));
+
+    // only the token can be attached
+    assert!(modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::TransferFunds {
+                recipient: test_env.users[1].address(),
+            },
+            &[Coin::new(100, "uosmo")],
+            &test_env.users[0],
+        )
+        .is_err());
+
+    // only an owner can exempt addresses, like pools and routers
+    let exempt_msg = ExecuteMsg::UpdateTransferFeeExempt {
+        add: vec![test_env.users[1].address()],
+        remove: vec![],
+    };
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &exempt_msg,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &exempt_msg, &[], &test_env.admin)
+        .unwrap();
+
+    // transfers from or to an exempt address are free, and can be sent directly
+    let _ = send(0, 1, 100).unwrap();
+    let _ = transfer_funds(1, 0, 50).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        350
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        140
+    );
+    assert_eq!(info().transfer_burned, Uint128::from(10u128));
+
+    assert!(send(0, 2, 100).is_err());
+
+    // the escrow exempted at instantiation can be sent to directly, so ibc transfers keep working
+    let escrow = escrow(&test_env.contract_addr);
+    let _ = modules
+        .bank
+        .send(
+            MsgSend {
+                from_address: test_env.users[0].address(),
+                to_address: escrow.clone(),
+                amount: vec![ProtoCoin {
+                    denom: test_env.denom.clone(),
+                    amount: "100".to_string(),
+                }],
+            },
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(balance(&test_env, &escrow, &test_env.denom), 100);
+
+    // only an owner can turn it off, and it stays off
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::DisableTransferBurn,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::DisableTransferBurn,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = send(0, 2, 100).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.admin.address(), &test_env.denom),
+        100
+    );
+
+    let info_res = info();
+    assert_eq!(info_res.transfer_burn, None);
+    assert_eq!(info_res.transfer_burned, Uint128::from(10u128));
+}
+
+#[test]
+fn test_transfer_tax() {
+    let test_env = instantiate_contract(Uint128::zero(), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(500u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let send = |from: usize, to: usize, amount: u128| {
+        modules.bank.send(
+            MsgSend {
+                from_address: test_env.users[from].address(),
+                to_address: test_env.users[to].address(),
+                amount: vec![ProtoCoin {
+                    denom: test_env.denom.clone(),
+                    amount: amount.to_string(),
+                }],
+            },
+            &test_env.users[from],
+        )
+    };
+    let transfer_funds = |from: usize, to: usize, amount: u128| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::TransferFunds {
+                recipient: test_env.users[to].address(),
+            },
+            &[Coin::new(amount, &test_env.denom)],
+            &test_env.users[from],
+        )
+    };
+    let set_tax = |treasury: Option<String>, exempt: Vec<String>, sender: &SigningAccount| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetTransferTax(Some(TransferTaxMsg {
+                transfer_tax: TransferTax {
+                    rate: Decimal::percent(10),
+                    treasury: treasury.map(Addr::unchecked),
+                },
+                exempt,
+            })),
+            &[],
+            sender,
+        )
+    };
+
+    // only an owner can set it
+    assert!(set_tax(Some(test_env.admin.address()), vec![], &test_env.users[0]).is_err());
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::SetTransferTax(Some(TransferTaxMsg {
+            transfer_tax: TransferTax {
+                rate: Decimal::one(),
+                treasury: None,
+            },
+            exempt: vec![],
+        })),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let _ = set_tax(Some(test_env.admin.address()), vec![], &test_env.admin).unwrap();
+
+    // direct transfers would skip the tax
+    assert!(send(0, 1, 100).is_err());
+
+    // so they go through the contract, which pays the tax to the treasury
+    let _ = transfer_funds(0, 1, 100).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        90
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.admin.address(), &test_env.denom),
+        10
+    );
+
+    // without a treasury it is burned
+    let _ = set_tax(None, vec![], &test_env.admin).unwrap();
+
+    let _ = transfer_funds(0, 1, 100).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        300
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        180
+    );
+
+    let info: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(info.current_supply, Uint128::from(490u128));
+
+    // ibc transfers move the token into the channel's escrow, which is rejected like any other
+    // direct transfer until the escrow is exempted along with the tax
+    let escrow = escrow(&test_env.contract_addr);
+    let escrow_send = || {
+        modules.bank.send(
+            MsgSend {
+                from_address: test_env.users[0].address(),
+                to_address: escrow.clone(),
+                amount: vec![ProtoCoin {
+                    denom: test_env.denom.clone(),
+                    amount: "100".to_string(),
+                }],
+            },
+            &test_env.users[0],
+        )
+    };
+
+    assert!(escrow_send().is_err());
+
+    let _ = set_tax(None, vec![escrow.clone()], &test_env.admin).unwrap();
+    let _ = escrow_send().unwrap();
+
+    assert_eq!(balance(&test_env, &escrow, &test_env.denom), 100);
+
+    // once it is renounced transfers are direct again, and it can't be set again
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::RenounceTransferTax,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = send(0, 1, 100).unwrap();
+    assert!(set_tax(None, vec![], &test_env.admin).is_err());
+
+    let transfer_tax: Option<TransferTax> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TransferTax)
+        .unwrap();
+
+    assert_eq!(transfer_tax, None);
+}
+
+#[test]
+fn test_reconcile() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // user[0] takes over the denom and mints outside the contract
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::RevokeTo {
+                new_admin: test_env.users[0].address(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .tf
+        .mint(
+            MsgMint {
+                sender: test_env.users[0].address(),
+                amount: Some(ProtoCoin {
+                    denom: test_env.denom.clone(),
+                    amount: "50".to_string(),
+                }),
+                mint_to_address: test_env.users[0].address(),
+            },
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // only an owner can reconcile
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Reconcile,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let res = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Reconcile,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-tfa-reconcile"
+            && event
+                .attributes
+                .iter()
+                .any(|attribute| attribute.key == "minted_delta" && attribute.value == "50")));
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(150u128));
+    assert_eq!(res.burned, Uint128::zero());
+
+    // minting past the cap out of band is flagged, and nothing more can be minted
+    let _ = modules
+        .tf
+        .mint(
+            MsgMint {
+                sender: test_env.users[0].address(),
+                amount: Some(ProtoCoin {
+                    denom: test_env.denom.clone(),
+                    amount: "200".to_string(),
+                }),
+                mint_to_address: test_env.users[0].address(),
+            },
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let res = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Reconcile,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-tfa-reconcile"
+            && event
+                .attributes
+                .iter()
+                .any(|attribute| attribute.key == "cap_exceeded" && attribute.value == "true")));
+
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(res.cap_reached);
+}
+
+#[test]
+fn test_update_symbol() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let update = |symbol: &str, sender: &SigningAccount| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateSymbol(symbol.to_string()),
+            &[],
+            sender,
+        )
+    };
+
+    // the new symbol follows the same rules
+    assert!(update("NEW SYMBOL", &test_env.admin).is_err());
+    assert!(update("NEW", &test_env.users[0]).is_err());
+
+    let res = update("NEW", &test_env.admin).unwrap();
+
+    assert!(res.events.iter().any(|event| event.ty == "wasm-tfa-symbol"));
+
+    // the denom stays the same
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.symbol, "NEW");
+    assert_eq!(res.denom, test_env.denom);
+}
+
+#[test]
+fn test_denom_metadata() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let publish = |name: &str, exponent: u32| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetDenomMetadata(DenomMetadata {
+                name: name.to_string(),
+                description: String::new(),
+                exponent,
+                uri: None,
+            }),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = publish("Test", 8).unwrap();
+
+    // the rest of the metadata can still be updated, but the exponent is fixed once published
+    let _ = publish("Test Token", 8).unwrap();
+    let res = publish("Test Token", 6);
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("exponent can't be changed"));
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.exponent, 8);
+}
+
+#[test]
+fn test_revoke_to() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // handing the admin back to the contract itself is rejected
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::RevokeTo {
+            new_admin: test_env.contract_addr.clone(),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // hand the token admin to user[0]
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::RevokeTo {
+                new_admin: test_env.users[0].address(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let new_admin = modules
+        .tf
+        .query_denom_authority_metadata(&QueryDenomAuthorityMetadataRequest {
+            denom: test_env.denom.clone(),
+        })
+        .unwrap()
+        .authority_metadata
+        .unwrap()
+        .admin;
+
+    assert_eq!(new_admin, test_env.users[0].address());
+
+    // the contract no longer controls the denom
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(res.revoked);
+}
+
+#[test]
+fn test_renounce_contract() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateDenom {
+                symbol: "GOLD".to_string(),
+                subdenom: None,
+                max_supply: Uint128::from(1_000u128),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateDistribution {
+                merkle_root: hex::encode([0u8; 32]),
+                snapshot_height: test_env.app.get_block_height() as u64,
+                total: Uint128::from(100u128),
+                expiry: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[Coin::new(1_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let renounce = ExecuteMsg::RenounceContract {
+        sweep_to: Some(test_env.users[1].address()),
+    };
+
+    // nobody could sweep what is left unclaimed once the owners are gone
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &renounce, &[], &test_env.admin);
+
+    assert!(res.unwrap_err().to_string().contains("must be swept"));
+
+    test_env.app.increase_time(86_400);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SweepDistribution {
+                id: 1,
+                to: test_env.admin.address(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &renounce, &[], &test_env.admin)
+        .unwrap();
+
+    // the treasury was swept, and the token admin revoked along with the contract
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        100
+    );
+
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(res.revoked);
+
+    // so are the admins of the further denoms
+    let admin = modules
+        .tf
+        .query_denom_authority_metadata(&QueryDenomAuthorityMetadataRequest {
+            denom: format!("factory/{}/GOLD", test_env.contract_addr),
+        })
+        .unwrap()
+        .authority_metadata
+        .unwrap()
+        .admin;
+
+    assert_ne!(admin, test_env.contract_addr);
+
+    let res: RolesResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Roles)
+        .unwrap();
+
+    assert!(res.owners.is_empty());
+
+    // the former owner can't act anymore
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Heartbeat,
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_null_address() {
+    let test_env = instantiate_with(|_, users| InstantiateMsg {
+        initial_supply: Some(Uint128::from(1_00u128)),
+        max_supply: Some(Uint128::from(1_000u128)),
+        null_address: Some(users[1].address()),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let res: String = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::NullAddress)
+        .unwrap();
+
+    assert_eq!(res, test_env.users[1].address());
+
+    // revoking hands the admin to the configured address instead of the zero address
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Revoke,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let new_admin = modules
+        .tf
+        .query_denom_authority_metadata(&QueryDenomAuthorityMetadataRequest {
+            denom: test_env.denom.clone(),
+        })
+        .unwrap()
+        .authority_metadata
+        .unwrap()
+        .admin;
+
+    assert_eq!(new_admin, test_env.users[1].address());
+}
+
+#[test]
+fn test_scheduled_revoke() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // schedule a revoke one day out
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ScheduleRevoke {
+                delay: 86_400,
+                new_admin: None,
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Option<PendingRevoke> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::PendingRevoke)
+        .unwrap();
+
+    assert!(res.is_some());
+
+    // confirming before the delay has passed fails
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ConfirmRevoke,
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // cancel, then confirming fails as nothing is scheduled
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CancelRevoke,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(86_400);
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ConfirmRevoke,
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // schedule again and confirm after the delay
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ScheduleRevoke {
+                delay: 3_600,
+                new_admin: None,
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(3_600);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ConfirmRevoke,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(res.revoked);
+
+    let res: Option<PendingRevoke> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::PendingRevoke)
+        .unwrap();
+
+    assert!(res.is_none());
+}
+
+#[test]
+fn test_events_schema() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let res: EventsSchemaResponse = get_modules(&test_env)
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::EventsSchema)
+        .unwrap();
+
+    assert_eq!(res.version, env!("CARGO_PKG_VERSION"));
+
+    let mint = res
+        .events
+        .iter()
+        .find(|event| event.action == "factory_mint")
+        .unwrap();
+
+    assert!(mint.attributes.contains(&"recipient".to_string()));
+
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.event_type == "tfa-mint" && event.action.is_empty()));
+}
+
+#[test]
+fn test_typed_events() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let res = get_modules(&test_env)
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(50u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // wasmd prefixes the type of custom events
+    let mint = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-tfa-mint")
+        .unwrap();
+    let value = |key: &str| {
+        mint.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.clone())
+            .unwrap()
+    };
+
+    assert_eq!(value("denom"), test_env.denom);
+    assert_eq!(value("recipient"), test_env.users[0].address());
+    assert_eq!(value("amount"), "50");
+    assert_eq!(value("new_total"), "150");
+}
+
+#[test]
+fn test_aggregate_receivers() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let res = get_modules(&test_env)
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![
+                Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(10u128),
+                    display_units: None,
+                },
+                Receiver {
+                    address: test_env.users[1].address(),
+                    amount: Uint128::from(20u128),
+                    display_units: None,
+                },
+                Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(15u128),
+                    display_units: None,
+                },
+            ]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the repeated receiver is minted to once, with the amounts added up
+    let mints: Vec<_> = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-tfa-mint")
+        .collect();
+
+    assert_eq!(mints.len(), 2);
+    assert!(mints[0]
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "amount" && attribute.value == "25"));
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        25
+    );
+}
+
+#[test]
+fn test_address_prefix() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // an address from another chain is reported with its index, instead of failing validation
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Send(vec![
+            Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(10u128),
+                display_units: None,
+            },
+            Receiver {
+                address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+                amount: Uint128::from(10u128),
+                display_units: None,
+            },
+        ]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains(
+        "Receiver 1 has the address cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu from another \
+         chain, expected the osmo prefix"
+    ));
+
+    // and the same check applies to the single address actions
+    let foreign = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string();
+    for msg in [
+        ExecuteMsg::IncreaseAllowance {
+            spender: foreign.clone(),
+            amount: Uint128::from(10u128),
+            expires: None,
+        },
+        ExecuteMsg::Sweep {
+            to: foreign.clone(),
+        },
+        ExecuteMsg::RevokeTo {
+            new_admin: foreign.clone(),
+        },
+        ExecuteMsg::SetEmission(Some(Emission {
+            recipient: Addr::unchecked(&foreign),
+            initial_reward: Uint128::from(10u128),
+            halving_interval: 86_400,
+            epochs: 1,
+            start: test_env.app.get_block_timestamp(),
+        })),
+        ExecuteMsg::AddRole {
+            role: Role::Minter,
+            address: foreign.clone(),
+        },
+        ExecuteMsg::UpdateSendPolicy {
+            enabled: None,
+            add: vec![foreign.clone()],
+            remove: vec![],
+        },
+        ExecuteMsg::UpdateTransferFeeExempt {
+            add: vec![foreign.clone()],
+            remove: vec![],
+        },
+    ] {
+        let res = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.admin);
+
+        assert!(res.unwrap_err().to_string().contains(
+            "The address cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu is from another chain, \
+             expected the osmo prefix"
+        ));
+    }
+
+    // the mint dry run flags it too
+    let res: CanMintResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::CanMint {
+                receivers: vec![Receiver {
+                    address: foreign,
+                    amount: Uint128::from(10u128),
+                    display_units: None,
+                }],
+                bucket: None,
+            },
+        )
+        .unwrap();
+
+    assert!(!res.receivers[0].valid);
+}
+
+#[test]
+fn test_history() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(50u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Burn(Some(Uint128::from(10u128))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // newest first, down to the initial mint
+    let res: Vec<(u64, HistoryEntry)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                start_before: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[0].1.action, "burn");
+    assert_eq!(res[0].1.amount, Uint128::from(10u128));
+    assert_eq!(res[1].1.action, "mint");
+    assert_eq!(res[1].1.address, Some(test_env.users[0].address()));
+    assert_eq!(res[2].1.address, Some(test_env.contract_addr.clone()));
+
+    let res: Vec<(u64, HistoryEntry)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                start_before: Some(res[0].0),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].1.action, "mint");
+}
+
+#[test]
+fn test_stats() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![
+                Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(50u128),
+                    display_units: None,
+                },
+                Receiver {
+                    address: test_env.users[1].address(),
+                    amount: Uint128::from(120u128),
+                    display_units: None,
+                },
+            ]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Stats = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Stats)
+        .unwrap();
+
+    // the initial mint and the batch each count as a single mint, but the contract isn't a recipient
+    assert_eq!(res.mints, 2);
+    assert_eq!(res.recipients, 2);
+    assert_eq!(res.largest_mint, Uint128::from(120u128));
+    assert!(res.last_mint.is_some());
+    assert_eq!(res.last_burn, None);
+
+    // a mint that fails leaves the stats as they were
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.admin.address(),
+            amount: Uint128::from(1_000u128),
+            display_units: None,
+        }]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let res: Stats = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Stats)
+        .unwrap();
+
+    assert_eq!(res.mints, 2);
+    assert_eq!(res.recipients, 2);
+    assert_eq!(res.largest_mint, Uint128::from(120u128));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Burn(Some(Uint128::from(10u128))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Stats = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Stats)
+        .unwrap();
+
+    assert!(res.last_burn.is_some());
+}
+
+#[test]
+fn test_nonpayable() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Burn(Some(Uint128::from(10u128))),
+        &[Coin::new(1_000, "uosmo")],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // without a fee or public mint, a mint doesn't take funds either
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(50u128),
+            display_units: None,
+        }]),
+        &[Coin::new(1_000, "uosmo")],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // actions spending from the contract's balance can be funded along with the call
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Finalize {
+                recipient: test_env.users[0].address(),
+                assetlist: None,
+            },
+            &[Coin::new(1_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_disable_send() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let send = || {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Send(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(10u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = send().unwrap();
+
+    // only an owner can disable sends
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::DisableSend,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::DisableSend,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: bool = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::SendDisabled)
+        .unwrap();
+
+    assert!(res);
+
+    // the rest of the treasury can't be moved, only burned
+    let res = send();
+
+    assert!(res.unwrap_err().to_string().contains("has been disabled"));
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Sweep {
+            to: test_env.users[0].address(),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Burn(None),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.contract_addr, &test_env.denom),
+        0
+    );
+}
+
+#[test]
+fn test_send_policy() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let send = |index: usize| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Send(vec![Receiver {
+                address: test_env.users[index].address(),
+                amount: Uint128::from(10u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateSendPolicy {
+                enabled: Some(true),
+                add: vec![test_env.users[1].address()],
+                remove: vec![],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: SendPolicyResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::SendPolicy {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert!(res.enabled);
+    assert_eq!(
+        res.destinations,
+        vec![Addr::unchecked(test_env.users[1].address())]
+    );
+
+    // only allowed destinations can receive the token held by the contract
+    assert!(send(0).is_err());
+    let _ = send(1).unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateSendPolicy {
+                enabled: Some(false),
+                add: vec![],
+                remove: vec![],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = send(0).unwrap();
+}
+
+#[test]
+fn test_contract_info() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let res: ContractInfoResponse = get_modules(&test_env)
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::ContractInfo)
+        .unwrap();
+
+    assert_eq!(res.contract, "factory");
+    assert_eq!(res.denom, test_env.denom);
+    assert_eq!(res.symbol, "TEST");
+    assert_eq!(res.admin, Some(test_env.contract_addr.clone()));
+    assert_eq!(res.owners, vec![Addr::unchecked(test_env.admin.address())]);
+    assert!(!res.revoked);
+    assert!(!res.features.contains(&"timelock".to_string()));
+}
+
+#[test]
+fn test_supported_interfaces() {
+    let test_env = instantiate_with(|app, _| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000u128)),
+        pre_launch: Some(PreLaunch {
+            trading_start: app.get_block_timestamp().plus_seconds(86_400),
+            allowlist: vec![],
+        }),
+        ..base_msg()
+    });
+
+    let res: SupportedInterfacesResponse = get_modules(&test_env)
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::SupportedInterfaces)
+        .unwrap();
+
+    let interfaces: Vec<&str> = res
+        .interfaces
+        .iter()
+        .map(|interface| interface.interface.as_str())
+        .collect();
+
+    assert_eq!(
+        interfaces,
+        vec![
+            "crates.io:factory",
+            "factory:pre_launch",
+            "factory:send_hook"
+        ]
+    );
+}
+
+#[test]
+fn test_storage_stats() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let count = |name: &str| {
+        let res: StorageStatsResponse = modules
+            .wasm
+            .query(&test_env.contract_addr, &QueryMsg::StorageStats)
+            .unwrap();
+        res.maps
+            .iter()
+            .find(|(map, _)| map == name)
+            .map(|(_, entries)| *entries)
+            .unwrap()
+    };
+
+    // only the instantiating admin holds a role
+    assert_eq!(count("roles"), 1);
+    assert_eq!(count("queued_actions"), 0);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::IncreaseAllowance {
+                spender: test_env.users[0].address(),
+                amount: Uint128::from(30u128),
+                expires: None,
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(count("allowances"), 1);
+
+    // clearing a map resets its count
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::DisableSend,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(count("allowances"), 0);
+}
+
+#[test]
+fn test_schedule() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // only mints, sends, supply updates and revokes can be scheduled
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Schedule {
+            msg: Box::new(ExecuteMsg::Pause),
+            execute_at: test_env.app.get_block_timestamp().plus_seconds(86_400),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Schedule {
+                msg: Box::new(ExecuteMsg::Mint(vec![Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(50u128),
+                    display_units: None,
+                }])),
+                execute_at: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the schedule is a commitment the owners can't take back
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::CancelQueued { id: 0 },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // nothing is due yet
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Crank { limit: None },
+        &[],
+        &test_env.users[1],
+    );
+
+    assert!(res.is_err());
+
+    // sending more than the contract holds will fail once it is due
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Schedule {
+                msg: Box::new(ExecuteMsg::Send(vec![Receiver {
+                    address: test_env.users[1].address(),
+                    amount: Uint128::from(1_000u128),
+                    display_units: None,
+                }])),
+                execute_at: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the crank reward is paid from the contract's balance
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetCrankReward(Some(Coin::new(10, &test_env.denom))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(86_400);
+
+    // anyone can crank once it's due. the failing action doesn't revert the mint
+    let res = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Crank { limit: None },
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.attributes.iter().any(
+            |attribute| attribute.key == "action" && attribute.value == "factory_crank_failed"
+        )));
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        50
+    );
+
+    // and only the action that succeeded earns the reward
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        10
+    );
+
+    let res: Vec<(u64, QueuedAction)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::QueuedActions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    // it stays scheduled, to be retried on a later crank
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 1);
+}
+
+#[test]
+fn test_schedule_paused() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Schedule {
+                msg: Box::new(ExecuteMsg::Mint(vec![Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(50u128),
+                    display_units: None,
+                }])),
+                execute_at: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(86_400);
+
+    let execute = |msg: &ExecuteMsg, sender: &SigningAccount| {
+        modules
+            .wasm
+            .execute(&test_env.contract_addr, msg, &[], sender)
+            .unwrap()
+    };
+    let queued = || -> Vec<(u64, QueuedAction)> {
+        modules
+            .wasm
+            .query(
+                &test_env.contract_addr,
+                &QueryMsg::QueuedActions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // the scheduled mint fails while the contract is paused
+    let _ = execute(&ExecuteMsg::Pause, &test_env.admin);
+    let res = execute(&ExecuteMsg::Crank { limit: None }, &test_env.users[1]);
+
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.attributes.iter().any(
+            |attribute| attribute.key == "action" && attribute.value == "factory_crank_failed"
+        )));
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        0
+    );
+
+    // but a pause can't cancel the commitment, it is executed by the next crank after unpausing
+    assert_eq!(queued().len(), 1);
+
+    let _ = execute(&ExecuteMsg::Unpause, &test_env.admin);
+    let _ = execute(&ExecuteMsg::Crank { limit: None }, &test_env.users[1]);
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        50
+    );
+    assert!(queued().is_empty());
+}
+
+#[test]
+fn test_timelock() {
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        initial_supply: Some(Uint128::from(1_00u128)),
+        max_supply: Some(Uint128::from(1_000u128)),
+        timelock: Some(86_400),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint_msg = ExecuteMsg::Mint(vec![Receiver {
+        address: test_env.users[0].address(),
+        amount: Uint128::from(100u128),
+        display_units: None,
+    }]);
+
+    // minting directly is not allowed while a timelock is configured
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint_msg, &[], &test_env.admin);
+
+    assert!(res.is_err());
+
+    // burning is not timelocked
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Burn(Some(Uint128::from(10u128))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // spending the treasury on a buyback and exempting an address from the token's restrictions
+    // are
+    for msg in [
+        ExecuteMsg::Buyback {
+            offer: Coin::new(100, "uosmo"),
+            pool_id: Some(1),
+            min_out: Uint128::zero(),
+        },
+        ExecuteMsg::UpdateAllowlist {
+            add: vec![test_env.users[0].address()],
+            remove: vec![],
+        },
+        ExecuteMsg::UpdateTransferFeeExempt {
+            add: vec![test_env.users[0].address()],
+            remove: vec![],
+        },
+        ExecuteMsg::UpdateMaxWalletExempt {
+            add: vec![test_env.users[0].address()],
+            remove: vec![],
+        },
+        ExecuteMsg::UpdateIbcHooks {
+            channel: "channel-0".to_string(),
+            senders: Some(vec![]),
+        },
+    ] {
+        let res = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.admin);
+
+        assert!(res.unwrap_err().to_string().contains("timelocked"));
+    }
+
+    // queue the mint
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Queue(Box::new(mint_msg)),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Vec<(u64, QueuedAction)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::QueuedActions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 0);
+
+    // it can't be executed before the delay
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ExecuteQueued { id: 0 },
+        &[],
+        &test_env.users[1],
+    );
+
+    assert!(res.is_err());
+
+    test_env.app.increase_time(86_400);
+
+    // anyone can execute it after the delay
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ExecuteQueued { id: 0 },
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(200u128));
+
+    // the action is removed from the queue once executed
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ExecuteQueued { id: 0 },
+        &[],
+        &test_env.users[1],
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_timelock_denom_minters() {
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        timelock: Some(86_400),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let create_msg = ExecuteMsg::CreateDenom {
+        symbol: "GOLD".to_string(),
+        subdenom: None,
+        max_supply: Uint128::from(1_000u128),
+    };
+    let minters_msg = ExecuteMsg::UpdateDenomMinters {
+        symbol: "GOLD".to_string(),
+        add: vec![test_env.users[1].address()],
+        remove: vec![],
+    };
+
+    // neither creating a denom nor adding a minter can skip the timelock, or an owner could add
+    // their own second key and mint right away
+    for msg in [&create_msg, &minters_msg] {
+        let res = modules
+            .wasm
+            .execute(&test_env.contract_addr, msg, &[], &test_env.admin);
+
+        assert!(res.unwrap_err().to_string().contains("timelocked"));
+    }
+
+    for msg in [create_msg, minters_msg] {
+        let _ = modules
+            .wasm
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::Queue(Box::new(msg)),
+                &[],
+                &test_env.admin,
+            )
+            .unwrap();
+    }
+
+    let mint = || {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintDenom {
+                symbol: "GOLD".to_string(),
+                receivers: vec![Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(100u128),
+                    display_units: None,
+                }],
+            },
+            &[],
+            &test_env.users[1],
+        )
+    };
+
+    test_env.app.increase_time(86_400);
+
+    // the queued minter can't mint until its addition is executed
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ExecuteQueued { id: 0 },
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert!(mint().unwrap_err().to_string().contains("Not authorized"));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ExecuteQueued { id: 1 },
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let _ = mint().unwrap();
+
+    assert_eq!(
+        balance(
+            &test_env,
+            &test_env.users[0].address(),
+            &format!("factory/{}/GOLD", test_env.contract_addr)
+        ),
+        100
+    );
+}
+
+#[test]
+fn test_propose_taker_fee() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let propose = |funds: &[Coin]| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ProposeTakerFee {
+                quote_denom: "uosmo".to_string(),
+                taker_fee: Decimal::permille(1),
+                deposit: vec![Coin::new(1_000_000, "uosmo")],
+                title: "TEST taker fee".to_string(),
+                description: "Set the TEST/OSMO taker fee".to_string(),
+            },
+            funds,
+            &test_env.admin,
+        )
+    };
+
+    // the rewards held for a distribution can't be put up as the deposit
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateDistribution {
+                merkle_root: hex::encode([0u8; 32]),
+                snapshot_height: test_env.app.get_block_height() as u64,
+                total: Uint128::from(100u128),
+                expiry: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[Coin::new(1_000_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res = propose(&[]);
+
+    assert!(res.unwrap_err().to_string().contains("less than"));
+
+    // the deposit is paid from the contract balance, so fund it along with the call
+    let _ = propose(&[Coin::new(1_000_000, "uosmo")]).unwrap();
+
+    let res: Vec<TakerFeeProposalResponse> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::TakerFeeProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].quote_denom, "uosmo");
+    assert_ne!(res[0].status, "unknown");
+
+    let res: Vec<TakerFeeProposalResponse> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::TakerFeeProposals {
+                start_after: Some(res[0].proposal_id),
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_lock_supply() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::LockSupply,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert!(res.supply_locked);
+    assert_eq!(res.max_supply, Uint128::from(300u128));
+
+    // the cap can't be raised or removed anymore
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::UpdateSupply(Uint128::from(400u128)),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::UpdateSupply(Uint128::zero()),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // an uncapped supply can't be locked
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::zero());
+
+    let res = get_modules(&test_env).wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::LockSupply,
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_finalize() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+    let assetlist = instantiate_assetlist(&test_env, Coin::new(1_000_000, "uosmo"));
+
+    let modules = get_modules(&test_env);
+
+    let metadata = Metadata {
+        symbol: "TEST".to_string(),
+        exp: Some(6),
+        logo: None,
+        chain: Some("osmosis-1".to_string()),
+        extensions: None,
+    };
+
+    // mint the rest of the supply to user[0], list the token and revoke in one transaction.
+    // the listing fee is attached so the contract can pay it
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Finalize {
+                recipient: test_env.users[0].address(),
+                assetlist: Some(AssetlistRegistration {
+                    contract: assetlist.clone(),
+                    metadata: metadata.clone(),
+                    fee: vec![Coin::new(1_000_000, "uosmo")],
+                }),
+            },
+            &[Coin::new(1_000_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(300u128));
+
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(res.revoked);
+    assert!(res.cap_reached);
+
+    let res: Vec<(String, Metadata)> = modules
+        .wasm
+        .query(
+            &assetlist,
+            &AssetlistQueryMsg::Listing(ListingQuery::Denom(vec![test_env.denom.clone()])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1, metadata);
+}
+
+#[test]
+fn test_mint_to() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+    // an assetlist charging its listing fee in the token stands in for a contract that reacts to
+    // receiving it
+    let assetlist = instantiate_assetlist(&test_env, Coin::new(50, &test_env.denom));
+
+    let modules = get_modules(&test_env);
+
+    let metadata = Metadata {
+        symbol: "TEST".to_string(),
+        exp: Some(6),
+        logo: None,
+        chain: Some("osmosis-1".to_string()),
+        extensions: None,
+    };
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintTo {
+                contract: assetlist.clone(),
+                amount: Uint128::from(50u128),
+                msg: to_json_binary(&AssetlistExecuteMsg::Listing(AssetlistListingMsg::Add(
+                    vec![(test_env.denom.clone(), metadata.clone())],
+                )))
+                .unwrap(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the minted tokens paid the fee
+    assert_eq!(balance(&test_env, &assetlist, &test_env.denom), 50);
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(150u128));
+
+    let res: Vec<(String, Metadata)> = modules
+        .wasm
+        .query(
+            &assetlist,
+            &AssetlistQueryMsg::Listing(ListingQuery::Denom(vec![test_env.denom.clone()])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1, metadata);
+}
+
+#[test]
+fn test_send_and_call() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+    let assetlist = instantiate_assetlist(&test_env, Coin::new(50, &test_env.denom));
+
+    let modules = get_modules(&test_env);
+
+    let send_and_call = ExecuteMsg::SendAndCall {
+        contract: assetlist.clone(),
+        amount: Uint128::from(50u128),
+        msg: to_json_binary(&AssetlistExecuteMsg::Listing(AssetlistListingMsg::Add(
+            vec![(
+                test_env.denom.clone(),
+                Metadata {
+                    symbol: "TEST".to_string(),
+                    exp: Some(6),
+                    logo: None,
+                    chain: Some("osmosis-1".to_string()),
+                    extensions: None,
+                },
+            )],
+        )))
+        .unwrap(),
+    };
+
+    // only an owner can move the treasury
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &send_and_call,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // the listing fee is paid from the initial supply held by the contract
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &send_and_call,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(balance(&test_env, &assetlist, &test_env.denom), 50);
+    assert_eq!(
+        balance(&test_env, &test_env.contract_addr, &test_env.denom),
+        50
+    );
+}
+
+#[test]
+fn test_ibc_hooks() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // accept mints from one remote sender over channel-0
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateIbcHooks {
+                channel: "channel-0".to_string(),
+                senders: Some(vec!["cosmos1remote".to_string()]),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Vec<(String, Vec<String>)> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::IbcHooks)
+        .unwrap();
+
+    assert_eq!(
+        res,
+        vec![("channel-0".to_string(), vec!["cosmos1remote".to_string()])]
+    );
+
+    // the hook address is derived from both the channel and the remote sender
+    let sender = |channel: &str| -> Addr {
+        modules
+            .wasm
+            .query(
+                &test_env.contract_addr,
+                &QueryMsg::IbcHookSender {
+                    channel: channel.to_string(),
+                    original_sender: "cosmos1remote".to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    assert!(sender("channel-0").as_str().starts_with("osmo1"));
+    assert_ne!(sender("channel-0"), sender("channel-1"));
+
+    // a local account can't claim to be the remote sender
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::IbcMint {
+            channel: "channel-0".to_string(),
+            original_sender: "cosmos1remote".to_string(),
+            receivers: vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(10u128),
+                display_units: None,
+            }],
+        },
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Sender is not the ibc-hooks address"));
+}
+
+#[test]
+fn test_cl_pool() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let res: Option<ClPool> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::ClPool)
+        .unwrap();
+
+    assert!(res.is_none());
+
+    // the token can't be paired with itself
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::CreateClPool {
+            amount: Uint128::from(100u128),
+            quote: Coin::new(100, &test_env.denom),
+            tick_spacing: 100,
+            spread_factor: Decimal::permille(2),
+            locked_until: None,
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("the quote must be another denom"));
+
+    // only an owner can create the pool
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::CreateClPool {
+            amount: Uint128::from(100u128),
+            quote: Coin::new(1_000_000, "uosmo"),
+            tick_spacing: 100,
+            spread_factor: Decimal::permille(2),
+            locked_until: None,
+        },
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // the pool is seeded from the contract's balance, which holds the initial supply but no quote
+    for amount in [101u128, 100] {
+        let res = modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateClPool {
+                amount: Uint128::from(amount),
+                quote: Coin::new(1_000_000, "uosmo"),
+                tick_spacing: 100,
+                spread_factor: Decimal::permille(2),
+                locked_until: None,
+            },
+            &[],
+            &test_env.admin,
+        );
+
+        assert!(res.unwrap_err().to_string().contains("less than"));
+    }
+
+    // there is no position to withdraw yet
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::WithdrawPosition,
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("no position to withdraw"));
+
+    // nor a pool to price the token against
+    let res = modules.wasm.query::<_, PriceResponse>(
+        &test_env.contract_addr,
+        &QueryMsg::Price {
+            quote_denom: "uosmo".to_string(),
+        },
+    );
+
+    assert!(res.unwrap_err().to_string().contains("No pool"));
+}
+
+#[test]
+fn test_buyback() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let buyback = |offer: Coin, pool_id: Option<u64>| ExecuteMsg::Buyback {
+        offer,
+        pool_id,
+        min_out: Uint128::from(1u128),
+    };
+
+    // the token can't buy itself back
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &buyback(Coin::new(100, &test_env.denom), Some(1)),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("another denom"));
+
+    // there is no concentrated liquidity pool to default to
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &buyback(Coin::new(100, "uosmo"), None),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("and a pool"));
+
+    // the contract holds no osmo to spend
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &buyback(Coin::new(100, "uosmo"), Some(1)),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("holds less than"));
+
+    // only an owner can buy back
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &buyback(Coin::new(100, "uosmo"), Some(1)),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("Not authorized"));
+}
+
+#[test]
+fn test_create_gauge() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let gauge = |amount: u128, epochs: u64, mint: bool| ExecuteMsg::CreateGauge {
+        pool_id: 1,
+        amount: Uint128::from(amount),
+        epochs,
+        lock_duration: Some(86_400),
+        start_time: None,
+        mint,
+    };
+
+    // a gauge has to pay out over at least one epoch
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &gauge(100, 0, false),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("at least one epoch"));
+
+    // the contract only holds the initial supply
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &gauge(150, 7, false),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("holds less than"));
+
+    // minting the rewards is still bound by the max supply
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &gauge(250, 7, true),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // only an owner can create a gauge
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &gauge(100, 7, false),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("Not authorized"));
+}
+
+#[test]
+fn test_project_info() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let res: ProjectInfo = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::ProjectInfo)
+        .unwrap();
+
+    assert_eq!(res, ProjectInfo::default());
+
+    let project_info = ProjectInfo {
+        description: Some("The test token".to_string()),
+        website: Some("https://example.com".to_string()),
+        x: Some("https://x.com/example".to_string()),
+        telegram: None,
+        logo_uri: Some("ipfs://logo.png".to_string()),
+    };
+
+    // only an owner or metadata manager can set it
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::SetProjectInfo(project_info.clone()),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("Not authorized"));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetProjectInfo(project_info.clone()),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: ProjectInfo = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::ProjectInfo)
+        .unwrap();
+
+    assert_eq!(res, project_info);
+
+    // fields are bounded in length
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::SetProjectInfo(ProjectInfo {
+            description: Some("a".repeat(513)),
+            ..project_info
+        }),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("longer than"));
+}
+
+#[test]
+fn test_multi_denom() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    // the token's own symbol is taken
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::CreateDenom {
+            symbol: "TEST".to_string(),
+            subdenom: None,
+            max_supply: Uint128::from(1_000u128),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("already exists"));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateDenom {
+                symbol: "GOLD".to_string(),
+                subdenom: None,
+                max_supply: Uint128::from(1_000u128),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let gold = format!("factory/{}/GOLD", test_env.contract_addr);
+
+    let mint = |amount: u128, sender: &SigningAccount| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintDenom {
+                symbol: "GOLD".to_string(),
+                receivers: vec![Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(amount),
+                    display_units: None,
+                }],
+            },
+            &[],
+            sender,
+        )
+    };
+
+    // only an owner or one of the denom's minters can mint it
+    let res = mint(100, &test_env.users[1]);
+
+    assert!(res.unwrap_err().to_string().contains("Not authorized"));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateDenomMinters {
+                symbol: "GOLD".to_string(),
+                add: vec![test_env.users[1].address()],
+                remove: vec![],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = mint(600, &test_env.users[1]).unwrap();
+    let _ = mint(300, &test_env.admin).unwrap();
+
+    // each denom has its own cap
+    let res = mint(101, &test_env.users[1]);
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("exceed the max supply"));
+    assert_eq!(balance(&test_env, &test_env.users[0].address(), &gold), 900);
+
+    let res: DenomsResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Denoms {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        res.denoms,
+        vec![DenomInfo {
+            symbol: "GOLD".to_string(),
+            denom: gold.clone(),
+            max_supply: Uint128::from(1_000u128),
+            minted: Uint128::from(900u128),
+            burned: Uint128::zero(),
+            minters: vec![Addr::unchecked(test_env.users[1].address())],
+        }]
+    );
+
+    // mints of further denoms are kept in the history along with their denom
+    let res: Vec<(u64, HistoryEntry)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                start_before: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1.action, "mint");
+    assert_eq!(res[0].1.amount, Uint128::from(300u128));
+    assert_eq!(res[0].1.denom, Some(gold.clone()));
+
+    // burns of further denoms are kept too
+    let _ = modules
+        .bank
+        .send(
+            MsgSend {
+                from_address: test_env.users[0].address(),
+                to_address: test_env.contract_addr.clone(),
+                amount: vec![ProtoCoin {
+                    denom: gold.clone(),
+                    amount: "100".to_string(),
+                }],
+            },
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::BurnDenom {
+                symbol: "GOLD".to_string(),
+                amount: Uint128::from(100u128),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Vec<(u64, HistoryEntry)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::History {
+                start_before: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1.action, "burn");
+    assert_eq!(res[0].1.amount, Uint128::from(100u128));
+    assert_eq!(res[0].1.denom, Some(gold));
+}
+
+#[test]
+fn test_mint_tax() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let set_tax = |rate: Decimal| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetMintTax(Some(MintTax {
+                rate,
+                treasury: Addr::unchecked(test_env.users[1].address()),
+            })),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    // the whole mint can't go to the treasury
+    assert!(set_tax(Decimal::one()).is_err());
+
+    let _ = set_tax(Decimal::percent(10)).unwrap();
+
+    let res: Option<MintTax> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::MintTax)
+        .unwrap();
+
+    assert_eq!(res.unwrap().rate, Decimal::percent(10));
+
+    let mint = |amount: u128| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(amount),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = mint(100).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        10
+    );
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(210u128));
+
+    // the tax counts against the max supply
+    let res = mint(90);
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("more than max supply"));
+
+    // and against the bucket it is drawn from
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetBucket {
+                label: "team".to_string(),
+                amount: Uint128::from(50u128),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mint_from_bucket = |amount: u128| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintFromBucket {
+                bucket: "team".to_string(),
+                receivers: vec![Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(amount),
+                    display_units: None,
+                }],
+            },
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = mint_from_bucket(40).unwrap();
+
+    let res: BucketsResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Buckets)
+        .unwrap();
+
+    assert_eq!(res.buckets[0].minted, Uint128::from(44u128));
+
+    assert!(mint_from_bucket(6).is_ok());
+    assert!(mint_from_bucket(1).is_err());
+}
+
+#[test]
+fn test_finalize_buckets() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    for msg in [
+        ExecuteMsg::SetBucket {
+            label: "team".to_string(),
+            amount: Uint128::from(50u128),
+        },
+        ExecuteMsg::ScheduleRevoke {
+            delay: 100,
+            new_admin: None,
+        },
+        ExecuteMsg::Finalize {
+            recipient: test_env.users[0].address(),
+            assetlist: None,
+        },
+    ] {
+        let _ = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.admin)
+            .unwrap();
+    }
+
+    // the supply reserved for the bucket isn't minted
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(250u128));
+
+    // and the scheduled revoke is done with
+    let res: Option<PendingRevoke> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::PendingRevoke)
+        .unwrap();
+
+    assert!(res.is_none());
+}
+
+#[test]
+fn test_finalize_mint_tax() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetMintTax(Some(MintTax {
+                rate: Decimal::percent(10),
+                treasury: Addr::unchecked(test_env.users[1].address()),
+            })),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the remaining 200 cover a mint of 181 and its tax of 18
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Finalize {
+                recipient: test_env.users[0].address(),
+                assetlist: None,
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        18
+    );
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.minted, Uint128::from(299u128));
+}
+
+#[test]
+fn test_mint_limit() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetMintLimit(Some(Uint128::from(100u128))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mint = |amounts: &[u128]| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(
+                amounts
+                    .iter()
+                    .map(|amount| Receiver {
+                        address: test_env.users[0].address(),
+                        amount: Uint128::from(*amount),
+                        display_units: None,
+                    })
+                    .collect(),
+            ),
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    let _ = mint(&[60]).unwrap();
+
+    // repeating the receiver in a batch doesn't get around the limit
+    let res = mint(&[30, 30]);
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("per-address mint limit"));
+
+    let _ = mint(&[40]).unwrap();
+
+    let res: Uint128 = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::MintedTo {
+                address: test_env.users[0].address(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res, Uint128::from(100u128));
+
+    // tokens the contract holds for a receiver count towards the receiver
+    let mint_vested = |receiver: String, amount: u128| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintVested {
+                receiver,
+                amount: Uint128::from(amount),
+                schedule: VestingSchedule {
+                    start: test_env.app.get_block_timestamp(),
+                    cliff: 0,
+                    duration: 1_000,
+                },
+            },
+            &[],
+            &test_env.admin,
+        )
+    };
+
+    assert!(mint_vested(test_env.users[0].address(), 1).is_err());
+
+    // and they don't need a bucket, as long as they fit in the unreserved supply
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetBucket {
+                label: "team".to_string(),
+                amount: Uint128::from(700u128),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert!(mint([10].as_slice())
+        .unwrap_err()
+        .to_string()
+        .contains("bucket"));
+    let _ = mint_vested(test_env.users[1].address(), 100).unwrap();
+
+    // the rest of the supply is reserved for the bucket
+    assert!(mint_vested(test_env.admin.address(), 1)
+        .unwrap_err()
+        .to_string()
+        .contains("more than max supply"));
+
+    let res: Uint128 = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::MintedTo {
+                address: test_env.users[1].address(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res, Uint128::from(100u128));
+}
+
+#[test]
+fn test_emission() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000_000u128));
+
+    let modules = get_modules(&test_env);
+
+    // 1000 over the first 100 seconds, then 500, then 250
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetEmission(Some(Emission {
+                recipient: Addr::unchecked(test_env.users[1].address()),
+                start: Timestamp::from_nanos(test_env.app.get_block_time_nanos() as u64),
+                initial_reward: Uint128::from(1_000u128),
+                halving_interval: 100,
+                epochs: 3,
+            })),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Option<EmissionResponse> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Emission)
+        .unwrap();
+
+    assert_eq!(res.unwrap().total, Uint128::from(1_750u128));
+
+    // anyone can mint what has been released, and it always goes to the recipient
+    test_env.app.increase_time(150);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintEmission,
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let res: Option<EmissionResponse> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Emission)
+        .unwrap();
+    let minted = res.unwrap().minted;
+
+    assert!(minted >= Uint128::from(1_250u128) && minted < Uint128::from(1_500u128));
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        minted.u128()
+    );
+
+    // the emission isn't held back by the per-address mint limit
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetMintLimit(Some(Uint128::from(100u128))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // nothing more is released after the last epoch
+    test_env.app.increase_time(1_000);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintEmission,
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::MintEmission,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("Nothing has been released"));
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        1_750
+    );
+}
+
+#[test]
+fn test_mint_vested() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintVested {
+                receiver: test_env.users[0].address(),
+                amount: Uint128::from(100u128),
+                schedule: VestingSchedule {
+                    start: test_env.app.get_block_timestamp(),
+                    cliff: 100,
+                    duration: 1_000,
+                },
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let vesting = || -> VestingResponse {
+        modules
+            .wasm
+            .query(
+                &test_env.contract_addr,
+                &QueryMsg::Vesting {
+                    address: test_env.users[0].address(),
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(vesting().locked, Uint128::from(100u128));
+    assert_eq!(vesting().claimable, Uint128::zero());
+
+    // the treasury can only sweep the initial supply, not the grant
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Sweep {
+                to: test_env.users[1].address(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        100
+    );
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Burn(Some(Uint128::one())),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("holds less than"));
+
+    test_env.app.increase_time(1_000);
+
+    let res = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Claim,
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert!(res.events.iter().any(|event| event.ty == "wasm-tfa-send"
+        && event
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "amount" && attribute.value == "100")));
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        100
+    );
+    // the fully claimed grant is dropped
+    assert_eq!(vesting().claimed, Uint128::zero());
+    assert_eq!(vesting().locked, Uint128::zero());
+}
+
+#[test]
+fn test_allowance() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(300u128));
+
+    let modules = get_modules(&test_env);
+    let spender = &test_env.users[0];
+
+    // only owners can grant allowances
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::IncreaseAllowance {
+            spender: spender.address(),
+            amount: Uint128::from(30u128),
+            expires: None,
+        },
+        &[],
+        spender,
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::IncreaseAllowance {
+                spender: spender.address(),
+                amount: Uint128::from(30u128),
+                expires: None,
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::TransferFrom {
+                recipient: test_env.users[1].address(),
+                amount: Uint128::from(20u128),
+            },
+            &[],
+            spender,
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        20
+    );
+
+    // the allowance is spent down
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::TransferFrom {
+            recipient: test_env.users[1].address(),
+            amount: Uint128::from(20u128),
+        },
+        &[],
+        spender,
+    );
+
+    assert!(res.is_err());
+
+    let res: Allowance = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Allowance {
+                spender: spender.address(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.amount, Uint128::from(10u128));
+
+    // decreasing to zero removes the allowance
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::DecreaseAllowance {
+                spender: spender.address(),
+                amount: Uint128::from(10u128),
+                expires: None,
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: Vec<(Addr, Allowance)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Allowances {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_clawback() {
+    let clawback = |test_env: &TestEnv| ExecuteMsg::Clawback {
+        from: test_env.users[0].address(),
+        to: test_env.admin.address(),
+        amount: Uint128::from(10u128),
+        reason: "compromised key".to_string(),
+    };
+
+    // tokens that didn't opt in can't claw back
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let res = get_modules(&test_env).wasm.execute(
+        &test_env.contract_addr,
+        &clawback(&test_env),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("not enabled"));
+
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        initial_supply: Some(Uint128::from(1_00u128)),
+        max_supply: Some(Uint128::from(1_000u128)),
+        timelock: Some(86_400),
+        clawback: Some(true),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let res: bool = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Clawback)
+        .unwrap();
+
+    assert!(res);
+
+    // every clawback has to be queued publicly first
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &clawback(&test_env),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.unwrap_err().to_string().contains("must be queued"));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Queue(Box::new(clawback(&test_env))),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the contract's own tokens can't be clawed back past the send policy
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Queue(Box::new(ExecuteMsg::Clawback {
+                from: test_env.contract_addr.clone(),
+                to: test_env.users[0].address(),
+                amount: Uint128::from(10u128),
+                reason: "treasury".to_string(),
+            })),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(86_400);
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ExecuteQueued { id: 1 },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("not from the contract"));
+}
+
+#[test]
+fn test_distribution() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let leaf = |address: String, balance: u128| -> [u8; 32] {
+        Sha256::digest(format!("{}{}", address, balance)).into()
+    };
+    let leaves = [
+        leaf(test_env.users[0].address(), 300),
+        leaf(test_env.users[1].address(), 100),
+    ];
+    let mut pair = leaves;
+    pair.sort_unstable();
+    let root: [u8; 32] = Sha256::digest(pair.concat()).into();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateDistribution {
+                merkle_root: hex::encode(root),
+                snapshot_height: test_env.app.get_block_height() as u64,
+                total: Uint128::from(400u128),
+                expiry: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[Coin::new(1_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let claim = ExecuteMsg::ClaimDistribution {
+        id: 1,
+        balance: Uint128::from(300u128),
+        proof: vec![hex::encode(leaves[1])],
+    };
+
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &claim, &[], &test_env.users[0])
+        .unwrap();
+
+    // each holder can only claim once
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &claim, &[], &test_env.users[0]);
+
+    assert!(res.unwrap_err().to_string().contains("Already claimed"));
+
+    let res: Vec<(u64, Distribution)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Distributions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1.claimed, vec![Coin::new(750, "uosmo")]);
+
+    // the unclaimed rewards can only be swept once the distribution expires
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::SweepDistribution {
+            id: 1,
+            to: test_env.admin.address(),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res
+        .unwrap_err()
+        .to_string()
+        .contains("can't be swept before"));
+
+    // balances adding up to more than the total can't pay out more than was deposited
+    let leaves = [
+        leaf(test_env.users[0].address(), 300),
+        leaf(test_env.users[1].address(), 300),
+    ];
+    let mut pair = leaves;
+    pair.sort_unstable();
+    let root: [u8; 32] = Sha256::digest(pair.concat()).into();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::CreateDistribution {
+                merkle_root: hex::encode(root),
+                snapshot_height: test_env.app.get_block_height() as u64,
+                total: Uint128::from(400u128),
+                expiry: test_env.app.get_block_timestamp().plus_seconds(86_400),
+            },
+            &[Coin::new(1_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    for (user, sibling) in [(0, 1), (1, 0)] {
+        let _ = modules
+            .wasm
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::ClaimDistribution {
+                    id: 2,
+                    balance: Uint128::from(300u128),
+                    proof: vec![hex::encode(leaves[sibling])],
+                },
+                &[],
+                &test_env.users[user],
+            )
+            .unwrap();
+    }
+
+    let res: Vec<(u64, Distribution)> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Distributions {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1.claimed, vec![Coin::new(1_000, "uosmo")]);
+}
+
+#[test]
+fn test_staking() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ConfigureStaking {
+                reward_denom: "uosmo".to_string(),
+                reward_rate: Uint128::from(1u128),
+                tiers: vec![LockTier {
+                    duration: 100,
+                    multiplier: Decimal::one(),
+                }],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::FundStaking,
+            &[Coin::new(1_000, "uosmo")],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(100u128),
+                display_units: None,
+            }]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // stakes must use one of the configured durations
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Stake { duration: 50 },
+        &[Coin::new(100, &test_env.denom)],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Stake { duration: 100 },
+            &[Coin::new(100, &test_env.denom)],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let unstake = || {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Unstake { id: 1 },
+            &[],
+            &test_env.users[0],
+        )
+    };
+
+    assert!(unstake().unwrap_err().to_string().contains("locked until"));
+
+    test_env.app.increase_time(200);
+
+    let res: StakesResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Stakes {
+                address: test_env.users[0].address(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.positions.len(), 1);
+    assert_eq!(res.weight, Uint128::from(100u128));
+    assert!(!res.rewards.is_zero());
+
+    let _ = unstake().unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        100
+    );
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ClaimRewards,
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // once everything is claimed and unstaked, no rewards are left unaccounted for
+    let res: Option<Staking> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Staking)
+        .unwrap();
+    let staking = res.unwrap();
+
+    assert!(staking.unclaimed.is_zero());
+    assert!(staking.settled.is_zero());
+}
+
+#[test]
+fn test_governance() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    for msg in [
+        ExecuteMsg::ConfigureStaking {
+            reward_denom: "uosmo".to_string(),
+            reward_rate: Uint128::zero(),
+            tiers: vec![
+                LockTier {
+                    duration: 50,
+                    multiplier: Decimal::one(),
+                },
+                LockTier {
+                    duration: 1_000,
+                    multiplier: Decimal::one(),
+                },
+            ],
+        },
+        ExecuteMsg::ConfigureGovernance(Some(Governance {
+            voting_period: 100,
+            quorum: Decimal::percent(50),
+            threshold: Decimal::percent(50),
+            execution_period: 1_000,
+            deposit: Uint128::zero(),
+        })),
+        ExecuteMsg::Mint(vec![
+            Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(100u128),
+                display_units: None,
+            },
+            Receiver {
+                address: test_env.users[1].address(),
+                amount: Uint128::from(50u128),
+                display_units: None,
+            },
+        ]),
+    ] {
+        let _ = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.admin)
+            .unwrap();
+    }
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Stake { duration: 1_000 },
+            &[Coin::new(100, &test_env.denom)],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // user[1]'s stake unlocks before voting closes, so it can't vote
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Stake { duration: 50 },
+            &[Coin::new(50, &test_env.denom)],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let propose = |msgs: Vec<ExecuteMsg>, sender: &SigningAccount| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Propose {
+                title: "Raise the cap".to_string(),
+                description: String::new(),
+                msgs,
+            },
+            &[],
+            sender,
+        )
+    };
+
+    // only stakers can propose, and only whitelisted actions
+    let res = propose(
+        vec![ExecuteMsg::UpdateSupply(Uint128::from(2_000u128))],
+        &test_env.users[1],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("No stakes locked"));
+
+    let res = propose(vec![ExecuteMsg::Mint(vec![])], &test_env.users[0]);
+
+    assert!(res.unwrap_err().to_string().contains("can only execute"));
+
+    let _ = propose(
+        vec![ExecuteMsg::UpdateSupply(Uint128::from(2_000u128))],
+        &test_env.users[0],
+    )
+    .unwrap();
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Vote {
+                id: 1,
+                approve: true,
+            },
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let execute_proposal = || {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ExecuteProposal { id: 1 },
+            &[],
+            &test_env.users[1],
+        )
+    };
+
+    assert!(execute_proposal()
+        .unwrap_err()
+        .to_string()
+        .contains("Voting is open"));
+
+    test_env.app.increase_time(200);
+
+    let _ = execute_proposal().unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.max_supply, Uint128::from(2_000u128));
+
+    let res: Vec<ProposalResponse> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Proposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res[0].status, "executed");
+    // and doesn't count towards the quorum
+    assert_eq!(res[0].proposal.total_weight, Uint128::from(100u128));
+
+    // titles, descriptions and the number of actions are bounded
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Propose {
+            title: "a".repeat(129),
+            description: String::new(),
+            msgs: vec![ExecuteMsg::Pause],
+        },
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("at most 16 actions"));
+
+    // a passed proposal can't be executed once its execution period is over
+    let _ = propose(vec![ExecuteMsg::Pause], &test_env.users[0]).unwrap();
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Vote {
+                id: 2,
+                approve: true,
+            },
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    test_env.app.increase_time(1_100);
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ExecuteProposal { id: 2 },
+        &[],
+        &test_env.users[1],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("has expired"));
+
+    let res: Vec<ProposalResponse> = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Proposals {
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res[0].status, "expired");
+}
+
+#[test]
+fn test_proposal_deposit() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    for msg in [
+        ExecuteMsg::ConfigureStaking {
+            reward_denom: "uosmo".to_string(),
+            reward_rate: Uint128::zero(),
+            tiers: vec![LockTier {
+                duration: 1_000,
+                multiplier: Decimal::one(),
+            }],
+        },
+        ExecuteMsg::ConfigureGovernance(Some(Governance {
+            voting_period: 100,
+            quorum: Decimal::percent(50),
+            threshold: Decimal::percent(50),
+            execution_period: 1_000,
+            deposit: Uint128::from(10u128),
+        })),
+        ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(100u128),
+            display_units: None,
+        }]),
+    ] {
+        let _ = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.admin)
+            .unwrap();
+    }
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Stake { duration: 1_000 },
+            &[Coin::new(50, &test_env.denom)],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let propose = |funds: &[Coin]| {
+        modules.wasm.execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Propose {
+                title: "Pause".to_string(),
+                description: String::new(),
+                msgs: vec![ExecuteMsg::Pause],
+            },
+            funds,
+            &test_env.users[0],
+        )
+    };
+
+    let res = propose(&[]);
+
+    assert!(res.unwrap_err().to_string().contains("deposit of exactly"));
+
+    let _ = propose(&[Coin::new(10, &test_env.denom)]).unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        40
+    );
+
+    // without votes the proposal misses the quorum, and its deposit is burned
+    test_env.app.increase_time(200);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SettleDeposit { id: 1 },
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.current_supply, Uint128::from(190u128));
+
+    // a proposal that passes gets its deposit back when it is executed
+    let _ = propose(&[Coin::new(10, &test_env.denom)]).unwrap();
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Vote {
+                id: 2,
+                approve: true,
+            },
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    test_env.app.increase_time(200);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ExecuteProposal { id: 2 },
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        40
+    );
+
+    // disabling governance doesn't strand the deposits of open proposals
+    let _ = propose(&[Coin::new(10, &test_env.denom)]).unwrap();
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ConfigureGovernance(None),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(200);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SettleDeposit { id: 3 },
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.current_supply, Uint128::from(180u128));
+}
+
+#[test]
+fn test_mint_window() {
+    let test_env = instantiate_with(|app, _| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000u128)),
+        mint_start: Some(app.get_block_timestamp().plus_seconds(3_600)),
+        mint_end: Some(app.get_block_timestamp().plus_seconds(86_400)),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint = ExecuteMsg::Mint(vec![Receiver {
+        address: test_env.users[0].address(),
+        amount: Uint128::from(100u128),
+        display_units: None,
+    }]);
+
+    // the window hasn't opened yet
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.admin);
+
+    assert!(res.is_err());
+
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(!res.window_open);
+
+    test_env.app.increase_time(3_600);
+
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.admin)
+        .unwrap();
+
+    test_env.app.increase_time(86_400);
+
+    // after the window the supply is final
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.admin);
+
+    assert!(res.is_err());
+
+    let res: MintableResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Mintable)
+        .unwrap();
+
+    assert!(!res.window_open);
+    assert!(!res.cap_reached);
+}
+
+#[test]
+fn test_buckets() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    // reserve 500 for the team and 300 for an airdrop
+    for (label, amount) in [("team", 500u128), ("airdrop", 300u128)] {
+        let _ = modules
+            .wasm
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::SetBucket {
+                    label: label.to_string(),
+                    amount: Uint128::from(amount),
+                },
+                &[],
+                &test_env.admin,
+            )
+            .unwrap();
+    }
+
+    // only 100 is left unallocated, so a 200 bucket doesn't fit
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::SetBucket {
+            label: "liquidity".to_string(),
+            amount: Uint128::from(200u128),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // plain mints are rejected now that buckets exist
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(1u128),
+            display_units: None,
+        }]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // mint 300 from the team bucket
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::MintFromBucket {
+                bucket: "team".to_string(),
+                receivers: vec![Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(300u128),
+                    display_units: None,
+                }],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    // the airdrop bucket can't be overdrawn
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::MintFromBucket {
+            bucket: "airdrop".to_string(),
+            receivers: vec![Receiver {
+                address: test_env.users[1].address(),
+                amount: Uint128::from(301u128),
+                display_units: None,
+            }],
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    let res: BucketsResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Buckets)
+        .unwrap();
+
+    let team = res.buckets.iter().find(|b| b.label == "team").unwrap();
+    assert_eq!(team.minted, Uint128::from(300u128));
+    assert_eq!(team.remaining, Uint128::from(200u128));
+    assert_eq!(res.unallocated, Some(Uint128::from(100u128)));
+
+    // the cap can't be reduced below minted + reserved
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::UpdateSupply(Uint128::from(899u128)),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_bucket_minters() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    for (label, amount) in [("team", 500u128), ("airdrop", 300u128)] {
+        let _ = modules
+            .wasm
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::SetBucket {
+                    label: label.to_string(),
+                    amount: Uint128::from(amount),
+                },
+                &[],
+                &test_env.admin,
+            )
+            .unwrap();
+    }
+
+    // user[0] operates the airdrop
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::SetBucketMinters {
+                label: "airdrop".to_string(),
+                minters: vec![test_env.users[0].address()],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let airdrop = |bucket: &str| ExecuteMsg::MintFromBucket {
+        bucket: bucket.to_string(),
+        receivers: vec![Receiver {
+            address: test_env.users[1].address(),
+            amount: Uint128::from(100u128),
+            display_units: None,
+        }],
+    };
+
+    // user[0] can mint from the airdrop bucket
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &airdrop("airdrop"),
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // but not from the team bucket
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &airdrop("team"),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // and has no other admin rights
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Burn(Some(Uint128::from(1u128))),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // user[1] was never authorized
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &airdrop("airdrop"),
+        &[],
+        &test_env.users[1],
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_roles() {
+    let test_env = instantiate_contract(Uint128::from(1_00u128), Uint128::from(1_000u128));
+
+    let modules = get_modules(&test_env);
+
+    let roles: RolesResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Roles)
+        .unwrap();
+
+    assert_eq!(roles.owners.len(), 1);
+    assert_eq!(roles.owners[0].as_str(), test_env.admin.address());
+    assert!(roles.minters.is_empty());
+
+    // user[0] is a mint bot, user[1] can pause
+    for (role, user) in [(Role::Minter, 0), (Role::Pauser, 1)] {
+        let _ = modules
+            .wasm
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::AddRole {
+                    role,
+                    address: test_env.users[user].address(),
+                },
+                &[],
+                &test_env.admin,
+            )
+            .unwrap();
+    }
+
+    let mint = ExecuteMsg::Mint(vec![Receiver {
+        address: test_env.admin.address(),
+        amount: Uint128::from(100u128),
+        display_units: None,
+    }]);
+
+    // the minter can mint
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.users[0])
+        .unwrap();
+
+    // but can't change the supply or revoke
+    for msg in [
+        ExecuteMsg::UpdateSupply(Uint128::zero()),
+        ExecuteMsg::Revoke,
+    ] {
+        let res = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.users[0]);
+
+        assert!(res.is_err());
+    }
+
+    // the pauser can't mint, but can pause minting
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.users[1]);
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Pause,
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.users[0]);
+
+    assert!(res.is_err());
+
+    let info: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert!(info.paused);
+
+    // the last owner can't be removed
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::RemoveRole {
+            role: Role::Owner,
+            address: test_env.admin.address(),
+        },
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // removing the minter role takes away mint rights
+    for msg in [
+        ExecuteMsg::Unpause,
+        ExecuteMsg::RemoveRole {
+            role: Role::Minter,
+            address: test_env.users[0].address(),
+        },
+    ] {
+        let _ = modules
+            .wasm
+            .execute(&test_env.contract_addr, &msg, &[], &test_env.admin)
+            .unwrap();
+    }
+
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.users[0]);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_mint_fee() {
+    let test_env = instantiate_with(|_, users| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000u128)),
+        mint_fee: Some(MintFee {
+            fee: Coin::new(1_000_000, "uosmo"),
+            collector: Addr::unchecked(users[1].address()),
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::AddRole {
+                role: Role::Minter,
+                address: test_env.users[0].address(),
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let mint = ExecuteMsg::Mint(vec![Receiver {
+        address: test_env.users[0].address(),
+        amount: Uint128::from(100u128),
+        display_units: None,
+    }]);
+
+    // the minter must attach the fee
+    let res = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.users[0]);
+
+    assert!(res.is_err());
+
+    let collector_before = balance(&test_env, &test_env.users[1].address(), "uosmo");
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint,
+            &[Coin::new(1_000_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // the fee is forwarded to the collector
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), "uosmo"),
+        collector_before + 1_000_000
+    );
+
+    // owners don't pay the fee
+    let _ = modules
+        .wasm
+        .execute(&test_env.contract_addr, &mint, &[], &test_env.admin)
+        .unwrap();
+}
+
+#[test]
+fn test_dead_man_switch() {
+    let test_env = instantiate_with(|_, users| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000u128)),
+        dead_man_switch: Some(DeadManSwitch {
+            inactivity: 86_400,
+            fallback: Some(Addr::unchecked(users[1].address())),
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    // the owner is still active
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ClaimOwnership,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // a heartbeat just before the deadline resets the timer
+    test_env.app.increase_time(86_000);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Heartbeat,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    test_env.app.increase_time(1_000);
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ClaimOwnership,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // once the owner has been inactive for long enough anyone can trigger the switch
+    test_env.app.increase_time(86_400);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ClaimOwnership,
+            &[],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    let roles: RolesResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Roles)
+        .unwrap();
+
+    assert_eq!(
+        roles.owners,
+        vec![Addr::unchecked(test_env.users[1].address())]
+    );
+
+    // the previous owner has lost control
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Heartbeat,
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_public_mint() {
+    let test_env = instantiate_with(|_, users| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000_000u128)),
+        public_mint: Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::percent(50),
+            cap: None,
+            merkle_root: None,
+            limit_per_address: Some(Uint128::from(100_000u128)),
+            treasury: Some(Addr::unchecked(users[1].address())),
+            curve: None,
+            anti_snipe: None,
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint = |amount: u128| {
+        ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(amount),
+            display_units: None,
+        }])
+    };
+
+    // the price must be paid
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(60_000),
+        &[Coin::new(1_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let treasury_before = balance(&test_env, &test_env.users[1].address(), "uosmo");
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint(60_000),
+            &[Coin::new(30_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        60_000
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), "uosmo"),
+        treasury_before + 30_000
+    );
+
+    // the second purchase would go over the per-address limit
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(60_000),
+        &[Coin::new(30_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let purchased: Uint128 = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::PublicMinted {
+                address: test_env.users[0].address(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(purchased, Uint128::from(60_000u128));
+
+    // public buyers still can't use privileged actions
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Burn(Some(Uint128::from(1u128))),
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_anti_snipe() {
+    let test_env = instantiate_with(|app, _| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000_000u128)),
+        public_mint: Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::percent(50),
+            cap: None,
+            merkle_root: None,
+            limit_per_address: None,
+            treasury: None,
+            curve: None,
+            anti_snipe: Some(AntiSnipe {
+                until: app.get_block_timestamp().plus_seconds(3_600),
+                max_per_tx: Some(Uint128::from(10_000u128)),
+                max_per_address: None,
+            }),
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint = |amount: u128| {
+        ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(amount),
+            display_units: None,
+        }])
+    };
+
+    // large purchases are blocked at launch
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(20_000),
+        &[Coin::new(10_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint(10_000),
+            &[Coin::new(5_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // and allowed once the opening is over
+    test_env.app.increase_time(3_600);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint(20_000),
+            &[Coin::new(10_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        30_000
+    );
+}
+
+#[test]
+fn test_presale() {
+    let test_env = instantiate_with(|app, users| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000_000u128)),
+        public_mint: Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::one(),
+            cap: None,
+            merkle_root: None,
+            limit_per_address: None,
+            treasury: None,
+            curve: None,
+            anti_snipe: None,
+        }),
+        presale: Some(PresaleMsg {
+            presale: Presale {
+                start: app.get_block_timestamp().plus_seconds(3_600),
+                end: app.get_block_timestamp().plus_seconds(86_400),
+                price: Decimal::percent(50),
+                cap: Some(Uint128::from(100_000u128)),
+                limit_per_address: Some(Uint128::from(60_000u128)),
+            },
+            allowlist: vec![users[0].address()],
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint = |amount: u128| {
+        ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(amount),
+            display_units: None,
+        }])
+    };
+
+    // nothing can be bought before the presale starts
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(10_000),
+        &[Coin::new(5_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    test_env.app.increase_time(3_600);
+
+    // only the allowlist can buy during the presale, at the presale price
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(10_000),
+        &[Coin::new(5_000, "uosmo")],
+        &test_env.users[1],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint(60_000),
+            &[Coin::new(30_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // over the per-address presale limit
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(10_000),
+        &[Coin::new(5_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let phase: PhaseResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Phase)
+        .unwrap();
+
+    assert_eq!(phase.phase, "presale");
+    assert_eq!(phase.presale_minted, Uint128::from(60_000u128));
+
+    test_env.app.increase_time(86_400);
+
+    // the public phase is open to everyone at the public price
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint(10_000),
+            &[Coin::new(10_000, "uosmo")],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let purchased: PurchasesResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::Purchases {
+                address: test_env.users[1].address(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(purchased.presale, Uint128::zero());
+    assert_eq!(purchased.public, Uint128::from(10_000u128));
+}
+
+#[test]
+fn test_merkle_allowlist() {
+    let leaf = |address: String, cap: u128| -> [u8; 32] {
+        Sha256::digest(format!("{}{}", address, cap)).into()
+    };
+
+    let test_env = instantiate_with(|_, users| {
+        let mut pair = [
+            leaf(users[0].address(), 50_000),
+            leaf(users[1].address(), 10_000),
+        ];
+        pair.sort_unstable();
+        let root: [u8; 32] = Sha256::digest(pair.concat()).into();
+
+        InstantiateMsg {
+            max_supply: Some(Uint128::from(1_000_000u128)),
+            public_mint: Some(PublicMint {
+                payment_denom: "uosmo".to_string(),
+                price: Decimal::one(),
+                limit_per_address: None,
+                cap: None,
+                merkle_root: Some(hex::encode(root)),
+                treasury: None,
+                curve: None,
+                anti_snipe: None,
+            }),
+            ..base_msg()
+        }
+    });
+
+    let modules = get_modules(&test_env);
+
+    let mint = |amount: u128, cap: u128, proof: [u8; 32]| ExecuteMsg::MintWithProof {
+        receivers: vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(amount),
+            display_units: None,
+        }],
+        cap: Uint128::from(cap),
+        proof: vec![hex::encode(proof)],
+    };
+    let proof = leaf(test_env.users[1].address(), 10_000);
+
+    // a proof is required
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(10_000u128),
+            display_units: None,
+        }]),
+        &[Coin::new(10_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // the cap is part of the leaf and can't be raised
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(60_000, 60_000, proof),
+        &[Coin::new(60_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &mint(40_000, 50_000, proof),
+            &[Coin::new(40_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        40_000
+    );
+
+    // over the leaf cap
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &mint(20_000, 50_000, proof),
+        &[Coin::new(20_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_proceeds_vesting() {
+    let test_env = instantiate_with(|app, users| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000_000u128)),
+        public_mint: Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::percent(50),
+            limit_per_address: None,
+            cap: None,
+            merkle_root: None,
+            treasury: Some(Addr::unchecked(users[0].address())),
+            curve: None,
+            anti_snipe: None,
+        }),
+        // half of the proceeds vest to user[1] over 100 seconds
+        proceeds_vesting: Some(ProceedsVesting {
+            share: Decimal::percent(50),
+            beneficiary: Addr::unchecked(users[1].address()),
+            start: app.get_block_timestamp(),
+            cliff: 0,
+            duration: 100,
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    let treasury_before = balance(&test_env, &test_env.users[0].address(), "uosmo");
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[1].address(),
+                amount: Uint128::from(60_000u128),
+                display_units: None,
+            }]),
+            &[Coin::new(30_000, "uosmo")],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), "uosmo"),
+        treasury_before + 15_000
+    );
+
+    // only the beneficiary can claim
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::ClaimVested,
+        &[],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    test_env.app.increase_time(100);
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ClaimVested,
+            &[],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let res: Option<ProceedsVestingResponse> = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::ProceedsVesting)
+        .unwrap();
+    let res = res.unwrap();
+
+    assert_eq!(res.deposited, Coin::new(15_000, "uosmo"));
+    assert_eq!(res.claimed, Coin::new(15_000, "uosmo"));
+}
+
+#[test]
+fn test_bonding_curve() {
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000_000_000_000u128)),
+        public_mint: Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::one(),
+            cap: None,
+            merkle_root: None,
+            limit_per_address: None,
+            treasury: None,
+            // the price goes up by 1 uosmo for every 1_000_000 minted
+            curve: Some(Curve::Linear {
+                slope: Decimal::from_ratio(1u128, 1_000_000u128),
+            }),
+            anti_snipe: None,
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    // each unit is priced at the supply it is minted at, so the first one costs the base price
+    let res: MintCostResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::MintCost {
+                amount: Uint128::one(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.cost, Coin::new(1, "uosmo"));
+
+    let res: MintCostResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::MintCost {
+                amount: Uint128::from(1_000_000u128),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.spot_price, Decimal256::one());
+    assert_eq!(res.cost, Coin::new(1_500_000, "uosmo"));
+
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![Receiver {
+                address: test_env.users[0].address(),
+                amount: Uint128::from(1_000_000u128),
+                display_units: None,
+            }]),
+            &[Coin::new(1_500_000, "uosmo")],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    // the price has gone up and the payment is held as a reserve
+    let res: MintCostResponse = modules
+        .wasm
+        .query(
+            &test_env.contract_addr,
+            &QueryMsg::MintCost {
+                amount: Uint128::from(1_000_000u128),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.spot_price, Decimal256::from_ratio(2u128, 1u128));
+    assert_eq!(res.cost, Coin::new(2_500_000, "uosmo"));
+
+    let reserve: Coin = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Reserve)
+        .unwrap();
+
+    assert_eq!(reserve, Coin::new(1_500_000, "uosmo"));
+}
+
+#[test]
+fn test_redeem() {
+    let test_env = instantiate_with(|_, _| InstantiateMsg {
+        max_supply: Some(Uint128::from(1_000_000_000_000u128)),
+        public_mint: Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::one(),
+            cap: None,
+            merkle_root: None,
+            limit_per_address: None,
+            treasury: None,
+            curve: None,
+            anti_snipe: None,
+        }),
+        ..base_msg()
+    });
+
+    let modules = get_modules(&test_env);
+
+    // users[0] and users[1] both buy in, funding the reserve with 2_000_000 uosmo
+    for user in &test_env.users {
+        let _ = modules
+            .wasm
+            .execute(
+                &test_env.contract_addr,
+                &ExecuteMsg::Mint(vec![Receiver {
+                    address: user.address(),
+                    amount: Uint128::from(1_000_000u128),
+                    display_units: None,
+                }]),
+                &[Coin::new(1_000_000, "uosmo")],
+                user,
+            )
+            .unwrap();
+    }
+
+    // tokens staked in the contract are still owned by their holder, so they count towards the
+    // supply the reserve is shared across
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::ConfigureStaking {
+                reward_denom: "uosmo".to_string(),
+                reward_rate: Uint128::zero(),
+                tiers: vec![LockTier {
+                    duration: 1_000,
+                    multiplier: Decimal::one(),
+                }],
+            },
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Stake { duration: 1_000 },
+            &[Coin::new(1_000_000, &test_env.denom)],
+            &test_env.users[1],
+        )
+        .unwrap();
+
+    let osmo_before = balance(&test_env, &test_env.users[0].address(), "uosmo");
+
+    // redeeming a quarter of the supply pays out a quarter of the reserve
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Redeem,
+            &[Coin::new(500_000, &test_env.denom)],
+            &test_env.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), "uosmo"),
+        osmo_before + 500_000
+    );
+
+    let reserve: Coin = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::Reserve)
+        .unwrap();
+
+    assert_eq!(reserve, Coin::new(1_500_000, "uosmo"));
+
+    let info: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    // the redeemed tokens stay counted as minted, so they can't be minted again past the cap
+    assert_eq!(info.minted, Uint128::from(2_000_000u128));
+    assert_eq!(info.burned, Uint128::from(500_000u128));
+
+    // other denoms can't be redeemed
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Redeem,
+        &[Coin::new(500_000, "uosmo")],
+        &test_env.users[0],
+    );
+
+    assert!(res.is_err());
+
+    // and nothing can be redeemed while paused
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Pause,
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Redeem,
+        &[Coin::new(500_000, &test_env.denom)],
+        &test_env.users[0],
+    );
+
+    assert!(res.unwrap_err().to_string().contains("paused"));
+}
+
+#[test]
+fn test_display_units() {
+    let test_env = instantiate_contract(Uint128::zero(), Uint128::from(10_000_000u128));
+
+    let modules = get_modules(&test_env);
+
+    // 2 whole tokens and 500 base units
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::Mint(vec![
+                Receiver {
+                    address: test_env.users[0].address(),
+                    amount: Uint128::from(2u128),
+                    display_units: Some(true),
+                },
+                Receiver {
+                    address: test_env.users[1].address(),
+                    amount: Uint128::from(500u128),
+                    display_units: None,
+                },
+            ]),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    assert_eq!(
+        balance(&test_env, &test_env.users[0].address(), &test_env.denom),
+        2_000_000
+    );
+    assert_eq!(
+        balance(&test_env, &test_env.users[1].address(), &test_env.denom),
+        500
+    );
+
+    // 10 whole tokens would go over the cap
+    let res = modules.wasm.execute(
+        &test_env.contract_addr,
+        &ExecuteMsg::Mint(vec![Receiver {
+            address: test_env.users[0].address(),
+            amount: Uint128::from(10u128),
+            display_units: Some(true),
+        }]),
+        &[],
+        &test_env.admin,
+    );
+
+    assert!(res.is_err());
+
+    // the cap can be raised in whole tokens too
+    let _ = modules
+        .wasm
+        .execute(
+            &test_env.contract_addr,
+            &ExecuteMsg::UpdateSupplyDisplay(Uint128::from(20u128)),
+            &[],
+            &test_env.admin,
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = modules
+        .wasm
+        .query(&test_env.contract_addr, &QueryMsg::TokenInfo)
+        .unwrap();
+
+    assert_eq!(res.max_supply, Uint128::from(20_000_000u128));
+    assert_eq!(res.exponent, 6);
+}
@@ -0,0 +1,54 @@
+// the tokenfactory messages the contract sends
+use cosmwasm_std::{CosmosMsg, StdResult, SubMsgResult, Uint128};
+use osmosis_std::types::{
+    cosmos::base::v1beta1::Coin as ProtoCoin,
+    osmosis::tokenfactory::v1beta1::{
+        MsgBurn, MsgChangeAdmin, MsgCreateDenom, MsgCreateDenomResponse, MsgMint,
+    },
+};
+
+pub fn create_denom(sender: &str, subdenom: &str) -> CosmosMsg {
+    MsgCreateDenom {
+        sender: sender.to_string(),
+        subdenom: subdenom.to_string(),
+    }
+    .into()
+}
+
+pub fn mint(sender: &str, denom: &str, amount: Uint128, recipient: &str) -> CosmosMsg {
+    MsgMint {
+        sender: sender.to_string(),
+        amount: Some(ProtoCoin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }),
+        mint_to_address: recipient.to_string(),
+    }
+    .into()
+}
+
+pub fn burn(sender: &str, denom: &str, amount: Uint128, from: &str) -> CosmosMsg {
+    MsgBurn {
+        sender: sender.to_string(),
+        amount: Some(ProtoCoin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }),
+        burn_from_address: from.to_string(),
+    }
+    .into()
+}
+
+pub fn change_admin(sender: &str, denom: &str, new_admin: &str) -> CosmosMsg {
+    MsgChangeAdmin {
+        sender: sender.to_string(),
+        denom: denom.to_string(),
+        new_admin: new_admin.to_string(),
+    }
+    .into()
+}
+
+// the denom created by a create_denom submessage
+pub fn created_denom(result: SubMsgResult) -> StdResult<String> {
+    Ok(MsgCreateDenomResponse::try_from(result)?.new_token_denom)
+}
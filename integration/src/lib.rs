@@ -0,0 +1,6 @@
+// end to end scenarios that deploy the workspace contracts together. the wasm files must be
+// built first with `cargo wasm`
+#[cfg(test)]
+mod suite;
+#[cfg(test)]
+mod tests;
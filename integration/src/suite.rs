@@ -0,0 +1,143 @@
+use assetlist::{msg::InstantiateMsg as AssetlistInstantiateMsg, state::Config as AssetlistConfig};
+use cosmwasm_std::Coin;
+use factory::{helpers, msg::InstantiateMsg as FactoryInstantiateMsg};
+use osmosis_test_tube::{
+    osmosis_std::types::cosmos::{
+        bank::v1beta1::{MsgSend, MsgSendResponse, QueryBalanceRequest},
+        base::v1beta1::Coin as ProtoCoin,
+    },
+    Account, Bank, Module, OsmosisTestApp, RunnerExecuteResult, SigningAccount, Wasm,
+};
+use serde::Serialize;
+
+pub struct Suite {
+    pub app: OsmosisTestApp,
+    pub admin: SigningAccount,
+    pub users: Vec<SigningAccount>,
+    pub assetlist: String,
+}
+
+impl Suite {
+    // a chain with a funded admin, two users and an assetlist charging a 1 OSMO listing fee
+    pub fn new() -> Self {
+        let app = OsmosisTestApp::new();
+
+        let admin = app
+            .init_account(&[Coin::new(1_000_000_000_000, "uosmo")])
+            .unwrap();
+
+        let users = app
+            .init_accounts(&[Coin::new(1_000_000_000, "uosmo")], 2)
+            .unwrap();
+
+        let mut suite = Suite {
+            app,
+            admin,
+            users,
+            assetlist: String::default(),
+        };
+
+        suite.assetlist = suite.deploy(
+            "assetlist",
+            &AssetlistInstantiateMsg(AssetlistConfig {
+                add_permissioned: None,
+                remove_permissioned: None,
+                required_fields: None,
+                fee: Some(vec![Coin::new(1_000_000, "uosmo")]),
+                deposit: None,
+                update_fee: None,
+                renewal_fee: None,
+                listing_ttl: None,
+                fee_collector: None,
+                admins: None,
+                owner: None,
+                confirm_threshold: None,
+                confirm_period: None,
+                review: None,
+                extension_limits: None,
+            }),
+        );
+
+        suite
+    }
+
+    pub fn wasm(&self) -> Wasm<'_, OsmosisTestApp> {
+        Wasm::new(&self.app)
+    }
+
+    // deploys a factory and returns its address and denom
+    pub fn create_token(&self, msg: &FactoryInstantiateMsg) -> (String, String) {
+        let contract = self.deploy("factory", msg);
+        let denom = helpers::denom(
+            &contract,
+            &msg.symbol,
+            msg.subdenom.as_deref(),
+            msg.tfa_prefix.unwrap_or_default(),
+        );
+        (contract, denom)
+    }
+
+    pub fn balance(&self, address: &str, denom: &str) -> u128 {
+        Bank::new(&self.app)
+            .query_balance(&QueryBalanceRequest {
+                address: address.to_string(),
+                denom: denom.to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap()
+            .amount
+            .parse::<u128>()
+            .unwrap()
+    }
+
+    // a plain bank send, which goes through the token's before-send hook
+    pub fn send(
+        &self,
+        from: &SigningAccount,
+        to: &str,
+        coin: Coin,
+    ) -> RunnerExecuteResult<MsgSendResponse> {
+        Bank::new(&self.app).send(
+            MsgSend {
+                from_address: from.address(),
+                to_address: to.to_string(),
+                amount: vec![ProtoCoin {
+                    denom: coin.denom,
+                    amount: coin.amount.to_string(),
+                }],
+            },
+            from,
+        )
+    }
+
+    fn deploy(&self, name: &str, msg: &impl Serialize) -> String {
+        let path = format!(
+            "{}/../target/wasm32-unknown-unknown/release/{}.wasm",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        let wasm_byte_code = std::fs::read(path)
+            .unwrap_or_else(|_| panic!("could not read wasm file - run `cargo wasm` first"));
+
+        let code_id = self
+            .wasm()
+            .store_code(&wasm_byte_code, None, &self.admin)
+            .unwrap()
+            .data
+            .code_id;
+
+        self.wasm()
+            .instantiate(
+                code_id,
+                msg,
+                Some(&self.admin.address()),
+                Some(name),
+                &[],
+                &self.admin,
+            )
+            .unwrap()
+            .data
+            .address
+    }
+}
@@ -0,0 +1,374 @@
+use crate::suite::Suite;
+use assetlist::{
+    msg::{ListingQuery, QueryMsg as AssetlistQueryMsg},
+    state::Metadata,
+};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use factory::{
+    msg::{
+        AssetlistRegistration, ExecuteMsg, InstantiateMsg, MintableResponse, QueryMsg, Receiver,
+        TokenInfoResponse, TransferBurnMsg,
+    },
+    state::{ClPool, Curve, PublicMint},
+};
+use osmosis_test_tube::{
+    osmosis_std::types::{
+        cosmos::base::v1beta1::Coin as ProtoCoin,
+        osmosis::poolmanager::v1beta1::{MsgSwapExactAmountIn, SwapAmountInRoute},
+    },
+    Account, Module, PoolManager,
+};
+
+fn token(symbol: &str, public_mint: Option<PublicMint>) -> InstantiateMsg {
+    InstantiateMsg {
+        symbol: symbol.to_string(),
+        subdenom: None,
+        tfa_prefix: None,
+        symbol_rules: None,
+        initial_supply: None,
+        max_supply: Some(Uint128::from(1_000_000_000u128)),
+        admin: None,
+        pre_launch: None,
+        timelock: None,
+        mint_start: None,
+        mint_end: None,
+        mint_fee: None,
+        dead_man_switch: None,
+        exponent: None,
+        public_mint,
+        proceeds_vesting: None,
+        presale: None,
+        assetlist: None,
+        clawback: None,
+        null_address: None,
+        max_wallet: None,
+        transfer_burn: None,
+        project_info: None,
+        multisig: None,
+    }
+}
+
+fn metadata(symbol: &str) -> Metadata {
+    Metadata {
+        symbol: symbol.to_string(),
+        exp: Some(6),
+        logo: None,
+        chain: Some("osmosis-1".to_string()),
+        extensions: None,
+    }
+}
+
+fn mint(address: String, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Mint(vec![Receiver {
+        address,
+        amount: Uint128::from(amount),
+        display_units: None,
+    }])
+}
+
+// create -> public sale -> team mint -> finalize, which lists the token and revokes the admin
+#[test]
+fn test_launch() {
+    let suite = Suite::new();
+    let wasm = suite.wasm();
+
+    let (factory, denom) = suite.create_token(&token(
+        "LAUNCH",
+        Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::one(),
+            limit_per_address: None,
+            cap: None,
+            merkle_root: None,
+            treasury: Some(Addr::unchecked(suite.admin.address())),
+            curve: None,
+            anti_snipe: None,
+        }),
+    ));
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &mint(suite.users[0].address(), 1_000_000),
+            &[Coin::new(1_000_000, "uosmo")],
+            &suite.users[0],
+        )
+        .unwrap();
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &mint(suite.users[1].address(), 100_000_000),
+            &[],
+            &suite.admin,
+        )
+        .unwrap();
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &ExecuteMsg::Finalize {
+                recipient: suite.admin.address(),
+                assetlist: Some(AssetlistRegistration {
+                    contract: suite.assetlist.clone(),
+                    metadata: metadata("LAUNCH"),
+                    fee: vec![Coin::new(1_000_000, "uosmo")],
+                }),
+            },
+            &[Coin::new(1_000_000, "uosmo")],
+            &suite.admin,
+        )
+        .unwrap();
+
+    assert_eq!(suite.balance(&suite.users[0].address(), &denom), 1_000_000);
+    assert_eq!(
+        suite.balance(&suite.users[1].address(), &denom),
+        100_000_000
+    );
+    assert_eq!(suite.balance(&suite.admin.address(), &denom), 899_000_000);
+
+    let res: Vec<(String, Metadata)> = wasm
+        .query(
+            &suite.assetlist,
+            &AssetlistQueryMsg::Listing(ListingQuery::Denom(vec![denom.clone()])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1, metadata("LAUNCH"));
+
+    let res: MintableResponse = wasm.query(&factory, &QueryMsg::Mintable).unwrap();
+
+    assert!(res.revoked);
+    assert!(res.cap_reached);
+
+    // nothing can be minted once the token is finalized, publicly or otherwise
+    let res = wasm.execute(
+        &factory,
+        &mint(suite.users[0].address(), 1),
+        &[Coin::new(1, "uosmo")],
+        &suite.users[0],
+    );
+
+    assert!(res.is_err());
+}
+
+// buyers on a bonding curve fund a reserve that holders can redeem against before the token
+// is listed and finalized
+#[test]
+fn test_reserve_launch() {
+    let suite = Suite::new();
+    let wasm = suite.wasm();
+
+    let (factory, denom) = suite.create_token(&token(
+        "CURVE",
+        Some(PublicMint {
+            payment_denom: "uosmo".to_string(),
+            price: Decimal::one(),
+            limit_per_address: None,
+            cap: None,
+            merkle_root: None,
+            treasury: None,
+            curve: Some(Curve::Linear {
+                slope: Decimal::from_ratio(1u128, 1_000_000u128),
+            }),
+            anti_snipe: None,
+        }),
+    ));
+
+    // 1 OSMO for the first token, rising to 2 OSMO at 1_000_000 minted
+    for (user, cost) in [
+        (&suite.users[0], 1_500_000u128),
+        (&suite.users[1], 2_500_000),
+    ] {
+        let _ = wasm
+            .execute(
+                &factory,
+                &mint(user.address(), 1_000_000),
+                &[Coin::new(cost, "uosmo")],
+                user,
+            )
+            .unwrap();
+    }
+
+    // the first buyer exits with half of the reserve
+    let before = suite.balance(&suite.users[0].address(), "uosmo");
+    let _ = wasm
+        .execute(
+            &factory,
+            &ExecuteMsg::Redeem,
+            &[Coin::new(1_000_000, denom.clone())],
+            &suite.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(
+        suite.balance(&suite.users[0].address(), "uosmo"),
+        before + 2_000_000
+    );
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &ExecuteMsg::Finalize {
+                recipient: suite.admin.address(),
+                assetlist: Some(AssetlistRegistration {
+                    contract: suite.assetlist.clone(),
+                    metadata: metadata("CURVE"),
+                    fee: vec![Coin::new(1_000_000, "uosmo")],
+                }),
+            },
+            &[Coin::new(1_000_000, "uosmo")],
+            &suite.admin,
+        )
+        .unwrap();
+
+    let res: TokenInfoResponse = wasm.query(&factory, &QueryMsg::TokenInfo).unwrap();
+
+    assert_eq!(res.minted, Uint128::from(1_000_000_000u128));
+
+    let res: Vec<(String, Metadata)> = wasm
+        .query(
+            &suite.assetlist,
+            &AssetlistQueryMsg::Listing(ListingQuery::Denom(vec![denom])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1, metadata("CURVE"));
+}
+
+// create with a transfer burn -> seed a concentrated liquidity pool -> lock the position ->
+// finalize, which lists the token and revokes the admin
+#[test]
+fn test_pool_launch() {
+    let suite = Suite::new();
+    let wasm = suite.wasm();
+
+    // the pool the contract creates is exempted when it is created, so nothing else is listed
+    let (factory, denom) = suite.create_token(&InstantiateMsg {
+        transfer_burn: Some(TransferBurnMsg {
+            rate: Decimal::percent(1),
+            exempt: vec![],
+        }),
+        ..token("POOL", None)
+    });
+
+    for (address, amount) in [
+        (factory.clone(), 100_000_000),
+        (suite.users[0].address(), 10_000_000),
+    ] {
+        let _ = wasm
+            .execute(&factory, &mint(address, amount), &[], &suite.admin)
+            .unwrap();
+    }
+
+    // the quote and the pool creation fee are paid from the contract's balance
+    let _ = suite
+        .send(&suite.admin, &factory, Coin::new(1_100_000_000, "uosmo"))
+        .unwrap();
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &ExecuteMsg::CreateClPool {
+                amount: Uint128::from(100_000_000u128),
+                quote: Coin::new(10_000_000, "uosmo"),
+                tick_spacing: 100,
+                spread_factor: Decimal::permille(2),
+                locked_until: None,
+            },
+            &[],
+            &suite.admin,
+        )
+        .unwrap();
+
+    let res: Option<ClPool> = wasm.query(&factory, &QueryMsg::ClPool).unwrap();
+    let pool = res.unwrap();
+
+    assert!(pool.position_id.is_some());
+    assert_eq!(pool.locked_until, None);
+
+    // the launch liquidity is locked for good
+    let res = wasm.execute(&factory, &ExecuteMsg::WithdrawPosition, &[], &suite.admin);
+
+    assert!(res.unwrap_err().to_string().contains("locked permanently"));
+
+    // holders can't send the token directly while the burn applies
+    let res = suite.send(
+        &suite.users[0],
+        &suite.users[1].address(),
+        Coin::new(1_000_000, denom.clone()),
+    );
+
+    assert!(res.is_err());
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &ExecuteMsg::TransferFunds {
+                recipient: suite.users[1].address(),
+            },
+            &[Coin::new(1_000_000, denom.clone())],
+            &suite.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(suite.balance(&suite.users[0].address(), &denom), 9_000_000);
+    assert_eq!(suite.balance(&suite.users[1].address(), &denom), 990_000);
+
+    // swaps move the token to and from the exempt pool, so they go through directly
+    let osmo_before = suite.balance(&suite.users[0].address(), "uosmo");
+    let _ = PoolManager::new(&suite.app)
+        .swap_exact_amount_in(
+            MsgSwapExactAmountIn {
+                sender: suite.users[0].address(),
+                routes: vec![SwapAmountInRoute {
+                    pool_id: pool.pool_id,
+                    token_out_denom: "uosmo".to_string(),
+                }],
+                token_in: Some(ProtoCoin {
+                    denom: denom.clone(),
+                    amount: "1000000".to_string(),
+                }),
+                token_out_min_amount: "1".to_string(),
+            },
+            &suite.users[0],
+        )
+        .unwrap();
+
+    assert_eq!(suite.balance(&suite.users[0].address(), &denom), 8_000_000);
+    assert!(suite.balance(&suite.users[0].address(), "uosmo") > osmo_before);
+
+    let _ = wasm
+        .execute(
+            &factory,
+            &ExecuteMsg::Finalize {
+                recipient: suite.admin.address(),
+                assetlist: Some(AssetlistRegistration {
+                    contract: suite.assetlist.clone(),
+                    metadata: metadata("POOL"),
+                    fee: vec![Coin::new(1_000_000, "uosmo")],
+                }),
+            },
+            &[Coin::new(1_000_000, "uosmo")],
+            &suite.admin,
+        )
+        .unwrap();
+
+    let res: Vec<(String, Metadata)> = wasm
+        .query(
+            &suite.assetlist,
+            &AssetlistQueryMsg::Listing(ListingQuery::Denom(vec![denom])),
+        )
+        .unwrap();
+
+    assert_eq!(res[0].1, metadata("POOL"));
+
+    let res: MintableResponse = wasm.query(&factory, &QueryMsg::Mintable).unwrap();
+
+    assert!(res.revoked);
+
+    // revoking leaves the pool and its locked position in place
+    let res: Option<ClPool> = wasm.query(&factory, &QueryMsg::ClPool).unwrap();
+
+    assert_eq!(res, Some(pool));
+}